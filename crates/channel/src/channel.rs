@@ -9,9 +9,9 @@ use std::sync::Arc;
 pub use channel_buffer::{ChannelBuffer, ChannelBufferEvent, ACKNOWLEDGE_DEBOUNCE_INTERVAL};
 pub use channel_chat::{
     mentions_to_proto, ChannelChat, ChannelChatEvent, ChannelMessage, ChannelMessageId,
-    MessageParams,
+    MessageParams, RATE_LIMITED_RESEND_DELAY,
 };
-pub use channel_store::{Channel, ChannelEvent, ChannelMembership, ChannelStore};
+pub use channel_store::{Channel, ChannelEvent, ChannelMembership, ChannelStore, TYPING_TIMEOUT};
 
 #[cfg(test)]
 mod channel_store_tests;
@@ -6,20 +6,29 @@ use client::{
     ChannelId, Client, Subscription, TypedEnvelope, UserId,
 };
 use collections::HashSet;
+use fs::{Fs, RemoveOptions};
 use futures::lock::Mutex;
 use gpui::{
     AppContext, AsyncAppContext, Context, EventEmitter, Model, ModelContext, Task, WeakModel,
 };
 use rand::prelude::*;
-use rpc::AnyProtoClient;
+use rpc::{AnyProtoClient, ErrorCode, ErrorExt, RpcError};
+use serde::{Deserialize, Serialize};
 use std::{
     ops::{ControlFlow, Range},
+    path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 use sum_tree::{Bias, SumTree};
 use time::OffsetDateTime;
 use util::{post_inc, ResultExt as _, TryFutureExt};
 
+/// How long to wait before resending a message that the server rate limited. The delay
+/// doubles for every further rate limited attempt, up to [`MAX_RATE_LIMITED_RESEND_DELAY`].
+pub const RATE_LIMITED_RESEND_DELAY: Duration = Duration::from_secs(1);
+const MAX_RATE_LIMITED_RESEND_DELAY: Duration = Duration::from_secs(30);
+
 pub struct ChannelChat {
     pub channel_id: ChannelId,
     messages: SumTree<ChannelMessage>,
@@ -32,6 +41,7 @@ pub struct ChannelChat {
     user_store: Model<UserStore>,
     rpc: Arc<Client>,
     outgoing_messages_lock: Arc<Mutex<()>>,
+    outbox: Option<Arc<Outbox>>,
     rng: StdRng,
     _subscription: Subscription,
 }
@@ -53,6 +63,9 @@ pub struct ChannelMessage {
     pub mentions: Vec<(Range<usize>, UserId)>,
     pub reply_to_message_id: Option<u64>,
     pub edited_at: Option<OffsetDateTime>,
+    /// Whether the message was deleted. Deleted messages have an empty body, and are
+    /// kept so that their position in the conversation is preserved.
+    pub deleted: bool,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -96,6 +109,79 @@ pub enum ChannelChatEvent {
 }
 
 impl EventEmitter<ChannelChatEvent> for ChannelChat {}
+
+/// Persists the messages that haven't been acknowledged by the server yet, so
+/// that they can be resent if the app quits before they are delivered.
+struct Outbox {
+    fs: Arc<dyn Fs>,
+    path: PathBuf,
+    pending_write: parking_lot::Mutex<Option<Vec<OutboxMessage>>>,
+    write_lock: Mutex<()>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct OutboxMessage {
+    body: String,
+    mentions: Vec<(Range<usize>, UserId)>,
+    nonce: u128,
+    reply_to_message_id: Option<u64>,
+}
+
+impl Outbox {
+    fn new(fs: Arc<dyn Fs>, user_id: UserId, channel_id: ChannelId) -> Self {
+        Self {
+            fs,
+            path: paths::chat_outbox_dir()
+                .join(user_id.to_string())
+                .join(format!("{}.json", channel_id.0)),
+            pending_write: Default::default(),
+            write_lock: Default::default(),
+        }
+    }
+
+    async fn load(&self) -> Vec<OutboxMessage> {
+        if !self.fs.is_file(&self.path).await {
+            return Vec::new();
+        }
+        self.fs
+            .load(&self.path)
+            .await
+            .and_then(|json| Ok(serde_json::from_str(&json)?))
+            .log_err()
+            .unwrap_or_default()
+    }
+
+    fn save(self: &Arc<Self>, messages: Vec<OutboxMessage>, cx: &AppContext) -> Task<Result<()>> {
+        *self.pending_write.lock() = Some(messages);
+        let this = self.clone();
+        cx.background_executor().spawn(async move {
+            // Writes may complete out of order, so each write saves the most recent messages
+            // and later writes are skipped if there is nothing new to save.
+            let _guard = this.write_lock.lock().await;
+            let Some(messages) = this.pending_write.lock().take() else {
+                return Ok(());
+            };
+            if messages.is_empty() {
+                this.fs
+                    .remove_file(
+                        &this.path,
+                        RemoveOptions {
+                            recursive: false,
+                            ignore_if_not_exists: true,
+                        },
+                    )
+                    .await
+            } else {
+                if let Some(dir) = this.path.parent() {
+                    this.fs.create_dir(dir).await?;
+                }
+                this.fs
+                    .atomic_write(this.path.clone(), serde_json::to_string(&messages)?)
+                    .await
+            }
+        })
+    }
+}
 pub fn init(client: &AnyProtoClient) {
     client.add_model_message_handler(ChannelChat::handle_message_sent);
     client.add_model_message_handler(ChannelChat::handle_message_removed);
@@ -112,6 +198,11 @@ impl ChannelChat {
     ) -> Result<Model<Self>> {
         let channel_id = channel.id;
         let subscription = client.subscribe_to_entity(channel_id.0).unwrap();
+        let outbox = cx.update(|cx| {
+            let fs = <dyn Fs>::try_global(cx)?;
+            let user_id = user_store.read(cx).current_user()?.id;
+            Some(Arc::new(Outbox::new(fs, user_id, channel_id)))
+        })?;
 
         let response = client
             .request(proto::JoinChannelChat {
@@ -127,6 +218,7 @@ impl ChannelChat {
                 channel_store,
                 rpc: client.clone(),
                 outgoing_messages_lock: Default::default(),
+                outbox: outbox.clone(),
                 messages: Default::default(),
                 acknowledged_message_ids: Default::default(),
                 loaded_all_messages: false,
@@ -146,6 +238,30 @@ impl ChannelChat {
             &mut cx,
         )
         .await?;
+
+        // Resend any messages that were still pending when the app last quit.
+        if let Some(outbox) = outbox {
+            let outbox_messages = outbox.load().await;
+            handle.update(&mut cx, |this, cx| {
+                for message in outbox_messages {
+                    if this.messages.iter().any(|m| m.nonce == message.nonce) {
+                        continue;
+                    }
+                    this.insert_pending_message(
+                        &MessageParams {
+                            text: message.body,
+                            mentions: message.mentions,
+                            reply_to_message_id: message.reply_to_message_id,
+                        },
+                        message.nonce,
+                        cx,
+                    )
+                    .log_err();
+                }
+                this.resend_pending_messages(Duration::ZERO, cx)
+                    .detach_and_log_err(cx);
+            })?;
+        }
         Ok(handle)
     }
 
@@ -173,6 +289,60 @@ impl ChannelChat {
         message: MessageParams,
         cx: &mut ModelContext<Self>,
     ) -> Result<Task<Result<u64>>> {
+        let nonce = self.rng.gen();
+        let pending_id = self.insert_pending_message(&message, nonce, cx)?;
+        let channel_id = self.channel_id;
+        let user_store = self.user_store.clone();
+        let rpc = self.rpc.clone();
+        let outgoing_messages_lock = self.outgoing_messages_lock.clone();
+
+        Ok(cx.spawn(move |this, mut cx| async move {
+            let outgoing_message_guard = outgoing_messages_lock.lock().await;
+            let request = rpc.request(proto::SendChannelMessage {
+                channel_id: channel_id.0,
+                body: message.text,
+                nonce: Some(nonce.into()),
+                mentions: mentions_to_proto(&message.mentions),
+                reply_to_message_id: message.reply_to_message_id,
+            });
+            let response = match request.await {
+                Ok(response) => response,
+                Err(error) => {
+                    drop(outgoing_message_guard);
+                    this.update(&mut cx, |this, cx| {
+                        if is_rate_limited(&error) {
+                            this.resend_pending_messages(RATE_LIMITED_RESEND_DELAY, cx)
+                                .detach_and_log_err(cx);
+                        } else if !should_resend(&error) {
+                            this.remove_pending_message(pending_id, cx);
+                        }
+                    })?;
+                    return Err(error);
+                }
+            };
+            drop(outgoing_message_guard);
+            let response = response.message.ok_or_else(|| anyhow!("invalid message"))?;
+            let id = response.id;
+            let message = ChannelMessage::from_proto(response, &user_store, &mut cx).await?;
+            this.update(&mut cx, |this, cx| {
+                this.insert_messages(SumTree::from_item(message, &()), cx);
+                this.save_outbox(cx);
+                if this.first_loaded_message_id.is_none() {
+                    this.first_loaded_message_id = Some(id);
+                }
+            })?;
+            Ok(id)
+        }))
+    }
+
+    /// Adds a message to the chat that hasn't been acknowledged by the server yet, and
+    /// saves it to the outbox.
+    fn insert_pending_message(
+        &mut self,
+        message: &MessageParams,
+        nonce: u128,
+        cx: &mut ModelContext<Self>,
+    ) -> Result<ChannelMessageId> {
         if message.text.trim().is_empty() {
             Err(anyhow!("message body can't be empty"))?;
         }
@@ -183,9 +353,7 @@ impl ChannelChat {
             .current_user()
             .ok_or_else(|| anyhow!("current_user is not present"))?;
 
-        let channel_id = self.channel_id;
         let pending_id = ChannelMessageId::Pending(post_inc(&mut self.next_pending_message_id));
-        let nonce = self.rng.gen();
         self.insert_messages(
             SumTree::from_item(
                 ChannelMessage {
@@ -197,38 +365,14 @@ impl ChannelChat {
                     nonce,
                     reply_to_message_id: message.reply_to_message_id,
                     edited_at: None,
+                    deleted: false,
                 },
                 &(),
             ),
             cx,
         );
-        let user_store = self.user_store.clone();
-        let rpc = self.rpc.clone();
-        let outgoing_messages_lock = self.outgoing_messages_lock.clone();
-
-        // todo - handle messages that fail to send (e.g. >1024 chars)
-        Ok(cx.spawn(move |this, mut cx| async move {
-            let outgoing_message_guard = outgoing_messages_lock.lock().await;
-            let request = rpc.request(proto::SendChannelMessage {
-                channel_id: channel_id.0,
-                body: message.text,
-                nonce: Some(nonce.into()),
-                mentions: mentions_to_proto(&message.mentions),
-                reply_to_message_id: message.reply_to_message_id,
-            });
-            let response = request.await?;
-            drop(outgoing_message_guard);
-            let response = response.message.ok_or_else(|| anyhow!("invalid message"))?;
-            let id = response.id;
-            let message = ChannelMessage::from_proto(response, &user_store, &mut cx).await?;
-            this.update(&mut cx, |this, cx| {
-                this.insert_messages(SumTree::from_item(message, &()), cx);
-                if this.first_loaded_message_id.is_none() {
-                    this.first_loaded_message_id = Some(id);
-                }
-            })?;
-            Ok(id)
-        }))
+        self.save_outbox(cx);
+        Ok(pending_id)
     }
 
     pub fn remove_message(&mut self, id: u64, cx: &mut ModelContext<Self>) -> Task<Result<()>> {
@@ -251,6 +395,13 @@ impl ChannelChat {
         message: MessageParams,
         cx: &mut ModelContext<Self>,
     ) -> Result<Task<Result<()>>> {
+        let previous = self.find_loaded_message(id).map(|message| {
+            (
+                message.body.clone(),
+                message.mentions.clone(),
+                message.edited_at,
+            )
+        });
         self.message_update(
             ChannelMessageId::Saved(id),
             message.text.clone(),
@@ -268,8 +419,22 @@ impl ChannelChat {
             nonce: Some(nonce.into()),
             mentions: mentions_to_proto(&message.mentions),
         });
-        Ok(cx.spawn(move |_, _| async move {
-            request.await?;
+        Ok(cx.spawn(move |this, mut cx| async move {
+            if let Err(error) = request.await {
+                // The server rejected the edit, so revert the message to how it was before.
+                if let Some((body, mentions, edited_at)) = previous {
+                    this.update(&mut cx, |this, cx| {
+                        this.message_update(
+                            ChannelMessageId::Saved(id),
+                            body,
+                            mentions,
+                            edited_at,
+                            cx,
+                        )
+                    })?;
+                }
+                return Err(error);
+            }
             Ok(())
         }))
     }
@@ -289,6 +454,7 @@ impl ChannelChat {
                     .request(proto::GetChannelMessages {
                         channel_id: channel_id.0,
                         before_message_id,
+                        after_message_id: None,
                     })
                     .await?;
                 Self::handle_loaded_messages(
@@ -458,41 +624,125 @@ impl ChannelChat {
                 )
                 .await?;
 
-                let pending_messages = this.update(&mut cx, |this, _| {
-                    this.pending_messages().cloned().collect::<Vec<_>>()
-                })?;
+                this.update(&mut cx, |this, cx| {
+                    this.resend_pending_messages(Duration::ZERO, cx)
+                })?
+                .await
+            }
+            .log_err()
+        })
+        .detach();
+    }
 
-                for pending_message in pending_messages {
+    /// Sends the messages that are still pending again, in order, after waiting for `delay`.
+    ///
+    /// Messages that the server rate limits are kept and retried with an increasing delay,
+    /// while messages that it rejects for any other reason are dropped.
+    fn resend_pending_messages(
+        &self,
+        delay: Duration,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let user_store = self.user_store.clone();
+        let rpc = self.rpc.clone();
+        let channel_id = self.channel_id;
+        let outgoing_messages_lock = self.outgoing_messages_lock.clone();
+        cx.spawn(move |this, mut cx| async move {
+            if !delay.is_zero() {
+                cx.background_executor().timer(delay).await;
+            }
+            let _outgoing_message_guard = outgoing_messages_lock.lock().await;
+            let pending_messages = this.update(&mut cx, |this, _| {
+                this.pending_messages().cloned().collect::<Vec<_>>()
+            })?;
+
+            let mut backoff = RATE_LIMITED_RESEND_DELAY;
+            for pending_message in pending_messages {
+                let response = loop {
                     let request = rpc.request(proto::SendChannelMessage {
                         channel_id: channel_id.0,
-                        body: pending_message.body,
+                        body: pending_message.body.clone(),
                         mentions: mentions_to_proto(&pending_message.mentions),
                         nonce: Some(pending_message.nonce.into()),
                         reply_to_message_id: pending_message.reply_to_message_id,
                     });
-                    let response = request.await?;
-                    let message = ChannelMessage::from_proto(
-                        response.message.ok_or_else(|| anyhow!("invalid message"))?,
-                        &user_store,
-                        &mut cx,
-                    )
-                    .await?;
-                    this.update(&mut cx, |this, cx| {
-                        this.insert_messages(SumTree::from_item(message, &()), cx);
-                    })?;
-                }
-
-                anyhow::Ok(())
+                    match request.await {
+                        Ok(response) => break Some(response),
+                        Err(error) if is_rate_limited(&error) => {
+                            cx.background_executor().timer(backoff).await;
+                            backoff = (backoff * 2).min(MAX_RATE_LIMITED_RESEND_DELAY);
+                        }
+                        Err(error) if !should_resend(&error) => {
+                            log::error!("channel message was rejected: {error:?}");
+                            this.update(&mut cx, |this, cx| {
+                                this.remove_pending_message(pending_message.id, cx)
+                            })?;
+                            break None;
+                        }
+                        Err(error) => return Err(error),
+                    }
+                };
+                let Some(response) = response else {
+                    continue;
+                };
+                backoff = RATE_LIMITED_RESEND_DELAY;
+
+                let message = ChannelMessage::from_proto(
+                    response.message.ok_or_else(|| anyhow!("invalid message"))?,
+                    &user_store,
+                    &mut cx,
+                )
+                .await?;
+                this.update(&mut cx, |this, cx| {
+                    this.insert_messages(SumTree::from_item(message, &()), cx);
+                    this.save_outbox(cx);
+                })?;
             }
-            .log_err()
+
+            Ok(())
         })
-        .detach();
+    }
+
+    fn save_outbox(&self, cx: &AppContext) {
+        if let Some(outbox) = &self.outbox {
+            let messages = self
+                .pending_messages()
+                .map(|message| OutboxMessage {
+                    body: message.body.clone(),
+                    mentions: message.mentions.clone(),
+                    nonce: message.nonce,
+                    reply_to_message_id: message.reply_to_message_id,
+                })
+                .collect();
+            outbox.save(messages, cx).detach_and_log_err(cx);
+        }
     }
 
     pub fn message_count(&self) -> usize {
         self.messages.summary().count
     }
 
+    /// The number of loaded messages from other users that are newer than the last
+    /// message the current user read.
+    pub fn unread_count(&self, cx: &AppContext) -> usize {
+        let last_read_id = self
+            .channel_store
+            .read(cx)
+            .last_read_message_id(self.channel_id)
+            .max(self.last_acknowledged_id);
+        let current_user_id = self.user_store.read(cx).current_user().map(|user| user.id);
+        self.messages
+            .iter()
+            .filter(|message| match message.id {
+                ChannelMessageId::Saved(id) => {
+                    last_read_id.map_or(true, |last_read_id| id > last_read_id)
+                        && Some(message.sender.id) != current_user_id
+                }
+                ChannelMessageId::Pending(_) => false,
+            })
+            .count()
+    }
+
     pub fn messages(&self) -> &SumTree<ChannelMessage> {
         &self.messages
     }
@@ -646,35 +896,49 @@ impl ChannelChat {
     }
 
     fn message_removed(&mut self, id: u64, cx: &mut ModelContext<Self>) {
+        let id = ChannelMessageId::Saved(id);
         let mut cursor = self.messages.cursor::<ChannelMessageId>(&());
-        let mut messages = cursor.slice(&ChannelMessageId::Saved(id), Bias::Left, &());
-        if let Some(item) = cursor.item() {
-            if item.id == ChannelMessageId::Saved(id) {
-                let deleted_message_ix = messages.summary().count;
-                cursor.next(&());
-                messages.append(cursor.suffix(&()), &());
-                drop(cursor);
-                self.messages = messages;
-
-                // If the message that was deleted was the last acknowledged message,
-                // replace the acknowledged message with an earlier one.
-                self.channel_store.update(cx, |store, _| {
-                    let summary = self.messages.summary();
-                    if summary.count == 0 {
-                        store.set_acknowledged_message_id(self.channel_id, None);
-                    } else if deleted_message_ix == summary.count {
-                        if let ChannelMessageId::Saved(id) = summary.max_id {
-                            store.set_acknowledged_message_id(self.channel_id, Some(id));
-                        }
-                    }
-                });
+        let mut messages = cursor.slice(&id, Bias::Left, &());
+        let ix = messages.summary().count;
 
-                cx.emit(ChannelChatEvent::MessagesUpdated {
-                    old_range: deleted_message_ix..deleted_message_ix + 1,
-                    new_count: 0,
-                });
-            }
+        let Some(mut deleted_message) = cursor.item().filter(|item| item.id == id).cloned() else {
+            return;
+        };
+        deleted_message.body.clear();
+        deleted_message.mentions.clear();
+        deleted_message.deleted = true;
+        messages.push(deleted_message, &());
+        cursor.next(&());
+        messages.append(cursor.suffix(&()), &());
+        drop(cursor);
+        self.messages = messages;
+
+        cx.emit(ChannelChatEvent::UpdateMessage {
+            message_ix: ix,
+            message_id: id,
+        });
+        cx.notify();
+    }
+
+    /// Removes a pending message that the server rejected, so that it isn't resent.
+    fn remove_pending_message(&mut self, id: ChannelMessageId, cx: &mut ModelContext<Self>) {
+        let mut cursor = self.messages.cursor::<ChannelMessageId>(&());
+        let mut messages = cursor.slice(&id, Bias::Left, &());
+        let ix = messages.summary().count;
+        if cursor.item().map_or(true, |item| item.id != id) {
+            return;
         }
+        cursor.next(&());
+        messages.append(cursor.suffix(&()), &());
+        drop(cursor);
+        self.messages = messages;
+        self.save_outbox(cx);
+
+        cx.emit(ChannelChatEvent::MessagesUpdated {
+            old_range: ix..ix + 1,
+            new_count: 0,
+        });
+        cx.notify();
     }
 
     fn message_update(
@@ -760,6 +1024,7 @@ impl ChannelMessage {
                 .into(),
             reply_to_message_id: message.reply_to_message_id,
             edited_at,
+            deleted: message.deleted_at.is_some(),
         })
     }
 
@@ -805,6 +1070,20 @@ pub fn mentions_to_proto(mentions: &[(Range<usize>, UserId)]) -> Vec<proto::Chat
         .collect()
 }
 
+/// Whether a message that failed to send should stay pending and be sent again later.
+/// Other errors from the server mean it rejected the message, so resending it would fail again.
+fn should_resend(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<RpcError>().is_none()
+        || matches!(
+            error.error_code(),
+            ErrorCode::Disconnected | ErrorCode::SignedOut | ErrorCode::RateLimitExceeded
+        )
+}
+
+fn is_rate_limited(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<RpcError>().is_some() && error.error_code() == ErrorCode::RateLimitExceeded
+}
+
 impl sum_tree::Item for ChannelMessage {
     type Summary = ChannelMessageSummary;
 
@@ -16,11 +16,18 @@ use rpc::{
     TypedEnvelope,
 };
 use settings::Settings;
-use std::{mem, sync::Arc, time::Duration};
+use std::{
+    mem,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use util::{maybe, ResultExt};
 
 pub const RECONNECT_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// How long a user is shown as typing after their last typing event.
+pub const TYPING_TIMEOUT: Duration = Duration::from_secs(3);
+
 pub fn init(client: &Arc<Client>, user_store: Model<UserStore>, cx: &mut AppContext) {
     let channel_store =
         cx.new_model(|cx| ChannelStore::new(client.clone(), user_store.clone(), cx));
@@ -39,13 +46,14 @@ pub struct ChannelStore {
     channel_participants: HashMap<ChannelId, Vec<Arc<User>>>,
     channel_states: HashMap<ChannelId, ChannelState>,
     outgoing_invites: HashSet<(ChannelId, UserId)>,
+    typing_users: HashMap<ChannelId, HashMap<UserId, Instant>>,
     update_channels_tx: mpsc::UnboundedSender<proto::UpdateChannels>,
     opened_buffers: HashMap<ChannelId, OpenedModelHandle<ChannelBuffer>>,
     opened_chats: HashMap<ChannelId, OpenedModelHandle<ChannelChat>>,
     client: Arc<Client>,
     did_subscribe: bool,
     user_store: Model<UserStore>,
-    _rpc_subscriptions: [Subscription; 2],
+    _rpc_subscriptions: [Subscription; 3],
     _watch_connection_status: Task<Option<()>>,
     disconnect_channel_buffers_task: Option<Task<()>>,
     _update_channels: Task<()>,
@@ -165,6 +173,7 @@ impl ChannelStore {
         let rpc_subscriptions = [
             client.add_message_handler(cx.weak_model(), Self::handle_update_channels),
             client.add_message_handler(cx.weak_model(), Self::handle_update_user_channels),
+            client.add_message_handler(cx.weak_model(), Self::handle_channel_typing_update),
         ];
 
         let mut connection_status = client.status();
@@ -197,6 +206,7 @@ impl ChannelStore {
             channel_index: ChannelIndex::default(),
             channel_participants: Default::default(),
             outgoing_invites: Default::default(),
+            typing_users: Default::default(),
             opened_buffers: Default::default(),
             opened_chats: Default::default(),
             update_channels_tx,
@@ -380,6 +390,13 @@ impl ChannelStore {
         })
     }
 
+    /// The ID of the latest message in the channel that the current user has read.
+    pub fn last_read_message_id(&self, channel_id: ChannelId) -> Option<u64> {
+        self.channel_states
+            .get(&channel_id)
+            .and_then(|state| state.last_acknowledged_message_id())
+    }
+
     pub fn acknowledge_message_id(
         &mut self,
         channel_id: ChannelId,
@@ -568,6 +585,22 @@ impl ChannelStore {
             .map_or(&[], |v| v.as_slice())
     }
 
+    /// The users that are currently typing in the given channel's chat.
+    pub fn typing_user_ids(&self, channel_id: ChannelId) -> impl Iterator<Item = UserId> + '_ {
+        self.typing_users
+            .get(&channel_id)
+            .into_iter()
+            .flat_map(|users| users.keys().copied())
+    }
+
+    /// Lets the other members of the channel know that the current user is typing in its chat.
+    /// The server coalesces these, so it's fine to call this on every keystroke.
+    pub fn send_typing(&self, channel_id: ChannelId) -> Result<()> {
+        self.client.send(proto::ChannelTyping {
+            channel_id: channel_id.0,
+        })
+    }
+
     pub fn create_channel(
         &self,
         name: &str,
@@ -863,6 +896,44 @@ impl ChannelStore {
         Ok(())
     }
 
+    async fn handle_channel_typing_update(
+        this: Model<Self>,
+        message: TypedEnvelope<proto::ChannelTypingUpdate>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        let channel_id = ChannelId(message.payload.channel_id);
+        let user_id = message.payload.user_id;
+        this.update(&mut cx, |this, cx| {
+            let typed_at = cx.background_executor().now();
+            this.typing_users
+                .entry(channel_id)
+                .or_default()
+                .insert(user_id, typed_at);
+            cx.notify();
+
+            cx.spawn(|this, mut cx| async move {
+                cx.background_executor().timer(TYPING_TIMEOUT).await;
+                this.update(&mut cx, |this, cx| {
+                    let hash_map::Entry::Occupied(mut users) = this.typing_users.entry(channel_id)
+                    else {
+                        return;
+                    };
+                    // A later typing event from the same user extends their timeout.
+                    if users.get().get(&user_id) == Some(&typed_at) {
+                        users.get_mut().remove(&user_id);
+                        if users.get().is_empty() {
+                            users.remove();
+                        }
+                        cx.notify();
+                    }
+                })
+                .ok();
+            })
+            .detach();
+        })?;
+        Ok(())
+    }
+
     async fn handle_update_user_channels(
         this: Model<Self>,
         message: TypedEnvelope<proto::UpdateUserChannels>,
@@ -1014,6 +1085,7 @@ impl ChannelStore {
     fn handle_disconnect(&mut self, wait_for_reconnect: bool, cx: &mut ModelContext<Self>) {
         cx.notify();
         self.did_subscribe = false;
+        self.typing_users.clear();
         self.disconnect_channel_buffers_task.get_or_insert_with(|| {
             cx.spawn(move |this, mut cx| async move {
                 if wait_for_reconnect {
@@ -1,11 +1,15 @@
 use crate::channel_chat::ChannelChatEvent;
 
 use super::*;
-use client::{test::FakeServer, Client, UserStore};
+use client::{test::FakeServer, ChannelId, Client, UserStore};
 use clock::FakeSystemClock;
+use fs::{FakeFs, Fs};
 use gpui::{AppContext, Context, Model, SemanticVersion, TestAppContext};
 use http_client::FakeHttpClient;
-use rpc::proto::{self};
+use rpc::{
+    proto::{self},
+    ErrorCode, ErrorCodeExt, ErrorExt,
+};
 use settings::SettingsStore;
 
 #[gpui::test]
@@ -187,6 +191,7 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
                     nonce: Some(1.into()),
                     reply_to_message_id: None,
                     edited_at: None,
+                    deleted_at: None,
                 },
                 proto::ChannelMessage {
                     id: 11,
@@ -197,6 +202,7 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
                     nonce: Some(2.into()),
                     reply_to_message_id: None,
                     edited_at: None,
+                    deleted_at: None,
                 },
             ],
             done: false,
@@ -246,6 +252,7 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
             nonce: Some(3.into()),
             reply_to_message_id: None,
             edited_at: None,
+            deleted_at: None,
         }),
     });
 
@@ -301,6 +308,7 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
                     mentions: vec![],
                     reply_to_message_id: None,
                     edited_at: None,
+                    deleted_at: None,
                 },
                 proto::ChannelMessage {
                     id: 9,
@@ -311,6 +319,7 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
                     mentions: vec![],
                     reply_to_message_id: None,
                     edited_at: None,
+                    deleted_at: None,
                 },
             ],
         },
@@ -337,6 +346,195 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_resending_pending_messages_after_restart(cx: &mut TestAppContext) {
+    let user_id = 5;
+    let channel_id = 5;
+    let channel_store = cx.update(init_test);
+    let fs = FakeFs::new(cx.executor());
+    cx.update(|cx| <dyn Fs>::set_global(fs.clone(), cx));
+    let client = channel_store.update(cx, |s, _| s.client());
+    let server = FakeServer::for_client(user_id, &client, cx).await;
+
+    server.send(proto::UpdateChannels {
+        channels: vec![proto::Channel {
+            id: channel_id,
+            name: "the-channel".to_string(),
+            visibility: proto::ChannelVisibility::Members as i32,
+            parent_path: vec![],
+        }],
+        ..Default::default()
+    });
+    let get_users = server.receive::<proto::GetUsers>().await.unwrap();
+    server.respond(
+        get_users.receipt(),
+        proto::UsersResponse {
+            users: vec![proto::User {
+                id: 5,
+                github_login: "nathansobo".into(),
+                avatar_url: "http://avatar.com/nathansobo".into(),
+            }],
+        },
+    );
+
+    let open_chat = |cx: &mut TestAppContext| {
+        channel_store.update(cx, |store, cx| {
+            store.open_channel_chat(ChannelId(channel_id), cx)
+        })
+    };
+    let chat = open_chat(cx);
+    let join_chat = server.receive::<proto::JoinChannelChat>().await.unwrap();
+    server.respond(
+        join_chat.receipt(),
+        proto::JoinChannelChatResponse {
+            messages: vec![],
+            done: true,
+        },
+    );
+    let chat = chat.await.unwrap();
+
+    // Send a message, but quit before the server acknowledges it.
+    let send_task = chat.update(cx, |chat, cx| {
+        chat.send_message(
+            MessageParams {
+                text: "hello".into(),
+                mentions: vec![],
+                reply_to_message_id: None,
+            },
+            cx,
+        )
+        .unwrap()
+    });
+    let sent_message = server.receive::<proto::SendChannelMessage>().await.unwrap();
+    cx.executor().run_until_parked();
+    assert_eq!(
+        fs.files(),
+        [paths::chat_outbox_dir()
+            .join(user_id.to_string())
+            .join(format!("{channel_id}.json"))]
+    );
+    drop(send_task);
+    drop(chat);
+    cx.executor().run_until_parked();
+    server.receive::<proto::LeaveChannelChat>().await.unwrap();
+
+    // When the chat is opened again, the pending message is resent with the same nonce.
+    let chat = open_chat(cx);
+    let join_chat = server.receive::<proto::JoinChannelChat>().await.unwrap();
+    server.respond(
+        join_chat.receipt(),
+        proto::JoinChannelChatResponse {
+            messages: vec![],
+            done: true,
+        },
+    );
+    let chat = chat.await.unwrap();
+    let resent_message = server.receive::<proto::SendChannelMessage>().await.unwrap();
+    assert_eq!(resent_message.payload.body, "hello");
+    assert_eq!(resent_message.payload.nonce, sent_message.payload.nonce);
+    chat.update(cx, |chat, _| {
+        assert_eq!(
+            chat.pending_messages()
+                .map(|message| message.body.clone())
+                .collect::<Vec<_>>(),
+            ["hello"]
+        );
+    });
+
+    // Once the message is acknowledged, it is removed from the outbox.
+    server.respond(
+        resent_message.receipt(),
+        proto::SendChannelMessageResponse {
+            message: Some(proto::ChannelMessage {
+                id: 1,
+                body: "hello".into(),
+                timestamp: 1000,
+                sender_id: user_id,
+                mentions: vec![],
+                nonce: resent_message.payload.nonce,
+                reply_to_message_id: None,
+                edited_at: None,
+                deleted_at: None,
+            }),
+        },
+    );
+    cx.executor().run_until_parked();
+    chat.update(cx, |chat, _| assert_eq!(chat.pending_messages().count(), 0));
+    assert!(fs.files().is_empty());
+
+    // Messages that are rate limited stay pending, and are resent after a delay.
+    let send_task = chat.update(cx, |chat, cx| {
+        chat.send_message(
+            MessageParams {
+                text: "too fast".into(),
+                mentions: vec![],
+                reply_to_message_id: None,
+            },
+            cx,
+        )
+        .unwrap()
+    });
+    let sent_message = server.receive::<proto::SendChannelMessage>().await.unwrap();
+    server.respond_with_error(
+        sent_message.receipt(),
+        ErrorCode::RateLimitExceeded
+            .message("rate limit exceeded".into())
+            .to_proto(),
+    );
+    assert!(send_task.await.is_err());
+    cx.executor().run_until_parked();
+    chat.update(cx, |chat, _| assert_eq!(chat.pending_messages().count(), 1));
+    assert_eq!(fs.files().len(), 1);
+
+    cx.executor().advance_clock(RATE_LIMITED_RESEND_DELAY);
+    let resent_message = server.receive::<proto::SendChannelMessage>().await.unwrap();
+    assert_eq!(resent_message.payload.body, "too fast");
+    assert_eq!(resent_message.payload.nonce, sent_message.payload.nonce);
+    server.respond(
+        resent_message.receipt(),
+        proto::SendChannelMessageResponse {
+            message: Some(proto::ChannelMessage {
+                id: 2,
+                body: "too fast".into(),
+                timestamp: 1001,
+                sender_id: user_id,
+                mentions: vec![],
+                nonce: resent_message.payload.nonce,
+                reply_to_message_id: None,
+                edited_at: None,
+                deleted_at: None,
+            }),
+        },
+    );
+    cx.executor().run_until_parked();
+    chat.update(cx, |chat, _| assert_eq!(chat.pending_messages().count(), 0));
+    assert!(fs.files().is_empty());
+
+    // Messages the server rejects are dropped rather than kept for resending.
+    let send_task = chat.update(cx, |chat, cx| {
+        chat.send_message(
+            MessageParams {
+                text: "not allowed".into(),
+                mentions: vec![],
+                reply_to_message_id: None,
+            },
+            cx,
+        )
+        .unwrap()
+    });
+    let sent_message = server.receive::<proto::SendChannelMessage>().await.unwrap();
+    server.respond_with_error(
+        sent_message.receipt(),
+        ErrorCode::Forbidden
+            .message("not allowed".into())
+            .to_proto(),
+    );
+    assert!(send_task.await.is_err());
+    cx.executor().run_until_parked();
+    chat.update(cx, |chat, _| assert_eq!(chat.pending_messages().count(), 0));
+    assert!(fs.files().is_empty());
+}
+
 fn init_test(cx: &mut AppContext) -> Model<ChannelStore> {
     let settings_store = SettingsStore::test(cx);
     cx.set_global(settings_store);
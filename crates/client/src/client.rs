@@ -11,7 +11,7 @@ use async_recursion::async_recursion;
 use async_tungstenite::tungstenite::{
     client::IntoClientRequest,
     error::Error as WebsocketError,
-    http::{HeaderValue, Request, StatusCode},
+    http::{HeaderMap, HeaderValue, Request, StatusCode},
 };
 use chrono::{DateTime, Utc};
 use clock::SystemClock;
@@ -42,7 +42,7 @@ use std::{
         atomic::{AtomicU64, Ordering},
         Arc, LazyLock, Weak,
     },
-    time::{Duration, Instant},
+    time::Duration,
 };
 use telemetry::Telemetry;
 use thiserror::Error;
@@ -164,7 +164,7 @@ pub fn init(client: &Arc<Client>, cx: &mut AppContext) {
         move |_: &SignOut, cx| {
             if let Some(client) = client.upgrade() {
                 cx.spawn(|cx| async move {
-                    client.sign_out(&cx).await;
+                    client.sign_out(&cx).await.log_err();
                 })
                 .detach();
             }
@@ -196,12 +196,7 @@ pub struct Client {
     credentials_provider: Arc<dyn CredentialsProvider + Send + Sync + 'static>,
     state: RwLock<ClientState>,
     handler_set: parking_lot::Mutex<ProtoMessageHandlerSet>,
-
-    #[allow(clippy::type_complexity)]
-    #[cfg(any(test, feature = "test-support"))]
-    authenticate: RwLock<
-        Option<Box<dyn 'static + Send + Sync + Fn(&AsyncAppContext) -> Task<Result<Credentials>>>>,
-    >,
+    authenticator: RwLock<Option<Arc<dyn Authenticator>>>,
 
     #[allow(clippy::type_complexity)]
     #[cfg(any(test, feature = "test-support"))]
@@ -273,7 +268,10 @@ pub enum Status {
     Reauthenticating,
     Reconnecting,
     ReconnectionError {
-        next_reconnection: Instant,
+        /// How many consecutive reconnection attempts have failed.
+        attempt: usize,
+        /// How long the client will wait before attempting to reconnect again.
+        next_attempt_in: Duration,
     },
 }
 
@@ -290,9 +288,48 @@ impl Status {
 struct ClientState {
     credentials: Option<Credentials>,
     status: (watch::Sender<Status>, watch::Receiver<Status>),
+    reconnection_backoff: ReconnectionBackoff,
     _reconnect_task: Option<Task<()>>,
 }
 
+/// The exponential backoff between failed reconnection attempts. It is only
+/// reset once a connection succeeds or the user signs out.
+struct ReconnectionBackoff {
+    attempt: usize,
+    base_delay: Duration,
+    rng: StdRng,
+}
+
+impl Default for ReconnectionBackoff {
+    fn default() -> Self {
+        #[cfg(any(test, feature = "test-support"))]
+        let rng = StdRng::seed_from_u64(0);
+        #[cfg(not(any(test, feature = "test-support")))]
+        let rng = StdRng::from_entropy();
+
+        Self {
+            attempt: 0,
+            base_delay: INITIAL_RECONNECTION_DELAY,
+            rng,
+        }
+    }
+}
+
+impl ReconnectionBackoff {
+    /// Records a failed attempt, returning its number and the delay before the next one.
+    fn next_attempt(&mut self) -> (usize, Duration) {
+        // Double the delay after every failed attempt, with some jitter so
+        // that clients don't all reconnect at once after a server restart.
+        self.attempt += 1;
+        let delay = self
+            .base_delay
+            .mul_f32(self.rng.gen_range(1.0..=1.5))
+            .min(MAX_RECONNECTION_DELAY);
+        self.base_delay = (self.base_delay * 2).min(MAX_RECONNECTION_DELAY);
+        (self.attempt, delay)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Credentials {
     pub user_id: u64,
@@ -305,6 +342,24 @@ impl Credentials {
     }
 }
 
+/// Acquires [`Credentials`] for connecting to the server.
+///
+/// By default, the client signs in through the browser against zed.dev. Servers
+/// that use their own identity provider can supply an implementation of this
+/// trait via [`Client::set_authenticator`].
+pub trait Authenticator: Send + Sync {
+    fn authenticate(&self, cx: &AsyncAppContext) -> Task<Result<Credentials>>;
+}
+
+impl<F> Authenticator for F
+where
+    F: Send + Sync + Fn(&AsyncAppContext) -> Task<Result<Credentials>>,
+{
+    fn authenticate(&self, cx: &AsyncAppContext) -> Task<Result<Credentials>> {
+        self(cx)
+    }
+}
+
 /// A provider for [`Credentials`].
 ///
 /// Used to abstract over reading and writing credentials to some form of
@@ -336,6 +391,7 @@ impl Default for ClientState {
         Self {
             credentials: None,
             status: watch::channel_with(Status::SignedOut),
+            reconnection_backoff: Default::default(),
             _reconnect_task: None,
         }
     }
@@ -500,6 +556,15 @@ impl Client {
                 Arc::new(KeychainCredentialsProvider)
             };
 
+        Self::with_credentials_provider(clock, http, credentials_provider, cx)
+    }
+
+    fn with_credentials_provider(
+        clock: Arc<dyn SystemClock>,
+        http: Arc<HttpClientWithUrl>,
+        credentials_provider: Arc<dyn CredentialsProvider + Send + Sync + 'static>,
+        cx: &mut AppContext,
+    ) -> Arc<Self> {
         Arc::new(Self {
             id: AtomicU64::new(0),
             peer: Peer::new(0),
@@ -508,9 +573,8 @@ impl Client {
             credentials_provider,
             state: Default::default(),
             handler_set: Default::default(),
+            authenticator: Default::default(),
 
-            #[cfg(any(test, feature = "test-support"))]
-            authenticate: Default::default(),
             #[cfg(any(test, feature = "test-support"))]
             establish_connection: Default::default(),
             #[cfg(any(test, feature = "test-support"))]
@@ -549,13 +613,19 @@ impl Client {
         self.peer.teardown();
     }
 
+    /// Use the given [`Authenticator`] to acquire credentials instead of signing in
+    /// through the browser, e.g. when connecting to a self-hosted server.
+    pub fn set_authenticator(&self, authenticator: impl Authenticator + 'static) -> &Self {
+        *self.authenticator.write() = Some(Arc::new(authenticator));
+        self
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn override_authenticate<F>(&self, authenticate: F) -> &Self
     where
         F: 'static + Send + Sync + Fn(&AsyncAppContext) -> Task<Result<Credentials>>,
     {
-        *self.authenticate.write() = Some(Box::new(authenticate));
-        self
+        self.set_authenticator(authenticate)
     }
 
     #[cfg(any(test, feature = "test-support"))]
@@ -610,45 +680,52 @@ impl Client {
 
         match status {
             Status::Connected { .. } => {
+                state.reconnection_backoff = Default::default();
                 state._reconnect_task = None;
             }
             Status::ConnectionLost => {
-                let this = self.clone();
-                state._reconnect_task = Some(cx.spawn(move |cx| async move {
-                    #[cfg(any(test, feature = "test-support"))]
-                    let mut rng = StdRng::seed_from_u64(0);
-                    #[cfg(not(any(test, feature = "test-support")))]
-                    let mut rng = StdRng::from_entropy();
-
-                    let mut delay = INITIAL_RECONNECTION_DELAY;
-                    while let Err(error) = this.authenticate_and_connect(true, &cx).await {
-                        log::error!("failed to connect {}", error);
-                        if matches!(*this.status().borrow(), Status::ConnectionError) {
-                            this.set_status(
-                                Status::ReconnectionError {
-                                    next_reconnection: Instant::now() + delay,
-                                },
-                                &cx,
-                            );
-                            cx.background_executor().timer(delay).await;
-                            delay = delay
-                                .mul_f32(rng.gen_range(0.5..=2.5))
-                                .max(INITIAL_RECONNECTION_DELAY)
-                                .min(MAX_RECONNECTION_DELAY);
-                        } else {
-                            break;
-                        }
-                    }
-                }));
+                state._reconnect_task = Some(self.reconnect_after(Duration::ZERO, cx));
             }
             Status::SignedOut | Status::UpgradeRequired => {
                 self.telemetry.set_authenticated_user_info(None, false);
+                state.reconnection_backoff = Default::default();
                 state._reconnect_task.take();
             }
             _ => {}
         }
     }
 
+    /// Keeps trying to reconnect after `delay`, backing off between failed attempts.
+    fn reconnect_after(self: &Arc<Self>, delay: Duration, cx: &AsyncAppContext) -> Task<()> {
+        let this = self.clone();
+        cx.spawn(move |cx| async move {
+            if !delay.is_zero() {
+                cx.background_executor().timer(delay).await;
+            }
+            while let Err(error) = this.authenticate_and_connect(true, &cx).await {
+                log::error!("failed to connect {}", error);
+                if matches!(*this.status().borrow(), Status::ConnectionError) {
+                    let delay = this.record_failed_reconnection(&cx);
+                    cx.background_executor().timer(delay).await;
+                } else {
+                    break;
+                }
+            }
+        })
+    }
+
+    fn record_failed_reconnection(self: &Arc<Self>, cx: &AsyncAppContext) -> Duration {
+        let (attempt, next_attempt_in) = self.state.write().reconnection_backoff.next_attempt();
+        self.set_status(
+            Status::ReconnectionError {
+                attempt,
+                next_attempt_in,
+            },
+            cx,
+        );
+        next_attempt_in
+    }
+
     pub fn subscribe_to_entity<T>(
         self: &Arc<Self>,
         remote_id: u64,
@@ -982,9 +1059,9 @@ impl Client {
     }
 
     fn authenticate(self: &Arc<Self>, cx: &AsyncAppContext) -> Task<Result<Credentials>> {
-        #[cfg(any(test, feature = "test-support"))]
-        if let Some(callback) = self.authenticate.read().as_ref() {
-            return callback(cx);
+        let authenticator = self.authenticator.read().clone();
+        if let Some(authenticator) = authenticator {
+            return authenticator.authenticate(cx);
         }
 
         self.authenticate_with_browser(cx)
@@ -1049,6 +1126,12 @@ impl Client {
         }
     }
 
+    /// Returns the websocket URL of the collaboration server, as advertised by the
+    /// server that the HTTP client is configured to talk to.
+    pub async fn websocket_url(&self, release_channel: Option<ReleaseChannel>) -> Result<Url> {
+        websocket_url(self.rpc_url(self.http.clone(), release_channel).await?)
+    }
+
     fn establish_websocket_connection(
         self: &Arc<Self>,
         credentials: &Credentials,
@@ -1076,11 +1159,11 @@ impl Client {
                 Https,
             }
 
-            let mut rpc_url = rpc_url.await?;
-            let url_scheme = match rpc_url.scheme() {
-                "https" => Https,
-                "http" => Http,
-                _ => Err(anyhow!("invalid rpc url: {}", rpc_url))?,
+            let rpc_url = websocket_url(rpc_url.await?)?;
+            let url_scheme = if rpc_url.scheme() == "wss" {
+                Https
+            } else {
+                Http
             };
             let rpc_host = rpc_url
                 .host_str()
@@ -1090,13 +1173,6 @@ impl Client {
 
             log::info!("connected to rpc endpoint {}", rpc_url);
 
-            rpc_url
-                .set_scheme(match url_scheme {
-                    Https => "wss",
-                    Http => "ws",
-                })
-                .unwrap();
-
             // We call `into_client_request` to let `tungstenite` construct the WebSocket request
             // for us from the RPC URL.
             //
@@ -1141,13 +1217,15 @@ impl Client {
                             .with_no_client_auth()
                     };
 
-                    let (stream, _) =
+                    let (stream, response) =
                         async_tungstenite::async_tls::client_async_tls_with_connector(
                             request,
                             stream,
                             Some(client_config.into()),
                         )
                         .await?;
+                    let protocol_version = negotiated_protocol_version(response.headers())?;
+                    log::info!("connected using protocol version {protocol_version}");
                     Ok(Connection::new(
                         stream
                             .map_err(|error| anyhow!(error))
@@ -1155,7 +1233,10 @@ impl Client {
                     ))
                 }
                 Http => {
-                    let (stream, _) = async_tungstenite::client_async(request, stream).await?;
+                    let (stream, response) =
+                        async_tungstenite::client_async(request, stream).await?;
+                    let protocol_version = negotiated_protocol_version(response.headers())?;
+                    log::info!("connected using protocol version {protocol_version}");
                     Ok(Connection::new(
                         stream
                             .map_err(|error| anyhow!(error))
@@ -1404,16 +1485,17 @@ impl Client {
         })
     }
 
-    pub async fn sign_out(self: &Arc<Self>, cx: &AsyncAppContext) {
+    /// Disconnects from the server and deletes any stored credentials, so that
+    /// the user has to authenticate again the next time they connect. Requests
+    /// that are still awaiting a response fail once the connection is torn down.
+    pub async fn sign_out(self: &Arc<Self>, cx: &AsyncAppContext) -> Result<()> {
         self.state.write().credentials = None;
         self.disconnect(cx);
 
         if self.has_credentials(cx).await {
-            self.credentials_provider
-                .delete_credentials(cx)
-                .await
-                .log_err();
+            self.credentials_provider.delete_credentials(cx).await?;
         }
+        Ok(())
     }
 
     pub fn disconnect(self: &Arc<Self>, cx: &AsyncAppContext) {
@@ -1426,6 +1508,28 @@ impl Client {
         self.set_status(Status::ConnectionLost, cx);
     }
 
+    /// Attempts to reconnect immediately instead of waiting for the current
+    /// backoff delay to elapse. Does nothing if the client is already connected
+    /// or a connection attempt is under way.
+    pub async fn reconnect_now(self: &Arc<Self>, cx: &AsyncAppContext) -> Result<()> {
+        match *self.status().borrow() {
+            Status::ConnectionLost | Status::ConnectionError | Status::ReconnectionError { .. } => {
+            }
+            _ => return Ok(()),
+        }
+
+        // Cancel the pending backoff timer so it doesn't race with this attempt.
+        self.state.write()._reconnect_task.take();
+        let result = self.authenticate_and_connect(true, cx).await;
+        if result.is_err() && matches!(*self.status().borrow(), Status::ConnectionError) {
+            // Count this as another failed attempt, so that manual retries
+            // don't reset the backoff.
+            let delay = self.record_failed_reconnection(cx);
+            self.state.write()._reconnect_task = Some(self.reconnect_after(delay, cx));
+        }
+        result
+    }
+
     fn connection_id(&self) -> Result<ConnectionId> {
         if let Status::Connected { connection_id, .. } = *self.status().borrow() {
             Ok(connection_id)
@@ -1525,6 +1629,13 @@ impl Client {
         message: Box<dyn AnyTypedEnvelope>,
         cx: &AsyncAppContext,
     ) {
+        // Reconnect right away, so we're routed to another server before this one goes away.
+        if message.payload_type_id() == TypeId::of::<proto::ServerShutdown>() {
+            log::info!("server is shutting down, reconnecting");
+            self.reconnect(cx);
+            return;
+        }
+
         let sender_id = message.sender_id();
         let request_id = message.message_id();
         let type_name = message.payload_type_name();
@@ -1736,6 +1847,19 @@ impl CredentialsProvider for KeychainCredentialsProvider {
     }
 }
 
+/// Converts an `http(s)` RPC URL into the `ws(s)` URL to open the connection with.
+fn websocket_url(mut rpc_url: Url) -> Result<Url> {
+    let scheme = match rpc_url.scheme() {
+        "https" => "wss",
+        "http" => "ws",
+        _ => Err(anyhow!("invalid rpc url: {}", rpc_url))?,
+    };
+    rpc_url
+        .set_scheme(scheme)
+        .map_err(|_| anyhow!("invalid rpc url: {}", rpc_url))?;
+    Ok(rpc_url)
+}
+
 /// prefix for the zed:// url scheme
 pub const ZED_URL_SCHEME: &str = "zed";
 
@@ -1761,6 +1885,24 @@ pub fn parse_zed_link<'a>(link: &'a str, cx: &AppContext) -> Option<&'a str> {
     None
 }
 
+/// Reads the protocol version that the server chose from its response to our connection
+/// request. Servers that predate negotiation don't send one, and only accept our own version.
+fn negotiated_protocol_version(headers: &HeaderMap) -> Result<u32> {
+    let Some(version) = headers.get("x-zed-protocol-version") else {
+        return Ok(rpc::PROTOCOL_VERSION);
+    };
+    let version = version
+        .to_str()?
+        .parse::<u32>()
+        .context("invalid protocol version from server")?;
+    if !(rpc::MIN_SUPPORTED_PROTOCOL_VERSION..=rpc::PROTOCOL_VERSION).contains(&version) {
+        Err(anyhow!(
+            "server chose protocol version {version}, which this client doesn't support"
+        ))?;
+    }
+    Ok(version)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1774,6 +1916,38 @@ mod tests {
     use settings::SettingsStore;
     use std::future;
 
+    #[test]
+    fn test_negotiated_protocol_version() {
+        let headers_with_version = |version: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                "x-zed-protocol-version",
+                HeaderValue::from_str(version).unwrap(),
+            );
+            headers
+        };
+
+        let older_version = rpc::PROTOCOL_VERSION - 1;
+        assert_eq!(
+            negotiated_protocol_version(&headers_with_version(&older_version.to_string())).unwrap(),
+            older_version
+        );
+        assert_eq!(
+            negotiated_protocol_version(&HeaderMap::new()).unwrap(),
+            rpc::PROTOCOL_VERSION
+        );
+        let newer_version = rpc::PROTOCOL_VERSION + 1;
+        assert!(
+            negotiated_protocol_version(&headers_with_version(&newer_version.to_string())).is_err()
+        );
+        let unsupported_version = rpc::MIN_SUPPORTED_PROTOCOL_VERSION - 1;
+        assert!(negotiated_protocol_version(&headers_with_version(
+            &unsupported_version.to_string()
+        ))
+        .is_err());
+        assert!(negotiated_protocol_version(&headers_with_version("not-a-number")).is_err());
+    }
+
     #[gpui::test(iterations = 10)]
     async fn test_reconnection(cx: &mut TestAppContext) {
         init_test(cx);
@@ -1815,6 +1989,248 @@ mod tests {
         assert_eq!(server.auth_count(), 2); // Client re-authenticated due to an invalid token
     }
 
+    #[gpui::test]
+    async fn test_reconnecting_when_server_shuts_down(cx: &mut TestAppContext) {
+        init_test(cx);
+        let user_id = 5;
+        let client = cx.update(|cx| {
+            Client::new(
+                Arc::new(FakeSystemClock::default()),
+                FakeHttpClient::with_404_response(),
+                cx,
+            )
+        });
+        let server = FakeServer::for_client(user_id, &client, cx).await;
+        let mut status = client.status();
+        let Some(Status::Connected { connection_id, .. }) = status.next().await else {
+            panic!("client should be connected");
+        };
+
+        server.send(proto::ServerShutdown {});
+        loop {
+            if let Some(Status::Connected {
+                connection_id: new_connection_id,
+                ..
+            }) = status.next().await
+            {
+                assert_ne!(new_connection_id, connection_id);
+                break;
+            }
+        }
+    }
+
+    #[gpui::test(iterations = 10)]
+    async fn test_reconnection_backoff(cx: &mut TestAppContext) {
+        init_test(cx);
+        let user_id = 5;
+        let client = cx.update(|cx| {
+            Client::new(
+                Arc::new(FakeSystemClock::default()),
+                FakeHttpClient::with_404_response(),
+                cx,
+            )
+        });
+        let server = FakeServer::for_client(user_id, &client, cx).await;
+        let mut status = client.status();
+        assert!(matches!(
+            status.next().await,
+            Some(Status::Connected { .. })
+        ));
+
+        server.forbid_connections();
+        server.disconnect();
+
+        let mut previous_delay = Duration::ZERO;
+        for expected_attempt in 1..=6 {
+            let delay = loop {
+                if let Status::ReconnectionError {
+                    attempt,
+                    next_attempt_in,
+                } = status.next().await.unwrap()
+                {
+                    assert_eq!(attempt, expected_attempt);
+                    break next_attempt_in;
+                }
+            };
+            assert!(delay >= previous_delay);
+            assert!(delay <= MAX_RECONNECTION_DELAY);
+            previous_delay = delay;
+            cx.executor().advance_clock(delay);
+        }
+        assert_eq!(previous_delay, MAX_RECONNECTION_DELAY);
+
+        // A successful reconnection resets the attempt count.
+        server.allow_connections();
+        cx.executor().advance_clock(MAX_RECONNECTION_DELAY);
+        while !matches!(status.next().await, Some(Status::Connected { .. })) {}
+        server.forbid_connections();
+        server.disconnect();
+        while !matches!(
+            status.next().await,
+            Some(Status::ReconnectionError { attempt: 1, .. })
+        ) {}
+    }
+
+    #[gpui::test]
+    async fn test_reconnect_now(cx: &mut TestAppContext) {
+        init_test(cx);
+        let user_id = 5;
+        let client = cx.update(|cx| {
+            Client::new(
+                Arc::new(FakeSystemClock::default()),
+                FakeHttpClient::with_404_response(),
+                cx,
+            )
+        });
+        let server = FakeServer::for_client(user_id, &client, cx).await;
+        let mut status = client.status();
+        assert!(matches!(
+            status.next().await,
+            Some(Status::Connected { .. })
+        ));
+
+        // Reconnecting while connected does nothing.
+        client.reconnect_now(&cx.to_async()).await.unwrap();
+        assert!(matches!(
+            *client.status().borrow(),
+            Status::Connected { .. }
+        ));
+
+        server.forbid_connections();
+        server.disconnect();
+        while !matches!(status.next().await, Some(Status::ReconnectionError { .. })) {}
+
+        // Reconnect without waiting for the backoff delay to elapse.
+        server.allow_connections();
+        client.reconnect_now(&cx.to_async()).await.unwrap();
+        assert!(matches!(
+            *client.status().borrow(),
+            Status::Connected { .. }
+        ));
+
+        // A failed attempt continues the backoff instead of starting it over.
+        server.forbid_connections();
+        server.disconnect();
+        while !matches!(status.next().await, Some(Status::ReconnectionError { .. })) {}
+        client.reconnect_now(&cx.to_async()).await.unwrap_err();
+        cx.executor().run_until_parked();
+        let Status::ReconnectionError {
+            attempt,
+            next_attempt_in,
+        } = *client.status().borrow()
+        else {
+            panic!("expected a reconnection error");
+        };
+        assert_eq!(attempt, 2);
+        assert!(next_attempt_in >= 2 * INITIAL_RECONNECTION_DELAY);
+
+        // The backoff timer keeps retrying after the failed manual attempt.
+        server.allow_connections();
+        cx.executor().advance_clock(next_attempt_in);
+        while !matches!(status.next().await, Some(Status::Connected { .. })) {}
+    }
+
+    #[gpui::test]
+    async fn test_sign_out(cx: &mut TestAppContext) {
+        init_test(cx);
+        let user_id = 5;
+        let client = cx.update(|cx| {
+            Client::with_credentials_provider(
+                Arc::new(FakeSystemClock::default()),
+                FakeHttpClient::with_404_response(),
+                Arc::new(FakeCredentialsProvider::default()),
+                cx,
+            )
+        });
+        let server = FakeServer::for_client(user_id, &client, cx).await;
+        assert!(client.has_credentials(&cx.to_async()).await);
+
+        let response = cx
+            .background_executor
+            .spawn(client.request(proto::GetUsers { user_ids: vec![1] }));
+        server.receive::<proto::GetUsers>().await.unwrap();
+
+        client.sign_out(&cx.to_async()).await.unwrap();
+        assert_eq!(*client.status().borrow(), Status::SignedOut);
+        assert!(!client.has_credentials(&cx.to_async()).await);
+        assert!(response.await.is_err());
+    }
+
+    /// Stores credentials in memory instead of the system keychain.
+    #[derive(Default)]
+    struct FakeCredentialsProvider {
+        credentials: Mutex<Option<Credentials>>,
+    }
+
+    impl CredentialsProvider for FakeCredentialsProvider {
+        fn read_credentials<'a>(
+            &'a self,
+            _cx: &'a AsyncAppContext,
+        ) -> Pin<Box<dyn Future<Output = Option<Credentials>> + 'a>> {
+            future::ready(self.credentials.lock().clone()).boxed_local()
+        }
+
+        fn write_credentials<'a>(
+            &'a self,
+            user_id: u64,
+            access_token: String,
+            _cx: &'a AsyncAppContext,
+        ) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>> {
+            *self.credentials.lock() = Some(Credentials {
+                user_id,
+                access_token,
+            });
+            future::ready(Ok(())).boxed_local()
+        }
+
+        fn delete_credentials<'a>(
+            &'a self,
+            _cx: &'a AsyncAppContext,
+        ) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>> {
+            self.credentials.lock().take();
+            future::ready(Ok(())).boxed_local()
+        }
+    }
+
+    #[gpui::test]
+    async fn test_websocket_url(cx: &mut TestAppContext) {
+        init_test(cx);
+        let http = FakeHttpClient::create(|request| async move {
+            let location = match request.uri().to_string().as_str() {
+                "https://zed.example.com/rpc?preview=1" => "https://collab.zed.example.com/rpc",
+                "http://localhost:3000/rpc" => "http://localhost:8080/rpc",
+                "http://localhost:4000/rpc" => "ftp://localhost:8080/rpc",
+                uri => panic!("unexpected request to {uri}"),
+            };
+            Ok(http_client::Response::builder()
+                .status(302)
+                .header("Location", location)
+                .body(Default::default())
+                .unwrap())
+        });
+        let client =
+            cx.update(|cx| Client::new(Arc::new(FakeSystemClock::default()), http.clone(), cx));
+
+        http.set_base_url("https://zed.example.com");
+        assert_eq!(
+            client
+                .websocket_url(Some(ReleaseChannel::Preview))
+                .await
+                .unwrap()
+                .as_str(),
+            "wss://collab.zed.example.com/rpc"
+        );
+
+        http.set_base_url("http://localhost:3000");
+        assert_eq!(
+            client.websocket_url(None).await.unwrap().as_str(),
+            "ws://localhost:8080/rpc"
+        );
+
+        http.set_base_url("http://localhost:4000");
+        assert!(client.websocket_url(None).await.is_err());
+    }
+
     #[gpui::test(iterations = 10)]
     async fn test_connection_timeout(executor: BackgroundExecutor, cx: &mut TestAppContext) {
         init_test(cx);
@@ -1890,6 +2306,61 @@ mod tests {
         ));
     }
 
+    #[gpui::test]
+    async fn test_custom_authenticator(cx: &mut TestAppContext) {
+        init_test(cx);
+        let client = cx.update(|cx| {
+            Client::new(
+                Arc::new(FakeSystemClock::default()),
+                FakeHttpClient::with_404_response(),
+                cx,
+            )
+        });
+
+        struct TokenAuthenticator {
+            auth_count: Arc<Mutex<usize>>,
+        }
+
+        impl Authenticator for TokenAuthenticator {
+            fn authenticate(&self, _: &AsyncAppContext) -> Task<Result<Credentials>> {
+                *self.auth_count.lock() += 1;
+                Task::ready(Ok(Credentials {
+                    user_id: 7,
+                    access_token: "self-hosted-token".into(),
+                }))
+            }
+        }
+
+        let auth_count = Arc::new(Mutex::new(0));
+        let connected_with = Arc::new(Mutex::new(None));
+        client
+            .set_authenticator(TokenAuthenticator {
+                auth_count: auth_count.clone(),
+            })
+            .override_establish_connection({
+                let connected_with = connected_with.clone();
+                move |credentials, _| {
+                    *connected_with.lock() = Some(credentials.clone());
+                    Task::ready(Err(EstablishConnectionError::Other(anyhow!(
+                        "server is unavailable"
+                    ))))
+                }
+            });
+
+        client
+            .authenticate_and_connect(false, &cx.to_async())
+            .await
+            .unwrap_err();
+        assert_eq!(*auth_count.lock(), 1);
+        assert_eq!(
+            *connected_with.lock(),
+            Some(Credentials {
+                user_id: 7,
+                access_token: "self-hosted-token".into(),
+            })
+        );
+    }
+
     #[gpui::test(iterations = 10)]
     async fn test_authenticating_more_than_once(
         cx: &mut TestAppContext,
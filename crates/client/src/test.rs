@@ -195,6 +195,14 @@ impl FakeServer {
         self.peer.respond(receipt, response).unwrap()
     }
 
+    pub fn respond_with_error<T: proto::RequestMessage>(
+        &self,
+        receipt: Receipt<T>,
+        error: proto::Error,
+    ) {
+        self.peer.respond_with_error(receipt, error).unwrap()
+    }
+
     fn connection_id(&self) -> ConnectionId {
         self.state.lock().connection_id.expect("not connected")
     }
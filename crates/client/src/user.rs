@@ -95,6 +95,7 @@ pub struct UserStore {
     current_user: watch::Receiver<Option<Arc<User>>>,
     accepted_tos_at: Option<Option<DateTime<Utc>>>,
     contacts: Vec<Arc<Contact>>,
+    away_user_ids: HashSet<u64>,
     incoming_contact_requests: Vec<Arc<User>>,
     outgoing_contact_requests: Vec<Arc<User>>,
     pending_contact_requests: HashMap<u64, usize>,
@@ -116,6 +117,10 @@ pub enum Event {
         user: Arc<User>,
         kind: ContactEventKind,
     },
+    ContactChanged {
+        user_id: u64,
+        change: ContactChange,
+    },
     ShowContacts,
     ParticipantIndicesChanged,
 }
@@ -127,6 +132,13 @@ pub enum ContactEventKind {
     Cancelled,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContactChange {
+    Added,
+    Removed,
+    Updated,
+}
+
 impl EventEmitter<Event> for UserStore {}
 
 enum UpdateContacts {
@@ -142,6 +154,7 @@ impl UserStore {
         let rpc_subscriptions = vec![
             client.add_message_handler(cx.weak_model(), Self::handle_update_plan),
             client.add_message_handler(cx.weak_model(), Self::handle_update_contacts),
+            client.add_message_handler(cx.weak_model(), Self::handle_presence_changed),
             client.add_message_handler(cx.weak_model(), Self::handle_update_invite_info),
             client.add_message_handler(cx.weak_model(), Self::handle_show_contacts),
         ];
@@ -152,6 +165,7 @@ impl UserStore {
             current_plan: None,
             accepted_tos_at: None,
             contacts: Default::default(),
+            away_user_ids: Default::default(),
             incoming_contact_requests: Default::default(),
             participant_indices: Default::default(),
             outgoing_contact_requests: Default::default(),
@@ -293,6 +307,23 @@ impl UserStore {
         Ok(())
     }
 
+    async fn handle_presence_changed(
+        this: Model<Self>,
+        message: TypedEnvelope<proto::PresenceChanged>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            let user_id = message.payload.user_id;
+            if message.payload.status() == proto::PresenceStatus::Away {
+                this.away_user_ids.insert(user_id);
+            } else {
+                this.away_user_ids.remove(&user_id);
+            }
+            cx.notify();
+        })?;
+        Ok(())
+    }
+
     async fn handle_update_plan(
         this: Model<Self>,
         message: TypedEnvelope<proto::UpdateUserPlan>,
@@ -308,7 +339,7 @@ impl UserStore {
     fn update_contacts(
         &mut self,
         message: UpdateContacts,
-        cx: &ModelContext<Self>,
+        cx: &mut ModelContext<Self>,
     ) -> Task<Result<()>> {
         match message {
             UpdateContacts::Wait(barrier) => {
@@ -316,7 +347,13 @@ impl UserStore {
                 Task::ready(Ok(()))
             }
             UpdateContacts::Clear(barrier) => {
-                self.contacts.clear();
+                for contact in self.contacts.drain(..) {
+                    cx.emit(Event::ContactChanged {
+                        user_id: contact.user.id,
+                        change: ContactChange::Removed,
+                    });
+                }
+                self.away_user_ids.clear();
                 self.incoming_contact_requests.clear();
                 self.outgoing_contact_requests.clear();
                 drop(barrier);
@@ -330,6 +367,11 @@ impl UserStore {
                 user_ids.extend(message.incoming_requests.iter().map(|req| req.requester_id));
                 user_ids.extend(message.outgoing_requests.iter());
 
+                let contact_presence = message
+                    .contacts
+                    .iter()
+                    .map(|contact| (contact.user_id, contact.away))
+                    .collect::<Vec<_>>();
                 let load_users = self.get_users(user_ids.into_iter().collect(), cx);
                 cx.spawn(|this, mut cx| async move {
                     load_users.await?;
@@ -373,16 +415,43 @@ impl UserStore {
 
                     this.update(&mut cx, |this, cx| {
                         // Remove contacts
-                        this.contacts
-                            .retain(|contact| !removed_contacts.contains(&contact.user.id));
+                        this.contacts.retain(|contact| {
+                            if removed_contacts.contains(&contact.user.id) {
+                                cx.emit(Event::ContactChanged {
+                                    user_id: contact.user.id,
+                                    change: ContactChange::Removed,
+                                });
+                                false
+                            } else {
+                                true
+                            }
+                        });
                         // Update existing contacts and insert new ones
                         for updated_contact in updated_contacts {
-                            match this.contacts.binary_search_by_key(
+                            let user_id = updated_contact.user.id;
+                            let change = match this.contacts.binary_search_by_key(
                                 &&updated_contact.user.github_login,
                                 |contact| &contact.user.github_login,
                             ) {
-                                Ok(ix) => this.contacts[ix] = updated_contact,
-                                Err(ix) => this.contacts.insert(ix, updated_contact),
+                                Ok(ix) => {
+                                    this.contacts[ix] = updated_contact;
+                                    ContactChange::Updated
+                                }
+                                Err(ix) => {
+                                    this.contacts.insert(ix, updated_contact);
+                                    ContactChange::Added
+                                }
+                            };
+                            cx.emit(Event::ContactChanged { user_id, change });
+                        }
+                        for user_id in &removed_contacts {
+                            this.away_user_ids.remove(user_id);
+                        }
+                        for (user_id, away) in contact_presence {
+                            if away {
+                                this.away_user_ids.insert(user_id);
+                            } else {
+                                this.away_user_ids.remove(&user_id);
                             }
                         }
 
@@ -438,12 +507,47 @@ impl UserStore {
         &self.contacts
     }
 
+    pub fn contact_for_id(&self, user_id: u64) -> Option<&Arc<Contact>> {
+        self.contacts
+            .iter()
+            .find(|contact| contact.user.id == user_id)
+    }
+
     pub fn has_contact(&self, user: &Arc<User>) -> bool {
         self.contacts
             .binary_search_by_key(&&user.github_login, |contact| &contact.user.github_login)
             .is_ok()
     }
 
+    /// Returns whether the given user is a contact with at least one connection to the server.
+    pub fn is_online(&self, user_id: u64) -> bool {
+        self.contacts
+            .iter()
+            .any(|contact| contact.user.id == user_id && contact.online)
+    }
+
+    /// Returns the presence of the given contact, as last reported by the server.
+    pub fn presence(&self, user_id: u64) -> proto::PresenceStatus {
+        if !self.is_online(user_id) {
+            proto::PresenceStatus::Offline
+        } else if self.away_user_ids.contains(&user_id) {
+            proto::PresenceStatus::Away
+        } else {
+            proto::PresenceStatus::Online
+        }
+    }
+
+    /// Reports the current user as online or away to their contacts.
+    pub fn set_presence(&self, status: proto::PresenceStatus) -> Result<()> {
+        let client = self
+            .client
+            .upgrade()
+            .ok_or_else(|| anyhow!("can't set presence without a client"))?;
+        client.send(proto::UpdatePresence {
+            status: status.into(),
+        })
+    }
+
     pub fn incoming_contact_requests(&self) -> &[Arc<User>] {
         &self.incoming_contact_requests
     }
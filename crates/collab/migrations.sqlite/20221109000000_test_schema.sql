@@ -225,7 +225,8 @@ CREATE TABLE IF NOT EXISTS "channel_messages" (
     "sent_at" TIMESTAMP,
     "edited_at" TIMESTAMP,
     "nonce" BLOB NOT NULL,
-    "reply_to_message_id" INTEGER DEFAULT NULL
+    "reply_to_message_id" INTEGER DEFAULT NULL,
+    "deleted_at" TIMESTAMP
 );
 CREATE INDEX "index_channel_messages_on_channel_id" ON "channel_messages" ("channel_id");
 CREATE UNIQUE INDEX "index_channel_messages_on_sender_id_nonce" ON "channel_messages" ("sender_id", "nonce");
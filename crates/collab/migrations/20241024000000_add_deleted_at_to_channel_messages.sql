@@ -0,0 +1 @@
+ALTER TABLE channel_messages ADD deleted_at TIMESTAMP DEFAULT NULL;
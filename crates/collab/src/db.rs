@@ -5,7 +5,7 @@ mod tables;
 pub mod tests;
 
 use crate::{executor::Executor, Error, Result};
-use anyhow::anyhow;
+use anyhow::{anyhow, Context as _};
 use collections::{BTreeMap, HashMap, HashSet};
 use dashmap::DashMap;
 use futures::StreamExt;
@@ -93,6 +93,45 @@ impl Database {
         &self.options
     }
 
+    /// Returns an error if the migration with the given version hasn't been applied yet,
+    /// so that a server deployed against an outdated schema fails at startup.
+    pub async fn assert_migrations_applied(&self, expected_version: i64) -> Result<()> {
+        self.run(async {
+            let row = self
+                .pool
+                .query_one(Statement::from_string(
+                    self.pool.get_database_backend(),
+                    "SELECT MAX(version) AS version FROM _sqlx_migrations WHERE success",
+                ))
+                .await
+                .context("failed to query applied migrations")?;
+            let applied_version = match row {
+                Some(row) => row.try_get::<Option<i64>>("", "version")?,
+                None => None,
+            };
+
+            match applied_version {
+                Some(applied_version) if applied_version >= expected_version => Ok(()),
+                Some(applied_version) => Err(anyhow!(
+                    "database schema is out of date: expected migration {expected_version} to be applied, but the latest applied migration is {applied_version}"
+                ))?,
+                None => Err(anyhow!(
+                    "database schema is out of date: expected migration {expected_version} to be applied, but no migrations have been applied"
+                ))?,
+            }
+        })
+        .await
+    }
+
+    /// Runs a trivial query to check that the database is reachable.
+    pub async fn check_connection(&self) -> Result<()> {
+        self.run(async {
+            self.pool.execute_unprepared("SELECT 1").await?;
+            Ok(())
+        })
+        .await
+    }
+
     #[cfg(test)]
     pub fn reset(&self) {
         self.rooms.clear();
@@ -475,6 +514,8 @@ pub struct CreatedChannelMessage {
     pub message_id: MessageId,
     pub participant_connection_ids: HashSet<ConnectionId>,
     pub notifications: NotificationBatch,
+    /// The message that the sender already sent with the same nonce, if they're retrying it.
+    pub existing_message: Option<proto::ChannelMessage>,
 }
 
 pub struct UpdatedChannelMessage {
@@ -79,7 +79,8 @@ impl Database {
 
     /// Retrieves the messages in the specified channel.
     ///
-    /// Use `before_message_id` to paginate through the channel's messages.
+    /// Use `before_message_id` to paginate backward through the channel's messages,
+    /// or [`Self::get_channel_messages_after`] to paginate forward.
     pub async fn get_channel_messages(
         &self,
         channel_id: ChannelId,
@@ -111,6 +112,35 @@ impl Database {
         .await
     }
 
+    /// Retrieves the oldest messages in the specified channel that were sent after
+    /// `after_message_id`, for fetching only the messages a client hasn't seen yet.
+    pub async fn get_channel_messages_after(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+        count: usize,
+        after_message_id: MessageId,
+    ) -> Result<Vec<proto::ChannelMessage>> {
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_participant(&channel, user_id, &tx)
+                .await?;
+
+            let mut rows = channel_message::Entity::find()
+                .filter(channel_message::Column::ChannelId.eq(channel_id))
+                .filter(channel_message::Column::Id.gt(after_message_id))
+                .order_by_asc(channel_message::Column::Id)
+                .limit(count as u64)
+                .all(&*tx)
+                .await?;
+
+            // Messages are loaded from newest to oldest.
+            rows.reverse();
+            self.load_channel_messages(rows, &tx).await
+        })
+        .await
+    }
+
     /// Returns the channel messages with the given IDs.
     pub async fn get_channel_messages_by_id(
         &self,
@@ -166,6 +196,9 @@ impl Database {
                     edited_at: row
                         .edited_at
                         .map(|t| t.assume_utc().unix_timestamp() as u64),
+                    deleted_at: row
+                        .deleted_at
+                        .map(|t| t.assume_utc().unix_timestamp() as u64),
                 }
             })
             .collect::<Vec<_>>();
@@ -228,6 +261,25 @@ impl Database {
             .collect::<Vec<_>>())
     }
 
+    /// Returns whether the user has already sent a message with the given nonce to the channel.
+    pub async fn has_channel_message_with_nonce(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+        nonce: u128,
+    ) -> Result<bool> {
+        self.transaction(|tx| async move {
+            let count = channel_message::Entity::find()
+                .filter(channel_message::Column::ChannelId.eq(channel_id))
+                .filter(channel_message::Column::SenderId.eq(user_id))
+                .filter(channel_message::Column::Nonce.eq(Uuid::from_u128(nonce)))
+                .count(&*tx)
+                .await?;
+            Ok(count > 0)
+        })
+        .await
+    }
+
     /// Creates a new channel message.
     #[allow(clippy::too_many_arguments)]
     pub async fn create_channel_message(
@@ -279,6 +331,7 @@ impl Database {
                 id: ActiveValue::NotSet,
                 reply_to_message_id: ActiveValue::Set(reply_to_message_id),
                 edited_at: ActiveValue::NotSet,
+                deleted_at: ActiveValue::NotSet,
             })
             .on_conflict(
                 OnConflict::columns([
@@ -293,6 +346,7 @@ impl Database {
             .await?;
 
             let message_id;
+            let mut existing_message = None;
             let mut notifications = Vec::new();
             match result {
                 TryInsertResult::Inserted(result) => {
@@ -327,12 +381,22 @@ impl Database {
                         .await?;
                 }
                 _ => {
-                    message_id = channel_message::Entity::find()
+                    // The client is retrying a message we already stored, so return
+                    // the existing message instead of creating a duplicate.
+                    let existing_row = channel_message::Entity::find()
+                        .filter(channel_message::Column::SenderId.eq(user_id))
                         .filter(channel_message::Column::Nonce.eq(Uuid::from_u128(nonce)))
                         .one(&*tx)
                         .await?
-                        .ok_or_else(|| anyhow!("failed to insert message"))?
-                        .id;
+                        .ok_or_else(|| anyhow!("failed to insert message"))?;
+                    if existing_row.channel_id != channel_id {
+                        Err(anyhow!("nonce was already used in another channel"))?;
+                    }
+                    message_id = existing_row.id;
+                    existing_message = self
+                        .load_channel_messages(vec![existing_row], &tx)
+                        .await?
+                        .pop();
                 }
             }
 
@@ -340,6 +404,7 @@ impl Database {
                 message_id,
                 participant_connection_ids,
                 notifications,
+                existing_message,
             })
         })
         .await
@@ -484,7 +549,7 @@ impl Database {
             .map(|kind| kind.0 .0)
     }
 
-    /// Removes the channel message with the given ID.
+    /// Deletes the channel message with the given ID, leaving a tombstone in its place.
     pub async fn remove_channel_message(
         &self,
         channel_id: ChannelId,
@@ -512,22 +577,20 @@ impl Database {
                 Err(anyhow!("not a chat participant"))?;
             }
 
-            let result = channel_message::Entity::delete_by_id(message_id)
-                .filter(channel_message::Column::SenderId.eq(user_id))
-                .exec(&*tx)
+            let rows_affected = self
+                .tombstone_channel_message(message_id, Some(user_id), &tx)
                 .await?;
-
-            if result.rows_affected == 0 {
+            if rows_affected == 0 {
                 let channel = self.get_channel_internal(channel_id, &tx).await?;
                 if self
                     .check_user_is_channel_admin(&channel, user_id, &tx)
                     .await
                     .is_ok()
                 {
-                    let result = channel_message::Entity::delete_by_id(message_id)
-                        .exec(&*tx)
+                    let rows_affected = self
+                        .tombstone_channel_message(message_id, None, &tx)
                         .await?;
-                    if result.rows_affected == 0 {
+                    if rows_affected == 0 {
                         Err(anyhow!("no such message"))?;
                     }
                 } else {
@@ -535,6 +598,11 @@ impl Database {
                 }
             }
 
+            channel_message_mention::Entity::delete_many()
+                .filter(channel_message_mention::Column::MessageId.eq(message_id))
+                .exec(&*tx)
+                .await?;
+
             let notification_kind_id =
                 self.get_notification_kind_id_by_name("ChannelMessageMention");
 
@@ -562,6 +630,28 @@ impl Database {
         .await
     }
 
+    /// Clears the body of a message and marks it as deleted, optionally requiring it to
+    /// have been sent by the given user. Deleted messages are kept, so that clients can
+    /// show a placeholder in their place. Returns the number of messages affected.
+    async fn tombstone_channel_message(
+        &self,
+        message_id: MessageId,
+        sender_id: Option<UserId>,
+        tx: &DatabaseTransaction,
+    ) -> Result<u64> {
+        let deleted_at = OffsetDateTime::now_utc();
+        let deleted_at = time::PrimitiveDateTime::new(deleted_at.date(), deleted_at.time());
+        let mut query = channel_message::Entity::update_many()
+            .col_expr(channel_message::Column::Body, Expr::value(""))
+            .col_expr(channel_message::Column::DeletedAt, Expr::value(deleted_at))
+            .filter(channel_message::Column::Id.eq(message_id))
+            .filter(channel_message::Column::DeletedAt.is_null());
+        if let Some(sender_id) = sender_id {
+            query = query.filter(channel_message::Column::SenderId.eq(sender_id));
+        }
+        Ok(query.exec(tx).await?.rows_affected)
+    }
+
     /// Updates the channel message with the given ID, body and timestamp(edited_at).
     pub async fn update_channel_message(
         &self,
@@ -601,6 +691,7 @@ impl Database {
 
             let channel_message = channel_message::Entity::find_by_id(message_id)
                 .filter(channel_message::Column::SenderId.eq(user_id))
+                .filter(channel_message::Column::DeletedAt.is_null())
                 .one(&*tx)
                 .await?;
 
@@ -620,6 +711,7 @@ impl Database {
                 sender_id: ActiveValue::Unchanged(user_id),
                 sent_at: ActiveValue::Unchanged(channel_message.sent_at),
                 nonce: ActiveValue::Unchanged(channel_message.nonce),
+                deleted_at: ActiveValue::Unchanged(None),
             };
 
             let result = channel_message::Entity::update_many()
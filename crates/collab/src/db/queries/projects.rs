@@ -243,6 +243,23 @@ impl Database {
                 .await?
                 .ok_or_else(|| anyhow!("no such project: {project_id}"))?;
 
+            // Ignore updates that were delivered out of order. A single scan may be
+            // split across several updates, so only strictly older scans are stale.
+            let worktree = worktree::Entity::find_by_id((worktree_id, project_id))
+                .one(&*tx)
+                .await?;
+            if let Some(worktree) = worktree {
+                if (update.scan_id as i64) < worktree.scan_id {
+                    log::info!(
+                        "ignoring stale worktree update. worktree: {}, scan id: {}, latest scan id: {}",
+                        worktree_id,
+                        update.scan_id,
+                        worktree.scan_id
+                    );
+                    return Ok(Vec::new());
+                }
+            }
+
             // Update metadata.
             worktree::Entity::update(worktree::ActiveModel {
                 id: ActiveValue::set(worktree_id),
@@ -757,76 +774,108 @@ impl Database {
         connection: ConnectionId,
     ) -> Result<TransactionGuard<(Option<proto::Room>, LeftProject)>> {
         self.project_transaction(project_id, |tx| async move {
-            let result = project_collaborator::Entity::delete_many()
-                .filter(
-                    Condition::all()
-                        .add(project_collaborator::Column::ProjectId.eq(project_id))
-                        .add(project_collaborator::Column::ConnectionId.eq(connection.id as i32))
-                        .add(
-                            project_collaborator::Column::ConnectionServerId
-                                .eq(connection.owner_id as i32),
-                        ),
-                )
-                .exec(&*tx)
-                .await?;
-            if result.rows_affected == 0 {
-                Err(anyhow!("not a collaborator on this project"))?;
-            }
+            self.leave_project_internal(project_id, connection, &tx)
+                .await
+        })
+        .await
+    }
 
+    /// Removes a guest from the given project, on behalf of the project's host.
+    pub async fn remove_project_guest(
+        &self,
+        project_id: ProjectId,
+        host_connection: ConnectionId,
+        guest_connection: ConnectionId,
+    ) -> Result<TransactionGuard<(Option<proto::Room>, LeftProject)>> {
+        self.project_transaction(project_id, |tx| async move {
             let project = project::Entity::find_by_id(project_id)
                 .one(&*tx)
                 .await?
                 .ok_or_else(|| anyhow!("no such project"))?;
-            let collaborators = project
-                .find_related(project_collaborator::Entity)
-                .all(&*tx)
-                .await?;
-            let connection_ids: Vec<ConnectionId> = collaborators
-                .into_iter()
-                .map(|collaborator| collaborator.connection())
-                .collect();
-
-            follower::Entity::delete_many()
-                .filter(
-                    Condition::any()
-                        .add(
-                            Condition::all()
-                                .add(follower::Column::ProjectId.eq(Some(project_id)))
-                                .add(
-                                    follower::Column::LeaderConnectionServerId
-                                        .eq(connection.owner_id),
-                                )
-                                .add(follower::Column::LeaderConnectionId.eq(connection.id)),
-                        )
-                        .add(
-                            Condition::all()
-                                .add(follower::Column::ProjectId.eq(Some(project_id)))
-                                .add(
-                                    follower::Column::FollowerConnectionServerId
-                                        .eq(connection.owner_id),
-                                )
-                                .add(follower::Column::FollowerConnectionId.eq(connection.id)),
-                        ),
-                )
-                .exec(&*tx)
-                .await?;
-
-            let room = if let Some(room_id) = project.room_id {
-                Some(self.get_room(room_id, &tx).await?)
-            } else {
-                None
-            };
+            if project.host_connection()? != host_connection {
+                Err(anyhow!("only the host can remove guests from a project"))?;
+            }
+            if guest_connection == host_connection {
+                Err(anyhow!("the host can't be removed from a project"))?;
+            }
 
-            let left_project = LeftProject {
-                id: project_id,
-                should_unshare: connection == project.host_connection()?,
-                connection_ids,
-            };
-            Ok((room, left_project))
+            self.leave_project_internal(project_id, guest_connection, &tx)
+                .await
         })
         .await
     }
 
+    async fn leave_project_internal(
+        &self,
+        project_id: ProjectId,
+        connection: ConnectionId,
+        tx: &DatabaseTransaction,
+    ) -> Result<(Option<proto::Room>, LeftProject)> {
+        let result = project_collaborator::Entity::delete_many()
+            .filter(
+                Condition::all()
+                    .add(project_collaborator::Column::ProjectId.eq(project_id))
+                    .add(project_collaborator::Column::ConnectionId.eq(connection.id as i32))
+                    .add(
+                        project_collaborator::Column::ConnectionServerId
+                            .eq(connection.owner_id as i32),
+                    ),
+            )
+            .exec(tx)
+            .await?;
+        if result.rows_affected == 0 {
+            Err(anyhow!("not a collaborator on this project"))?;
+        }
+
+        let project = project::Entity::find_by_id(project_id)
+            .one(tx)
+            .await?
+            .ok_or_else(|| anyhow!("no such project"))?;
+        let collaborators = project
+            .find_related(project_collaborator::Entity)
+            .all(tx)
+            .await?;
+        let connection_ids: Vec<ConnectionId> = collaborators
+            .into_iter()
+            .map(|collaborator| collaborator.connection())
+            .collect();
+
+        follower::Entity::delete_many()
+            .filter(
+                Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(follower::Column::ProjectId.eq(Some(project_id)))
+                            .add(follower::Column::LeaderConnectionServerId.eq(connection.owner_id))
+                            .add(follower::Column::LeaderConnectionId.eq(connection.id)),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(follower::Column::ProjectId.eq(Some(project_id)))
+                            .add(
+                                follower::Column::FollowerConnectionServerId
+                                    .eq(connection.owner_id),
+                            )
+                            .add(follower::Column::FollowerConnectionId.eq(connection.id)),
+                    ),
+            )
+            .exec(tx)
+            .await?;
+
+        let room = if let Some(room_id) = project.room_id {
+            Some(self.get_room(room_id, tx).await?)
+        } else {
+            None
+        };
+
+        let left_project = LeftProject {
+            id: project_id,
+            should_unshare: connection == project.host_connection()?,
+            connection_ids,
+        };
+        Ok((room, left_project))
+    }
+
     pub async fn check_user_is_project_host(
         &self,
         project_id: ProjectId,
@@ -562,6 +562,317 @@ async fn test_project_count(db: &Arc<Database>) {
     assert_eq!(db.project_count_excluding_admins().await.unwrap(), 0);
 }
 
+test_both_dbs!(
+    test_out_of_order_worktree_updates,
+    test_out_of_order_worktree_updates_postgres,
+    test_out_of_order_worktree_updates_sqlite
+);
+
+async fn test_out_of_order_worktree_updates(db: &Arc<Database>) {
+    let owner_id = db.create_server("test").await.unwrap().0 as u32;
+    let host_connection = ConnectionId { owner_id, id: 0 };
+    let guest_connection = ConnectionId { owner_id, id: 1 };
+
+    let host = db
+        .create_user(
+            "host@example.com",
+            false,
+            NewUserParams {
+                github_login: "host".into(),
+                github_user_id: 0,
+            },
+        )
+        .await
+        .unwrap()
+        .user_id;
+    let guest = db
+        .create_user(
+            "guest@example.com",
+            false,
+            NewUserParams {
+                github_login: "guest".into(),
+                github_user_id: 1,
+            },
+        )
+        .await
+        .unwrap()
+        .user_id;
+
+    let room_id = RoomId::from_proto(db.create_room(host, host_connection, "").await.unwrap().id);
+    db.call(room_id, host, host_connection, guest, None)
+        .await
+        .unwrap();
+    db.join_room(room_id, guest, guest_connection)
+        .await
+        .unwrap();
+
+    let worktree = proto::WorktreeMetadata {
+        id: 1,
+        root_name: "root".into(),
+        visible: true,
+        abs_path: "/root".into(),
+    };
+    let (project_id, _) = db
+        .share_project(room_id, host_connection, &[worktree], false)
+        .await
+        .unwrap()
+        .into_inner();
+
+    let update = |scan_id: u64, path: &str| proto::UpdateWorktree {
+        project_id: project_id.to_proto(),
+        worktree_id: 1,
+        root_name: "root".into(),
+        abs_path: "/root".into(),
+        updated_entries: vec![proto::Entry {
+            id: 1,
+            path: path.into(),
+            ..Default::default()
+        }],
+        scan_id,
+        is_last_update: true,
+        ..Default::default()
+    };
+
+    db.update_worktree(&update(2, "b.txt"), host_connection)
+        .await
+        .unwrap();
+    // The delayed update from the earlier scan is ignored.
+    db.update_worktree(&update(1, "a.txt"), host_connection)
+        .await
+        .unwrap();
+
+    let (project, _) = db
+        .join_project(project_id, guest_connection, guest)
+        .await
+        .unwrap()
+        .into_inner();
+    let worktree = &project.worktrees[&1];
+    assert_eq!(worktree.scan_id, 2);
+    assert_eq!(worktree.completed_scan_id, 2);
+    assert_eq!(
+        worktree
+            .entries
+            .iter()
+            .map(|entry| entry.path.as_str())
+            .collect::<Vec<_>>(),
+        ["b.txt"]
+    );
+}
+
+test_both_dbs!(
+    test_rejoined_guests_receive_worktree_deltas,
+    test_rejoined_guests_receive_worktree_deltas_postgres,
+    test_rejoined_guests_receive_worktree_deltas_sqlite
+);
+
+async fn test_rejoined_guests_receive_worktree_deltas(db: &Arc<Database>) {
+    let owner_id = db.create_server("test").await.unwrap().0 as u32;
+    let host_connection = ConnectionId { owner_id, id: 0 };
+    let guest_connection = ConnectionId { owner_id, id: 1 };
+
+    let host = db
+        .create_user(
+            "host@example.com",
+            false,
+            NewUserParams {
+                github_login: "host".into(),
+                github_user_id: 0,
+            },
+        )
+        .await
+        .unwrap()
+        .user_id;
+    let guest = db
+        .create_user(
+            "guest@example.com",
+            false,
+            NewUserParams {
+                github_login: "guest".into(),
+                github_user_id: 1,
+            },
+        )
+        .await
+        .unwrap()
+        .user_id;
+
+    let room_id = RoomId::from_proto(db.create_room(host, host_connection, "").await.unwrap().id);
+    db.call(room_id, host, host_connection, guest, None)
+        .await
+        .unwrap();
+    db.join_room(room_id, guest, guest_connection)
+        .await
+        .unwrap();
+
+    let worktree = proto::WorktreeMetadata {
+        id: 1,
+        root_name: "root".into(),
+        visible: true,
+        abs_path: "/root".into(),
+    };
+    let (project_id, _) = db
+        .share_project(room_id, host_connection, &[worktree], false)
+        .await
+        .unwrap()
+        .into_inner();
+
+    let entry = |id: u64, path: &str| proto::Entry {
+        id,
+        path: path.into(),
+        ..Default::default()
+    };
+    db.update_worktree(
+        &proto::UpdateWorktree {
+            project_id: project_id.to_proto(),
+            worktree_id: 1,
+            root_name: "root".into(),
+            abs_path: "/root".into(),
+            updated_entries: vec![entry(1, "a.txt"), entry(2, "b.txt")],
+            scan_id: 1,
+            is_last_update: true,
+            ..Default::default()
+        },
+        host_connection,
+    )
+    .await
+    .unwrap();
+    db.join_project(project_id, guest_connection, guest)
+        .await
+        .unwrap();
+
+    // While the guest is disconnected, the host adds one entry and removes another.
+    db.connection_lost(guest_connection).await.unwrap();
+    db.update_worktree(
+        &proto::UpdateWorktree {
+            project_id: project_id.to_proto(),
+            worktree_id: 1,
+            root_name: "root".into(),
+            abs_path: "/root".into(),
+            updated_entries: vec![entry(3, "c.txt")],
+            removed_entries: vec![1],
+            scan_id: 2,
+            is_last_update: true,
+            ..Default::default()
+        },
+        host_connection,
+    )
+    .await
+    .unwrap();
+
+    // On rejoining, the guest only receives the entries that changed since the scan
+    // it last observed.
+    let new_guest_connection = ConnectionId { owner_id, id: 2 };
+    let rejoined_room = db
+        .rejoin_room(
+            proto::RejoinRoom {
+                id: room_id.to_proto(),
+                reshared_projects: Vec::new(),
+                rejoined_projects: vec![proto::RejoinProject {
+                    id: project_id.to_proto(),
+                    worktrees: vec![proto::RejoinWorktree { id: 1, scan_id: 1 }],
+                }],
+            },
+            guest,
+            new_guest_connection,
+        )
+        .await
+        .unwrap()
+        .into_inner();
+    let project = &rejoined_room.rejoined_projects[0];
+    assert_eq!(project.old_connection_id, guest_connection);
+    let worktree = &project.worktrees[0];
+    assert_eq!(worktree.scan_id, 2);
+    assert_eq!(
+        worktree
+            .updated_entries
+            .iter()
+            .map(|entry| entry.path.as_str())
+            .collect::<Vec<_>>(),
+        ["c.txt"]
+    );
+    assert_eq!(worktree.removed_entries, [1]);
+}
+
+test_both_dbs!(
+    test_project_replica_ids_are_reused,
+    test_project_replica_ids_are_reused_postgres,
+    test_project_replica_ids_are_reused_sqlite
+);
+
+async fn test_project_replica_ids_are_reused(db: &Arc<Database>) {
+    let owner_id = db.create_server("test").await.unwrap().0 as u32;
+
+    let mut users = Vec::new();
+    for i in 0..5 {
+        let user_id = db
+            .create_user(
+                &format!("user{i}@example.com"),
+                false,
+                NewUserParams {
+                    github_login: format!("user{i}"),
+                    github_user_id: i,
+                },
+            )
+            .await
+            .unwrap()
+            .user_id;
+        users.push((
+            user_id,
+            ConnectionId {
+                owner_id,
+                id: i as u32,
+            },
+        ));
+    }
+
+    let (host, host_connection) = users[0];
+    let room_id = RoomId::from_proto(db.create_room(host, host_connection, "").await.unwrap().id);
+    for &(guest, guest_connection) in &users[1..] {
+        db.call(room_id, host, host_connection, guest, None)
+            .await
+            .unwrap();
+        db.join_room(room_id, guest, guest_connection)
+            .await
+            .unwrap();
+    }
+    let (project_id, _) = db
+        .share_project(room_id, host_connection, &[], false)
+        .await
+        .unwrap()
+        .into_inner();
+
+    let mut replica_ids = Vec::new();
+    for &(guest, guest_connection) in &users[1..4] {
+        let (_, replica_id) = db
+            .join_project(project_id, guest_connection, guest)
+            .await
+            .unwrap()
+            .into_inner();
+        replica_ids.push(replica_id);
+    }
+    assert_eq!(replica_ids, [ReplicaId(1), ReplicaId(2), ReplicaId(3)]);
+
+    // The lowest replica id that isn't in use is handed out to the next guest.
+    db.leave_project(project_id, users[2].1).await.unwrap();
+    let (guest, guest_connection) = users[4];
+    let (project, replica_id) = db
+        .join_project(project_id, guest_connection, guest)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(replica_id, ReplicaId(2));
+
+    let mut collaborator_replica_ids = project
+        .collaborators
+        .iter()
+        .map(|collaborator| collaborator.replica_id)
+        .collect::<Vec<_>>();
+    collaborator_replica_ids.sort();
+    assert_eq!(
+        collaborator_replica_ids,
+        [ReplicaId(0), ReplicaId(1), ReplicaId(2), ReplicaId(3)]
+    );
+}
+
 #[test]
 fn test_fuzzy_like_string() {
     assert_eq!(Database::fuzzy_like_string("abcd"), "%a%b%c%d%");
@@ -616,3 +927,60 @@ async fn test_fuzzy_search_users(cx: &mut gpui::TestAppContext) {
             .collect::<Vec<_>>()
     }
 }
+
+test_both_dbs!(
+    test_check_connection,
+    test_check_connection_postgres,
+    test_check_connection_sqlite
+);
+
+async fn test_check_connection(db: &Arc<Database>) {
+    db.check_connection().await.unwrap();
+}
+
+#[gpui::test]
+async fn test_check_connection_when_disconnected(cx: &mut gpui::TestAppContext) {
+    let mut test_db = TestDb::sqlite(cx.executor());
+    let db = Arc::get_mut(test_db.db.as_mut().unwrap()).unwrap();
+    let pool = std::mem::replace(&mut db.pool, sea_orm::DatabaseConnection::Disconnected);
+    assert!(db.check_connection().await.is_err());
+
+    // Restore the pool so that the test database can be torn down.
+    db.pool = pool;
+    db.check_connection().await.unwrap();
+}
+
+#[gpui::test]
+async fn test_assert_migrations_applied(cx: &mut gpui::TestAppContext) {
+    let test_db = TestDb::sqlite(cx.executor());
+    let db = test_db.db();
+
+    // The SQLite test schema isn't created by migrations, so record one manually.
+    db.pool
+        .execute_unprepared(
+            "CREATE TABLE IF NOT EXISTS _sqlx_migrations (
+                version BIGINT PRIMARY KEY,
+                description TEXT NOT NULL,
+                installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                success BOOLEAN NOT NULL,
+                checksum BLOB NOT NULL,
+                execution_time BIGINT NOT NULL
+            );
+            INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+            VALUES (20240101000000, 'first', TRUE, x'00', 0);",
+        )
+        .await
+        .unwrap();
+
+    db.assert_migrations_applied(20230101000000).await.unwrap();
+    db.assert_migrations_applied(20240101000000).await.unwrap();
+
+    let error = db
+        .assert_migrations_applied(20240201000000)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "database schema is out of date: expected migration 20240201000000 to be applied, but the latest applied migration is 20240101000000"
+    );
+}
@@ -65,6 +65,83 @@ async fn test_channel_message_retrieval(db: &Arc<Database>) {
     assert_eq!(messages, &all_messages[2..6]);
 }
 
+test_both_dbs!(
+    test_channel_message_forward_pagination,
+    test_channel_message_forward_pagination_postgres,
+    test_channel_message_forward_pagination_sqlite
+);
+
+async fn test_channel_message_forward_pagination(db: &Arc<Database>) {
+    let user = new_test_user(db, "user@example.com").await;
+    let channel = db.create_channel("channel", None, user).await.unwrap().0;
+
+    let owner_id = db.create_server("test").await.unwrap().0 as u32;
+    db.join_channel_chat(channel.id, rpc::ConnectionId { owner_id, id: 0 }, user)
+        .await
+        .unwrap();
+
+    let mut all_messages = Vec::new();
+    for i in 0..150 {
+        all_messages.push(
+            db.create_channel_message(
+                channel.id,
+                user,
+                &i.to_string(),
+                &[],
+                OffsetDateTime::now_utc(),
+                i,
+                None,
+            )
+            .await
+            .unwrap()
+            .message_id
+            .to_proto(),
+        );
+    }
+
+    // A client that has only seen the first message fetches the rest in two pages,
+    // oldest first.
+    let messages = db
+        .get_channel_messages_after(
+            channel.id,
+            user,
+            100,
+            MessageId::from_proto(all_messages[0]),
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|message| message.id)
+        .collect::<Vec<_>>();
+    assert_eq!(messages, &all_messages[1..101]);
+
+    let messages = db
+        .get_channel_messages_after(
+            channel.id,
+            user,
+            100,
+            MessageId::from_proto(*messages.last().unwrap()),
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|message| message.id)
+        .collect::<Vec<_>>();
+    assert_eq!(messages, &all_messages[101..150]);
+
+    // There are no messages after the latest one.
+    let messages = db
+        .get_channel_messages_after(
+            channel.id,
+            user,
+            100,
+            MessageId::from_proto(all_messages[149]),
+        )
+        .await
+        .unwrap();
+    assert!(messages.is_empty());
+}
+
 test_both_dbs!(
     test_channel_message_nonces,
     test_channel_message_nonces_postgres,
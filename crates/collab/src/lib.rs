@@ -6,6 +6,7 @@ pub mod db;
 pub mod env;
 pub mod executor;
 pub mod llm;
+pub mod logging;
 pub mod migrations;
 mod rate_limiter;
 pub mod rpc;
@@ -28,7 +29,7 @@ use executor::Executor;
 use llm::db::LlmDatabase;
 pub use rate_limiter::*;
 use serde::Deserialize;
-use std::{path::PathBuf, sync::Arc};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 use util::ResultExt;
 
 use crate::stripe_billing::StripeBilling;
@@ -165,6 +166,9 @@ pub struct Config {
     pub llm_api_secret: Option<String>,
     pub rust_log: Option<String>,
     pub log_json: Option<bool>,
+    pub log_stdout: Option<bool>,
+    pub log_file: Option<PathBuf>,
+    pub log_file_max_bytes: Option<u64>,
     pub blob_store_url: Option<String>,
     pub blob_store_region: Option<String>,
     pub blob_store_access_key: Option<String>,
@@ -182,6 +186,9 @@ pub struct Config {
     pub stripe_api_key: Option<String>,
     pub supermaven_admin_api_key: Option<Arc<str>>,
     pub user_backfiller_github_access_token: Option<Arc<str>>,
+    pub rpc_keepalive_interval_ms: Option<u64>,
+    pub rpc_receive_timeout_ms: Option<u64>,
+    pub contact_update_debounce_ms: Option<u64>,
 }
 
 impl Config {
@@ -189,6 +196,25 @@ impl Config {
         self.zed_environment == "development".into()
     }
 
+    /// How long to wait before pinging a client that we haven't sent anything to.
+    pub fn rpc_keepalive_interval(&self) -> Duration {
+        self.rpc_keepalive_interval_ms
+            .map_or(::rpc::KEEPALIVE_INTERVAL, Duration::from_millis)
+    }
+
+    /// How long a client can go without sending anything, including responses to our
+    /// pings, before its connection is dropped and it's signed out.
+    pub fn rpc_receive_timeout(&self) -> Duration {
+        self.rpc_receive_timeout_ms
+            .map_or(::rpc::RECEIVE_TIMEOUT, Duration::from_millis)
+    }
+
+    /// How long to wait for more changes to a user's status before telling their contacts.
+    pub fn contact_update_debounce(&self) -> Duration {
+        self.contact_update_debounce_ms
+            .map_or(rpc::CONTACT_UPDATE_DEBOUNCE, Duration::from_millis)
+    }
+
     /// Returns the base `zed.dev` URL.
     pub fn zed_dot_dev_url(&self) -> &str {
         match self.zed_environment.as_ref() {
@@ -215,6 +241,9 @@ impl Config {
             llm_api_secret: None,
             rust_log: None,
             log_json: None,
+            log_stdout: None,
+            log_file: None,
+            log_file_max_bytes: None,
             zed_environment: "test".into(),
             blob_store_url: None,
             blob_store_region: None,
@@ -238,6 +267,9 @@ impl Config {
             stripe_api_key: None,
             supermaven_admin_api_key: None,
             user_backfiller_github_access_token: None,
+            rpc_keepalive_interval_ms: None,
+            rpc_receive_timeout_ms: None,
+            contact_update_debounce_ms: None,
         }
     }
 }
@@ -284,6 +316,11 @@ impl AppState {
         let mut db_options = db::ConnectOptions::new(config.database_url.clone());
         db_options.max_connections(config.database_max_connections);
         let mut db = Database::new(db_options, Executor::Production).await?;
+        if let Some(migrations_path) = &config.migrations_path {
+            if let Some(version) = migrations::latest_migration_version(migrations_path).await? {
+                db.assert_migrations_applied(version).await?;
+            }
+        }
         db.initialize_notification_kinds().await?;
 
         let llm_db = if let Some((llm_database_url, llm_database_max_connections)) = config
@@ -0,0 +1,170 @@
+use parking_lot::Mutex;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+use tracing::Subscriber;
+use tracing_subscriber::{
+    fmt::{format::JsonFields, MakeWriter},
+    registry::LookupSpan,
+    Layer,
+};
+
+/// The size at which log files are rotated when no size is configured.
+pub const DEFAULT_LOG_FILE_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Builds a formatting layer that writes events to the given writer, either as
+/// one JSON object per line or in a human-readable format. ANSI colors should
+/// only be enabled when writing to a terminal.
+pub fn fmt_layer<S, W>(json: bool, ansi: bool, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    if json {
+        Box::new(
+            tracing_subscriber::fmt::layer()
+                .with_writer(writer)
+                .fmt_fields(JsonFields::default())
+                .event_format(
+                    tracing_subscriber::fmt::format()
+                        .json()
+                        .flatten_event(true)
+                        .with_span_list(false),
+                ),
+        )
+    } else {
+        Box::new(
+            tracing_subscriber::fmt::layer()
+                .with_writer(writer)
+                .with_ansi(ansi)
+                .event_format(tracing_subscriber::fmt::format().pretty()),
+        )
+    }
+}
+
+/// A log file that is moved aside to `<path>.1` once it grows past a maximum size,
+/// replacing any previously rotated file.
+pub struct RotatingLogFile {
+    path: PathBuf,
+    max_bytes: u64,
+    state: Mutex<LogFileState>,
+}
+
+struct LogFileState {
+    file: File,
+    len: u64,
+}
+
+impl RotatingLogFile {
+    pub fn open(path: impl Into<PathBuf>, max_bytes: u64) -> io::Result<Self> {
+        let path = path.into();
+        let file = Self::open_file(&path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_bytes,
+            state: Mutex::new(LogFileState { file, len }),
+        })
+    }
+
+    pub fn rotated_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".1");
+        path.into()
+    }
+
+    fn open_file(path: &Path) -> io::Result<File> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+
+    fn write_event(&self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.state.lock();
+
+        // Each event is written in a single call, so rotating here never splits a line.
+        if state.len > 0 && state.len + buf.len() as u64 > self.max_bytes {
+            state.file.flush()?;
+            fs::rename(&self.path, self.rotated_path())?;
+            state.file = Self::open_file(&self.path)?;
+            state.len = 0;
+        }
+
+        let written = state.file.write(buf)?;
+        state.len += written as u64;
+        Ok(written)
+    }
+}
+
+impl Write for &RotatingLogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_event(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.state.lock().file.flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingLogFile {
+    type Writer = &'a RotatingLogFile;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_json_events_are_written_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs/collab.log");
+        let file = RotatingLogFile::open(&path, DEFAULT_LOG_FILE_MAX_BYTES).unwrap();
+
+        let subscriber = tracing_subscriber::registry().with(fmt_layer(true, false, file));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(user_id = 5, "user connected");
+            tracing::warn!(user_id = 6, "user disconnected");
+        });
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let events = contents
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["level"], "INFO");
+        assert_eq!(events[0]["message"], "user connected");
+        assert_eq!(events[0]["user_id"], 5);
+        assert_eq!(events[0]["target"], "collab::logging::tests");
+        assert!(events[0]["timestamp"].is_string());
+        assert_eq!(events[1]["level"], "WARN");
+        assert_eq!(events[1]["message"], "user disconnected");
+        assert_eq!(events[1]["user_id"], 6);
+    }
+
+    #[test]
+    fn test_log_file_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("collab.log");
+        let file = RotatingLogFile::open(&path, 10).unwrap();
+        let rotated_path = file.rotated_path();
+
+        (&file).write_all(b"first\n").unwrap();
+        (&file).write_all(b"second\n").unwrap();
+        assert_eq!(fs::read_to_string(&rotated_path).unwrap(), "first\n");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second\n");
+
+        // Only a single rotated file is kept.
+        (&file).write_all(b"third\n").unwrap();
+        assert_eq!(fs::read_to_string(&rotated_path).unwrap(), "second\n");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "third\n");
+    }
+}
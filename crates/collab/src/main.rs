@@ -2,19 +2,23 @@ use anyhow::anyhow;
 use axum::headers::HeaderMapExt;
 use axum::{
     extract::MatchedPath,
-    http::{Request, Response},
+    http::{Request, Response, StatusCode},
     routing::get,
     Extension, Router,
 };
 use collab::api::billing::sync_llm_usage_with_stripe_periodically;
 use collab::api::CloudflareIpCountryHeader;
 use collab::llm::{db::LlmDatabase, log_usage_periodically};
+use collab::logging::{fmt_layer, RotatingLogFile, DEFAULT_LOG_FILE_MAX_BYTES};
 use collab::migrations::run_database_migrations;
 use collab::user_backfiller::spawn_user_backfiller;
 use collab::{api::billing::poll_stripe_events_periodically, llm::LlmState, ServiceMode};
 use collab::{
-    api::fetch_extensions_from_blob_store_periodically, db, env, executor::Executor,
-    rpc::ResultExt, AppState, Config, RateLimiter, Result,
+    api::fetch_extensions_from_blob_store_periodically,
+    db, env,
+    executor::Executor,
+    rpc::{ResultExt, SHUTDOWN_TIMEOUT},
+    AppState, Config, Error, RateLimiter, Result,
 };
 use db::Database;
 use std::{
@@ -27,9 +31,7 @@ use std::{
 #[cfg(unix)]
 use tokio::signal::unix::SignalKind;
 use tower_http::trace::TraceLayer;
-use tracing_subscriber::{
-    filter::EnvFilter, fmt::format::JsonFields, util::SubscriberInitExt, Layer,
-};
+use tracing_subscriber::{filter::EnvFilter, util::SubscriberInitExt, Layer};
 use util::{maybe, ResultExt as _};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -94,7 +96,7 @@ async fn main() -> Result<()> {
             let listener = TcpListener::bind(format!("0.0.0.0:{}", config.http_port))
                 .expect("failed to bind TCP listener");
 
-            let mut on_shutdown = None;
+            let mut rpc_server_to_drain = None;
 
             if mode.is_llm() {
                 setup_llm_database(&config).await?;
@@ -140,7 +142,7 @@ async fn main() -> Result<()> {
                         .merge(collab::api::routes(rpc_server.clone()))
                         .merge(collab::rpc::routes(rpc_server.clone()));
 
-                    on_shutdown = Some(Box::new(move || rpc_server.teardown()));
+                    rpc_server_to_drain = Some(rpc_server);
                 }
 
                 if mode.is_api() {
@@ -249,8 +251,15 @@ async fn main() -> Result<()> {
                     signal.await;
                     tracing::info!("Received interrupt signal");
 
-                    if let Some(on_shutdown) = on_shutdown {
-                        on_shutdown();
+                    if let Some(rpc_server) = rpc_server_to_drain {
+                        rpc_server.begin_shutdown();
+                        if !rpc_server
+                            .wait_for_connections_to_close(SHUTDOWN_TIMEOUT, Executor::Production)
+                            .await
+                        {
+                            tracing::warn!("shutting down with connections still open");
+                        }
+                        rpc_server.teardown();
                     }
                 })
                 .await
@@ -339,12 +348,21 @@ async fn handle_liveness_probe(
     app_state: Option<Extension<Arc<AppState>>>,
     llm_state: Option<Extension<Arc<LlmState>>>,
 ) -> Result<String> {
+    // Report an unreachable database as 503 so that load balancers stop routing to this instance.
+    let unavailable = |error: Error| {
+        Error::Http(
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!("database is unavailable: {error}"),
+            Default::default(),
+        )
+    };
+
     if let Some(state) = app_state {
-        state.db.get_all_users(0, 1).await?;
+        state.db.check_connection().await.map_err(unavailable)?;
     }
 
     if let Some(llm_state) = llm_state {
-        llm_state.db.list_providers().await?;
+        llm_state.db.list_providers().await.map_err(unavailable)?;
     }
 
     Ok("ok".to_string())
@@ -354,29 +372,44 @@ pub fn init_tracing(config: &Config) -> Option<()> {
     use std::str::FromStr;
     use tracing_subscriber::layer::SubscriberExt;
 
-    let filter = EnvFilter::from_str(config.rust_log.as_deref()?).log_err()?;
-
-    tracing_subscriber::registry()
-        .with(if config.log_json.unwrap_or(false) {
-            Box::new(
-                tracing_subscriber::fmt::layer()
-                    .fmt_fields(JsonFields::default())
-                    .event_format(
-                        tracing_subscriber::fmt::format()
-                            .json()
-                            .flatten_event(true)
-                            .with_span_list(false),
-                    )
-                    .with_filter(filter),
-            ) as Box<dyn Layer<_> + Send + Sync>
-        } else {
-            Box::new(
-                tracing_subscriber::fmt::layer()
-                    .event_format(tracing_subscriber::fmt::format().pretty())
-                    .with_filter(filter),
-            )
-        })
-        .init();
+    let rust_log = config.rust_log.as_deref()?;
+    let json = config.log_json.unwrap_or(false);
+
+    let mut layers = Vec::new();
+    let mut log_stdout = config.log_stdout.unwrap_or(true);
+    let mut log_file_error = None;
+    if let Some(log_file) = &config.log_file {
+        let filter = EnvFilter::from_str(rust_log).log_err()?;
+        let max_bytes = config
+            .log_file_max_bytes
+            .unwrap_or(DEFAULT_LOG_FILE_MAX_BYTES);
+        match RotatingLogFile::open(log_file, max_bytes) {
+            Ok(file) => layers.push(fmt_layer(json, false, file).with_filter(filter).boxed()),
+            Err(error) => {
+                // Log to stdout instead, so that the server isn't left without any logs.
+                log_stdout = true;
+                log_file_error = Some((log_file, error));
+            }
+        }
+    }
+    if log_stdout {
+        let filter = EnvFilter::from_str(rust_log).log_err()?;
+        layers.push(
+            fmt_layer(json, true, std::io::stdout)
+                .with_filter(filter)
+                .boxed(),
+        );
+    }
+
+    tracing_subscriber::registry().with(layers).init();
+
+    if let Some((log_file, error)) = log_file_error {
+        tracing::error!(
+            ?log_file,
+            ?error,
+            "failed to open log file, logging to stdout"
+        );
+    }
 
     None
 }
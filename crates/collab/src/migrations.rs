@@ -7,6 +7,14 @@ use sea_orm::ConnectOptions;
 use sqlx::migrate::{Migrate, Migration, MigrationSource};
 use sqlx::Connection;
 
+/// Returns the version of the most recent migration in the specified directory.
+pub async fn latest_migration_version(migrations_path: impl AsRef<Path>) -> Result<Option<i64>> {
+    let migrations = MigrationSource::resolve(migrations_path.as_ref())
+        .await
+        .map_err(|err| anyhow!("failed to load migrations: {err:?}"))?;
+    Ok(migrations.iter().map(|migration| migration.version).max())
+}
+
 /// Runs the database migrations for the specified database.
 pub async fn run_database_migrations(
     database_options: &ConnectOptions,
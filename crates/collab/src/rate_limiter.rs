@@ -17,6 +17,8 @@ pub struct RateLimiter {
     buckets: DashMap<(UserId, String), RateBucket>,
     dirty_buckets: DashSet<(UserId, String)>,
     db: Arc<Database>,
+    #[cfg(test)]
+    clock_offset: parking_lot::Mutex<Duration>,
 }
 
 impl RateLimiter {
@@ -25,9 +27,24 @@ impl RateLimiter {
             buckets: DashMap::new(),
             dirty_buckets: DashSet::new(),
             db,
+            #[cfg(test)]
+            clock_offset: parking_lot::Mutex::new(Duration::zero()),
         }
     }
 
+    /// Moves the clock used to refill buckets forward, so tests don't have to wait.
+    #[cfg(test)]
+    pub fn advance_clock(&self, duration: Duration) {
+        *self.clock_offset.lock() += duration;
+    }
+
+    fn now(&self) -> DateTimeUtc {
+        let now = Utc::now();
+        #[cfg(test)]
+        let now = now + *self.clock_offset.lock();
+        now
+    }
+
     /// Spawns a new task that periodically saves rate limit data to the database.
     pub fn save_periodically(rate_limiter: Arc<Self>, executor: Executor) {
         const RATE_LIMITER_SAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
@@ -43,7 +60,7 @@ impl RateLimiter {
     /// Returns an error if the user has exceeded the specified `RateLimit`.
     /// Attempts to read the from the database if no cached RateBucket currently exists.
     pub async fn check(&self, limit: &dyn RateLimit, user_id: UserId) -> Result<()> {
-        self.check_internal(limit, user_id, Utc::now()).await
+        self.check_internal(limit, user_id, self.now()).await
     }
 
     async fn check_internal(
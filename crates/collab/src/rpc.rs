@@ -28,7 +28,7 @@ use axum::{
     middleware,
     response::IntoResponse,
     routing::get,
-    Extension, Router, TypedHeader,
+    Extension, Json, Router, TypedHeader,
 };
 use chrono::Utc;
 use collections::{HashMap, HashSet};
@@ -41,10 +41,15 @@ use sha2::Digest;
 use supermaven_api::{CreateExternalUserRequest, SupermavenAdminApi};
 
 use futures::{
-    channel::oneshot, future::BoxFuture, stream::FuturesUnordered, FutureExt, SinkExt, StreamExt,
-    TryStreamExt,
+    channel::oneshot,
+    future::{self, BoxFuture},
+    stream::FuturesUnordered,
+    FutureExt, SinkExt, StreamExt, TryStreamExt,
+};
+use prometheus::{
+    register_histogram_vec, register_int_counter, register_int_gauge, register_int_gauge_vec,
+    HistogramVec, IntCounter, IntGauge, IntGaugeVec,
 };
-use prometheus::{register_int_gauge, IntGauge};
 use rpc::{
     proto::{
         self, Ack, AnyTypedEnvelope, EntityMessage, EnvelopedMessage, LiveKitConnectionInfo,
@@ -81,6 +86,15 @@ pub const RECONNECT_TIMEOUT: Duration = Duration::from_secs(30);
 // kubernetes gives terminated pods 10s to shutdown gracefully. After they're gone, we can clean up old resources.
 pub const CLEANUP_TIMEOUT: Duration = Duration::from_secs(15);
 
+// How long to wait for clients to reconnect elsewhere before tearing down, within kubernetes' 10s grace period.
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(8);
+
+// How long to collect changes to a user's status before sending them to their contacts.
+pub const CONTACT_UPDATE_DEBOUNCE: Duration = Duration::from_millis(100);
+
+// The protocol version in which deleted channel messages started being sent as tombstones.
+const CHANNEL_MESSAGE_TOMBSTONES_PROTOCOL_VERSION: u32 = 69;
+
 const MESSAGE_COUNT_PER_PAGE: usize = 100;
 const MAX_MESSAGE_LEN: usize = 1024;
 const NOTIFICATION_COUNT_PER_PAGE: usize = 50;
@@ -137,7 +151,9 @@ struct Session {
     /// The GeoIP country code for the user.
     #[allow(unused)]
     geoip_country_code: Option<String>,
-    _executor: Executor,
+    /// The protocol version negotiated with the client.
+    protocol_version: u32,
+    executor: Executor,
 }
 
 impl Session {
@@ -239,6 +255,8 @@ pub struct Server {
     app_state: Arc<AppState>,
     handlers: HashMap<TypeId, MessageHandler>,
     teardown: watch::Sender<bool>,
+    shutting_down: AtomicBool,
+    active_connections: watch::Sender<usize>,
 }
 
 pub(crate) struct ConnectionPoolGuard<'a> {
@@ -266,11 +284,17 @@ impl Server {
     pub fn new(id: ServerId, app_state: Arc<AppState>) -> Arc<Self> {
         let mut server = Self {
             id: parking_lot::Mutex::new(id),
-            peer: Peer::new(id.0 as u32),
+            peer: Peer::with_keepalive(
+                id.0 as u32,
+                app_state.config.rpc_keepalive_interval(),
+                app_state.config.rpc_receive_timeout(),
+            ),
             app_state: app_state.clone(),
             connection_pool: Default::default(),
             handlers: Default::default(),
             teardown: watch::channel(false).0,
+            shutting_down: AtomicBool::new(false),
+            active_connections: watch::channel(0).0,
         };
 
         server
@@ -288,6 +312,7 @@ impl Server {
             .add_message_handler(unshare_project)
             .add_request_handler(join_project)
             .add_message_handler(leave_project)
+            .add_request_handler(remove_project_guest)
             .add_request_handler(update_project)
             .add_request_handler(update_worktree)
             .add_message_handler(start_language_server)
@@ -346,6 +371,7 @@ impl Server {
             .add_request_handler(request_contact)
             .add_request_handler(remove_contact)
             .add_request_handler(respond_to_contact_request)
+            .add_message_handler(update_presence)
             .add_message_handler(subscribe_to_channels)
             .add_request_handler(create_channel)
             .add_request_handler(delete_channel)
@@ -368,6 +394,7 @@ impl Server {
             .add_request_handler(update_channel_message)
             .add_request_handler(get_channel_messages)
             .add_request_handler(get_channel_messages_by_id)
+            .add_message_handler(channel_typing)
             .add_request_handler(get_notifications)
             .add_request_handler(mark_notification_as_read)
             .add_request_handler(move_channel)
@@ -563,9 +590,47 @@ impl Server {
         let _ = self.teardown.send(true);
     }
 
+    /// Stops accepting new connections and asks connected clients to reconnect,
+    /// so that they can move to another server before this one goes away.
+    pub fn begin_shutdown(&self) {
+        self.shutting_down.store(true, SeqCst);
+
+        let connection_ids = self
+            .connection_pool
+            .lock()
+            .connection_ids()
+            .collect::<Vec<_>>();
+        tracing::info!(
+            connection_count = connection_ids.len(),
+            "notifying connections of shutdown"
+        );
+        for connection_id in connection_ids {
+            self.peer
+                .send(connection_id, proto::ServerShutdown {})
+                .trace_err();
+        }
+    }
+
+    /// Waits for all connections to close, returning `false` if some are still open
+    /// once the timeout elapses.
+    pub async fn wait_for_connections_to_close(
+        &self,
+        timeout: Duration,
+        executor: Executor,
+    ) -> bool {
+        let mut active_connections = self.active_connections.subscribe();
+        let closed = active_connections.wait_for(|count| *count == 0).fuse();
+        futures::pin_mut!(closed);
+        futures::select_biased! {
+            _ = closed => true,
+            _ = executor.sleep(timeout).fuse() => false,
+        }
+    }
+
     #[cfg(test)]
     pub fn reset(&self, id: ServerId) {
         self.teardown();
+        self.shutting_down.store(false, SeqCst);
         *self.id.lock() = id;
         self.peer.reset(id.0 as u32);
         let _ = self.teardown.send(false);
@@ -596,6 +661,13 @@ impl Server {
                     let processing_duration_ms = start_time.elapsed().as_micros() as f64 / 1000.0;
                     let queue_duration_ms = total_duration_ms - processing_duration_ms;
                     let payload_type = M::NAME;
+                    let span = tracing::Span::current();
+                    span.record("total_duration_ms", total_duration_ms);
+                    span.record("processing_duration_ms", processing_duration_ms);
+                    span.record("queue_duration_ms", queue_duration_ms);
+                    handler_duration_metric()
+                        .with_label_values(&[payload_type])
+                        .observe(start_time.elapsed().as_secs_f64());
 
                     match result {
                         Err(error) => {
@@ -681,6 +753,7 @@ impl Server {
         address: String,
         principal: Principal,
         zed_version: ZedVersion,
+        protocol_version: u32,
         geoip_country_code: Option<String>,
         send_connection_id: Option<oneshot::Sender<ConnectionId>>,
         executor: Executor,
@@ -704,6 +777,15 @@ impl Server {
                 tracing::error!("server is tearing down");
                 return
             }
+            if this.shutting_down.load(SeqCst) {
+                tracing::info!("server is shutting down, rejecting connection");
+                return
+            }
+            this.active_connections.send_modify(|count| *count += 1);
+            let active_connection = util::defer({
+                let this = this.clone();
+                move || this.active_connections.send_modify(|count| *count -= 1)
+            });
             let (connection_id, handle_io, mut incoming_rx) = this
                 .peer
                 .add_connection(connection, {
@@ -737,11 +819,12 @@ impl Server {
                 app_state: this.app_state.clone(),
                 http_client,
                 geoip_country_code,
-                _executor: executor.clone(),
+                protocol_version,
+                executor: executor.clone(),
                 supermaven_client,
             };
 
-            if let Err(error) = this.send_initial_client_update(connection_id, &principal, zed_version, send_connection_id, &session).await {
+            if let Err(error) = this.send_initial_client_update(connection_id, &principal, zed_version, protocol_version, send_connection_id, &session).await {
                 tracing::error!(?error, "failed to send initial client update");
                 return;
             }
@@ -785,6 +868,9 @@ impl Server {
                                 user_id=field::Empty,
                                 login=field::Empty,
                                 impersonator=field::Empty,
+                                total_duration_ms=field::Empty,
+                                processing_duration_ms=field::Empty,
+                                queue_duration_ms=field::Empty,
                             );
                             principal.update_span(&span);
                             let span_enter = span.enter();
@@ -814,6 +900,7 @@ impl Server {
             }
 
             drop(foreground_message_handlers);
+            drop(active_connection);
             tracing::info!("signing out");
             if let Err(error) = connection_lost(session, teardown, executor).await {
                 tracing::error!(?error, "error signing out");
@@ -827,6 +914,7 @@ impl Server {
         connection_id: ConnectionId,
         principal: &Principal,
         zed_version: ZedVersion,
+        protocol_version: u32,
         mut send_connection_id: Option<oneshot::Sender<ConnectionId>>,
         session: &Session,
     ) -> Result<()> {
@@ -857,7 +945,13 @@ impl Server {
 
                 {
                     let mut pool = self.connection_pool.lock();
-                    pool.add_connection(connection_id, user.id, user.admin, zed_version);
+                    pool.add_connection(
+                        connection_id,
+                        user.id,
+                        user.admin,
+                        zed_version,
+                        protocol_version,
+                    );
                     self.peer.send(
                         connection_id,
                         build_initial_contacts_update(contacts, &pool),
@@ -1056,6 +1150,37 @@ pub fn routes(server: Arc<Server>) -> Router<(), Body> {
         .layer(Extension(server))
 }
 
+/// The body of the response sent to clients whose protocol version isn't supported.
+#[derive(Debug, Serialize)]
+pub struct UnsupportedProtocolVersion {
+    pub message: String,
+    pub client_version: u32,
+    pub required_version: u32,
+    pub min_supported_version: u32,
+}
+
+impl IntoResponse for UnsupportedProtocolVersion {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::UPGRADE_REQUIRED, Json(self)).into_response()
+    }
+}
+
+/// Returns the protocol version to use with a client that connected using the given
+/// version, or an error explaining which versions are supported. Clients newer than the
+/// server are expected to speak down to the server's version.
+pub fn negotiate_protocol_version(client_version: u32) -> Result<u32, UnsupportedProtocolVersion> {
+    if client_version >= rpc::MIN_SUPPORTED_PROTOCOL_VERSION {
+        return Ok(client_version.min(rpc::PROTOCOL_VERSION));
+    }
+
+    Err(UnsupportedProtocolVersion {
+        message: "client must be upgraded".to_string(),
+        client_version,
+        required_version: rpc::PROTOCOL_VERSION,
+        min_supported_version: rpc::MIN_SUPPORTED_PROTOCOL_VERSION,
+    })
+}
+
 pub async fn handle_websocket_request(
     TypedHeader(ProtocolVersion(protocol_version)): TypedHeader<ProtocolVersion>,
     app_version_header: Option<TypedHeader<AppVersionHeader>>,
@@ -1065,13 +1190,10 @@ pub async fn handle_websocket_request(
     country_code_header: Option<TypedHeader<CloudflareIpCountryHeader>>,
     ws: WebSocketUpgrade,
 ) -> axum::response::Response {
-    if protocol_version != rpc::PROTOCOL_VERSION {
-        return (
-            StatusCode::UPGRADE_REQUIRED,
-            "client must be upgraded".to_string(),
-        )
-            .into_response();
-    }
+    let protocol_version = match negotiate_protocol_version(protocol_version) {
+        Ok(protocol_version) => protocol_version,
+        Err(error) => return error.into_response(),
+    };
 
     let Some(version) = app_version_header.map(|header| ZedVersion(header.0 .0)) else {
         return (
@@ -1090,7 +1212,7 @@ pub async fn handle_websocket_request(
     }
 
     let socket_address = socket_address.to_string();
-    ws.on_upgrade(move |socket| {
+    let response = ws.on_upgrade(move |socket| {
         let socket = socket
             .map_ok(to_tungstenite_message)
             .err_into()
@@ -1103,27 +1225,78 @@ pub async fn handle_websocket_request(
                     socket_address,
                     principal,
                     version,
+                    protocol_version,
                     country_code_header.map(|header| header.to_string()),
                     None,
                     Executor::Production,
                 )
                 .await;
         }
+    });
+
+    // Let the client know which version was negotiated, so it can adapt to older servers.
+    (TypedHeader(ProtocolVersion(protocol_version)), response).into_response()
+}
+
+fn handler_duration_metric() -> &'static HistogramVec {
+    static HANDLER_DURATION_METRIC: OnceLock<HistogramVec> = OnceLock::new();
+    HANDLER_DURATION_METRIC.get_or_init(|| {
+        register_histogram_vec!(
+            "rpc_handler_duration_seconds",
+            "time spent handling each type of rpc message",
+            &["message"]
+        )
+        .unwrap()
+    })
+}
+
+fn channel_messages_sent_metric() -> &'static IntCounter {
+    static CHANNEL_MESSAGES_SENT_METRIC: OnceLock<IntCounter> = OnceLock::new();
+    CHANNEL_MESSAGES_SENT_METRIC.get_or_init(|| {
+        register_int_counter!("channel_messages_sent", "number of channel messages sent").unwrap()
     })
 }
 
 pub async fn handle_metrics(Extension(server): Extension<Arc<Server>>) -> Result<String> {
+    // Register these metrics even if nothing has been recorded in them yet.
+    handler_duration_metric();
+    channel_messages_sent_metric();
+
     static CONNECTIONS_METRIC: OnceLock<IntGauge> = OnceLock::new();
     let connections_metric = CONNECTIONS_METRIC
         .get_or_init(|| register_int_gauge!("connections", "number of connections").unwrap());
 
-    let connections = server
+    static CONNECTIONS_BY_PROTOCOL_VERSION_METRIC: OnceLock<IntGaugeVec> = OnceLock::new();
+    let connections_by_protocol_version_metric = CONNECTIONS_BY_PROTOCOL_VERSION_METRIC
+        .get_or_init(|| {
+            register_int_gauge_vec!(
+                "connections_by_protocol_version",
+                "number of connections using each protocol version",
+                &["protocol_version"]
+            )
+            .unwrap()
+        });
+
+    let mut connections = 0;
+    let mut connections_by_protocol_version = HashMap::<u32, i64>::default();
+    for connection in server
         .connection_pool
         .lock()
         .connections()
         .filter(|connection| !connection.admin)
-        .count();
-    connections_metric.set(connections as _);
+    {
+        connections += 1;
+        *connections_by_protocol_version
+            .entry(connection.protocol_version)
+            .or_default() += 1;
+    }
+    connections_metric.set(connections);
+    connections_by_protocol_version_metric.reset();
+    for (protocol_version, count) in connections_by_protocol_version {
+        connections_by_protocol_version_metric
+            .with_label_values(&[&protocol_version.to_string()])
+            .set(count);
+    }
 
     static SHARED_PROJECTS_METRIC: OnceLock<IntGauge> = OnceLock::new();
     let shared_projects_metric = SHARED_PROJECTS_METRIC.get_or_init(|| {
@@ -1185,6 +1358,13 @@ async fn connection_lost(
             }
 
             update_user_contacts(session.user_id(), &session).await?;
+            if !session
+                .connection_pool()
+                .await
+                .is_user_online(session.user_id())
+            {
+                broadcast_presence(session.user_id(), &session).await?;
+            }
         },
         _ = teardown.changed().fuse() => {}
     }
@@ -1933,6 +2113,54 @@ async fn leave_project(request: proto::LeaveProject, session: Session) -> Result
     Ok(())
 }
 
+/// Removes a guest from a project on behalf of its host.
+async fn remove_project_guest(
+    request: proto::RemoveProjectGuest,
+    response: Response<proto::RemoveProjectGuest>,
+    session: Session,
+) -> Result<()> {
+    let project_id = ProjectId::from_proto(request.project_id);
+    let guest_connection_id: ConnectionId = request
+        .peer_id
+        .ok_or_else(|| anyhow!("invalid peer id"))?
+        .into();
+
+    let db = session.db().await;
+    let (room, project) = &*db
+        .remove_project_guest(project_id, session.connection_id, guest_connection_id)
+        .await?;
+    tracing::info!(%project_id, %guest_connection_id, "remove project guest");
+
+    session
+        .peer
+        .send(
+            guest_connection_id,
+            proto::UnshareProject {
+                project_id: project_id.to_proto(),
+            },
+        )
+        .trace_err();
+    broadcast(
+        None,
+        project.connection_ids.iter().copied(),
+        |connection_id| {
+            session.peer.send(
+                connection_id,
+                proto::RemoveProjectCollaborator {
+                    project_id: project_id.to_proto(),
+                    peer_id: Some(guest_connection_id.into()),
+                },
+            )
+        },
+    );
+    if let Some(room) = room {
+        room_updated(room, &session.peer);
+    }
+
+    response.send(proto::Ack {})?;
+    Ok(())
+}
+
 /// Updates other participants with changes to the project
 async fn update_project(
     request: proto::UpdateProject,
@@ -2099,7 +2327,12 @@ where
         .await?;
     let payload = session
         .peer
-        .forward_request(session.connection_id, host_connection_id, request)
+        .forward_request_with_timeout(
+            session.connection_id,
+            host_connection_id,
+            request,
+            forwarded_request_timeout::<T>(&session.executor),
+        )
         .await?;
     response.send(payload)?;
     Ok(())
@@ -2124,6 +2357,15 @@ async fn forward_find_search_candidates_request(
     Ok(())
 }
 
+/// Completes once a request forwarded to a project's host has taken longer than its
+/// type's [`RequestMessage::forward_timeout`]. Never completes for requests without one.
+fn forwarded_request_timeout<T: RequestMessage>(executor: &Executor) -> BoxFuture<'static, ()> {
+    match T::forward_timeout() {
+        Some(timeout) => executor.sleep(timeout).boxed(),
+        None => future::pending().boxed(),
+    }
+}
+
 /// forward a project request to the host. These requests are disallowed
 /// for guests.
 async fn forward_mutating_project_request<T>(
@@ -2143,7 +2385,12 @@ where
         .await?;
     let payload = session
         .peer
-        .forward_request(session.connection_id, host_connection_id, request)
+        .forward_request_with_timeout(
+            session.connection_id,
+            host_connection_id,
+            request,
+            forwarded_request_timeout::<T>(&session.executor),
+        )
         .await?;
     response.send(payload)?;
     Ok(())
@@ -2601,6 +2848,39 @@ async fn remove_contact(
     Ok(())
 }
 
+/// Updates whether the current user is online or away, notifying their contacts.
+async fn update_presence(message: proto::UpdatePresence, session: Session) -> Result<()> {
+    let changed = session
+        .connection_pool()
+        .await
+        .set_user_presence(session.user_id(), message.status());
+    if changed {
+        broadcast_presence(session.user_id(), &session).await?;
+    }
+    Ok(())
+}
+
+/// Sends the given user's presence to all of their online contacts.
+async fn broadcast_presence(user_id: UserId, session: &Session) -> Result<()> {
+    let contacts = session.db().await.get_contacts(user_id).await?;
+
+    let pool = session.connection_pool().await;
+    let message = proto::PresenceChanged {
+        user_id: user_id.to_proto(),
+        status: pool.user_presence(user_id).into(),
+    };
+    let contact_user_ids = contacts.into_iter().filter_map(|contact| match contact {
+        db::Contact::Accepted { user_id, .. } => Some(user_id),
+        _ => None,
+    });
+    broadcast(
+        None,
+        contact_user_ids.flat_map(|contact_user_id| pool.user_connection_ids(contact_user_id)),
+        |connection_id| session.peer.send(connection_id, message.clone()),
+    );
+    Ok(())
+}
+
 fn should_auto_subscribe_to_channels(version: ZedVersion) -> bool {
     version.0.minor() < 139
 }
@@ -3340,14 +3620,7 @@ async fn send_channel_message(
     response: Response<proto::SendChannelMessage>,
     session: Session,
 ) -> Result<()> {
-    // Validate the message body.
-    let body = request.body.trim().to_string();
-    if body.len() > MAX_MESSAGE_LEN {
-        return Err(anyhow!("message is too long"))?;
-    }
-    if body.is_empty() {
-        return Err(anyhow!("message can't be blank"))?;
-    }
+    let body = validate_channel_message_body(&request.body)?;
 
     // TODO: adjust mentions if body is trimmed
 
@@ -3356,11 +3629,28 @@ async fn send_channel_message(
         .nonce
         .ok_or_else(|| anyhow!("nonce can't be blank"))?;
 
+    // Retrying a message that was already stored doesn't create a new one, so it isn't
+    // counted against the rate limit. Otherwise the client could be refused the message
+    // it's waiting for.
     let channel_id = ChannelId::from_proto(request.channel_id);
+    let is_stored = session
+        .db()
+        .await
+        .has_channel_message_with_nonce(channel_id, session.user_id(), nonce.clone().into())
+        .await?;
+    if !is_stored {
+        session
+            .app_state
+            .rate_limiter
+            .check(&SendChannelMessageRateLimit, session.user_id())
+            .await?;
+    }
+
     let CreatedChannelMessage {
         message_id,
         participant_connection_ids,
         notifications,
+        existing_message,
     } = session
         .db()
         .await
@@ -3375,7 +3665,11 @@ async fn send_channel_message(
         )
         .await?;
 
-    let message = proto::ChannelMessage {
+    // A retried message is answered with the one that was stored the first time. It's
+    // broadcast again, since we can't tell whether the first broadcast happened (e.g. if
+    // the server went down right after storing it), and clients ignore messages they have.
+    let is_retry = existing_message.is_some();
+    let message = existing_message.unwrap_or_else(|| proto::ChannelMessage {
         sender_id: session.user_id().to_proto(),
         id: message_id.to_proto(),
         body,
@@ -3384,7 +3678,12 @@ async fn send_channel_message(
         nonce: Some(nonce),
         reply_to_message_id: request.reply_to_message_id,
         edited_at: None,
-    };
+        deleted_at: None,
+    });
+
+    if !is_retry {
+        channel_messages_sent_metric().inc();
+    }
     broadcast(
         Some(session.connection_id),
         participant_connection_ids.clone(),
@@ -3429,6 +3728,18 @@ async fn send_channel_message(
     Ok(())
 }
 
+/// Trims the body of a chat message, rejecting it if it is blank or too long.
+fn validate_channel_message_body(body: &str) -> Result<String> {
+    let body = body.trim();
+    if body.len() > MAX_MESSAGE_LEN {
+        return Err(anyhow!("message is too long"))?;
+    }
+    if body.is_empty() {
+        return Err(anyhow!("message can't be blank"))?;
+    }
+    Ok(body.to_string())
+}
+
 /// Delete a channel message
 async fn remove_channel_message(
     request: proto::RemoveChannelMessage,
@@ -3470,6 +3781,7 @@ async fn update_channel_message(
     response: Response<proto::UpdateChannelMessage>,
     session: Session,
 ) -> Result<()> {
+    let body = validate_channel_message_body(&request.body)?;
     let channel_id = ChannelId::from_proto(request.channel_id);
     let message_id = MessageId::from_proto(request.message_id);
     let updated_at = OffsetDateTime::now_utc();
@@ -3488,7 +3800,7 @@ async fn update_channel_message(
             channel_id,
             message_id,
             session.user_id(),
-            request.body.as_str(),
+            &body,
             &request.mentions,
             updated_at,
         )
@@ -3502,12 +3814,13 @@ async fn update_channel_message(
     let message = proto::ChannelMessage {
         sender_id: session.user_id().to_proto(),
         id: message_id.to_proto(),
-        body: request.body.clone(),
+        body,
         mentions: request.mentions.clone(),
         timestamp: timestamp.assume_utc().unix_timestamp() as u64,
         nonce: Some(nonce),
         reply_to_message_id: reply_to_message_id.map(|id| id.to_proto()),
         edited_at: Some(updated_at.unix_timestamp() as u64),
+        deleted_at: None,
     };
 
     response.send(proto::Ack {})?;
@@ -3552,6 +3865,42 @@ async fn update_channel_message(
     Ok(())
 }
 
+/// Let the other members of a channel know that the user is typing in its chat
+async fn channel_typing(request: proto::ChannelTyping, session: Session) -> Result<()> {
+    let channel_id = ChannelId::from_proto(request.channel_id);
+    let mut pool = session.connection_pool().await;
+    let mut is_member = false;
+    let mut connection_ids = Vec::new();
+    for (connection_id, _) in pool.channel_connection_ids(channel_id) {
+        if connection_id == session.connection_id {
+            is_member = true;
+        } else {
+            connection_ids.push(connection_id);
+        }
+    }
+    if !is_member {
+        Err(anyhow!("not a channel member"))?;
+    }
+    if !pool.record_typing(session.connection_id, channel_id, Instant::now()) {
+        return Ok(());
+    }
+
+    let own_connection_ids = pool
+        .user_connection_ids(session.user_id())
+        .collect::<HashSet<_>>();
+    connection_ids.retain(|connection_id| !own_connection_ids.contains(connection_id));
+    broadcast(None, connection_ids, |connection_id| {
+        session.peer.send(
+            connection_id,
+            proto::ChannelTypingUpdate {
+                channel_id: channel_id.to_proto(),
+                user_id: session.user_id().to_proto(),
+            },
+        )
+    });
+    Ok(())
+}
+
 /// Mark a channel message as read
 async fn acknowledge_channel_message(
     request: proto::AckChannelMessage,
@@ -3635,6 +3984,22 @@ async fn count_language_model_tokens(
     Ok(())
 }
 
+pub(crate) struct SendChannelMessageRateLimit;
+
+impl RateLimit for SendChannelMessageRateLimit {
+    fn capacity(&self) -> usize {
+        20
+    }
+
+    fn refill_duration(&self) -> chrono::Duration {
+        chrono::Duration::seconds(10)
+    }
+
+    fn db_name(&self) -> &'static str {
+        "send-channel-message"
+    }
+}
+
 struct ZedProCountLanguageModelTokensRateLimit;
 
 impl RateLimit for ZedProCountLanguageModelTokensRateLimit {
@@ -3854,7 +4219,7 @@ async fn join_channel_chat(
         .await?;
     response.send(proto::JoinChannelChatResponse {
         done: messages.len() < MESSAGE_COUNT_PER_PAGE,
-        messages,
+        messages: omit_unsupported_tombstones(session.protocol_version, messages),
     })?;
     Ok(())
 }
@@ -3877,6 +4242,27 @@ async fn get_channel_messages(
     session: Session,
 ) -> Result<()> {
     let channel_id = ChannelId::from_proto(request.channel_id);
+    if let Some(after_message_id) = request.after_message_id {
+        // Fetch one extra message to know whether there are any more after this page.
+        let mut messages = session
+            .db()
+            .await
+            .get_channel_messages_after(
+                channel_id,
+                session.user_id(),
+                MESSAGE_COUNT_PER_PAGE + 1,
+                MessageId::from_proto(after_message_id),
+            )
+            .await?;
+        let done = messages.len() <= MESSAGE_COUNT_PER_PAGE;
+        messages.truncate(MESSAGE_COUNT_PER_PAGE);
+        response.send(proto::GetChannelMessagesResponse {
+            done,
+            messages: omit_unsupported_tombstones(session.protocol_version, messages),
+        })?;
+        return Ok(());
+    }
+
     let messages = session
         .db()
         .await
@@ -3889,7 +4275,7 @@ async fn get_channel_messages(
         .await?;
     response.send(proto::GetChannelMessagesResponse {
         done: messages.len() < MESSAGE_COUNT_PER_PAGE,
-        messages,
+        messages: omit_unsupported_tombstones(session.protocol_version, messages),
     })?;
     Ok(())
 }
@@ -3912,11 +4298,23 @@ async fn get_channel_messages_by_id(
         .await?;
     response.send(proto::GetChannelMessagesResponse {
         done: messages.len() < MESSAGE_COUNT_PER_PAGE,
-        messages,
+        messages: omit_unsupported_tombstones(session.protocol_version, messages),
     })?;
     Ok(())
 }
 
+/// Removes deleted messages for clients that predate tombstones, since they would show
+/// them as blank messages.
+fn omit_unsupported_tombstones(
+    protocol_version: u32,
+    mut messages: Vec<proto::ChannelMessage>,
+) -> Vec<proto::ChannelMessage> {
+    if protocol_version < CHANNEL_MESSAGE_TOMBSTONES_PROTOCOL_VERSION {
+        messages.retain(|message| message.deleted_at.is_none());
+    }
+    messages
+}
+
 /// Retrieve the current users notifications
 async fn get_notifications(
     request: proto::GetNotifications,
@@ -4207,6 +4605,7 @@ fn contact_for_user(user_id: UserId, busy: bool, pool: &ConnectionPool) -> proto
         user_id: user_id.to_proto(),
         online: pool.is_user_online(user_id),
         busy,
+        away: pool.user_presence(user_id) == proto::PresenceStatus::Away,
     }
 }
 
@@ -4261,9 +4660,34 @@ fn channel_updated(
     );
 }
 
+/// Notifies the given user's contacts of their online and busy status. Each user has at most
+/// one task sending these updates, which waits for further changes before each send so that
+/// a burst of changes produces a single update with the latest status.
 async fn update_user_contacts(user_id: UserId, session: &Session) -> Result<()> {
-    let db = session.db().await;
+    if session
+        .connection_pool()
+        .await
+        .queue_contact_update(user_id)
+    {
+        let session = session.clone();
+        let debounce = session.app_state.config.contact_update_debounce();
+        session.executor.clone().spawn_detached(async move {
+            loop {
+                session.executor.sleep(debounce).await;
+                if !session.connection_pool().await.take_contact_update(user_id) {
+                    break;
+                }
+                send_user_contacts_update(user_id, &session)
+                    .await
+                    .trace_err();
+            }
+        });
+    }
+    Ok(())
+}
 
+async fn send_user_contacts_update(user_id: UserId, session: &Session) -> Result<()> {
+    let db = session.db().await;
     let contacts = db.get_contacts(user_id).await?;
     let busy = db.is_user_busy(user_id).await?;
 
@@ -4442,3 +4866,119 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_negotiate_protocol_version(_cx: &mut gpui::TestAppContext) {
+        assert_eq!(
+            negotiate_protocol_version(rpc::PROTOCOL_VERSION).unwrap(),
+            rpc::PROTOCOL_VERSION
+        );
+        assert_eq!(
+            negotiate_protocol_version(rpc::MIN_SUPPORTED_PROTOCOL_VERSION).unwrap(),
+            rpc::MIN_SUPPORTED_PROTOCOL_VERSION
+        );
+
+        let old_version = rpc::MIN_SUPPORTED_PROTOCOL_VERSION - 1;
+        let response = negotiate_protocol_version(old_version)
+            .unwrap_err()
+            .into_response();
+        assert_eq!(response.status(), StatusCode::UPGRADE_REQUIRED);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "message": "client must be upgraded",
+                "client_version": old_version,
+                "required_version": rpc::PROTOCOL_VERSION,
+                "min_supported_version": rpc::MIN_SUPPORTED_PROTOCOL_VERSION,
+            })
+        );
+
+        // Only a small range of versions is accepted: a client one version behind can still
+        // connect during a deploy, but much older ones can't.
+        assert_eq!(
+            negotiate_protocol_version(rpc::PROTOCOL_VERSION - 1).unwrap(),
+            rpc::PROTOCOL_VERSION - 1
+        );
+        assert!(negotiate_protocol_version(rpc::PROTOCOL_VERSION - 5).is_err());
+
+        // Newer clients are told to use the server's version.
+        assert_eq!(
+            negotiate_protocol_version(rpc::PROTOCOL_VERSION + 1).unwrap(),
+            rpc::PROTOCOL_VERSION
+        );
+    }
+
+    #[gpui::test]
+    async fn test_forwarded_request_timeout(cx: &mut gpui::TestAppContext) {
+        let executor = Executor::Deterministic(cx.executor());
+        let mut hover_timeout = forwarded_request_timeout::<proto::GetHover>(&executor);
+        let mut format_timeout = forwarded_request_timeout::<proto::FormatBuffers>(&executor);
+
+        assert!((&mut hover_timeout).now_or_never().is_none());
+        assert!((&mut format_timeout).now_or_never().is_none());
+
+        cx.executor().advance_clock(proto::DEFAULT_FORWARD_TIMEOUT);
+        assert!((&mut hover_timeout).now_or_never().is_some());
+        assert!((&mut format_timeout).now_or_never().is_none());
+    }
+
+    #[test]
+    fn test_omit_unsupported_tombstones() {
+        let message = |id: u64, deleted_at: Option<u64>| proto::ChannelMessage {
+            id,
+            deleted_at,
+            ..Default::default()
+        };
+        let messages = vec![message(1, None), message(2, Some(1000)), message(3, None)];
+        let ids = |messages: Vec<proto::ChannelMessage>| {
+            messages
+                .iter()
+                .map(|message| message.id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            ids(omit_unsupported_tombstones(
+                CHANNEL_MESSAGE_TOMBSTONES_PROTOCOL_VERSION,
+                messages.clone()
+            )),
+            [1, 2, 3]
+        );
+        assert_eq!(
+            ids(omit_unsupported_tombstones(
+                CHANNEL_MESSAGE_TOMBSTONES_PROTOCOL_VERSION - 1,
+                messages
+            )),
+            [1, 3]
+        );
+    }
+
+    #[test]
+    fn test_contact_updates_are_coalesced() {
+        let mut pool = ConnectionPool::default();
+        let user_1 = UserId(1);
+        let user_2 = UserId(2);
+
+        // Only the first change starts a sender; later ones are picked up by it.
+        assert!(pool.queue_contact_update(user_1));
+        assert!(!pool.queue_contact_update(user_1));
+        assert!(pool.queue_contact_update(user_2));
+
+        assert!(pool.take_contact_update(user_1));
+
+        // Changes made while an update is being sent don't start another sender.
+        assert!(!pool.queue_contact_update(user_1));
+        assert!(pool.take_contact_update(user_1));
+
+        // The sender stops once there's nothing left to send.
+        assert!(!pool.take_contact_update(user_1));
+        assert!(pool.queue_contact_update(user_1));
+        assert!(pool.take_contact_update(user_2));
+    }
+}
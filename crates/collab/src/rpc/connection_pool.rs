@@ -1,22 +1,36 @@
 use crate::db::{ChannelId, ChannelRole, UserId};
 use anyhow::{anyhow, Result};
 use collections::{BTreeMap, HashMap, HashSet};
-use rpc::ConnectionId;
+use rpc::{proto, ConnectionId};
 use semantic_version::SemanticVersion;
 use serde::Serialize;
-use std::fmt;
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
 use tracing::instrument;
 
+/// How long to wait before relaying another typing event from the same connection
+/// in the same channel.
+pub const TYPING_COALESCE_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Default, Serialize)]
 pub struct ConnectionPool {
     connections: BTreeMap<ConnectionId, Connection>,
     connected_users: BTreeMap<UserId, ConnectedPrincipal>,
     channels: ChannelPool,
+    /// Users with a task sending their status to their contacts, and whether their status
+    /// changed since that task last sent it.
+    #[serde(skip)]
+    contact_update_senders: HashMap<UserId, bool>,
 }
 
 #[derive(Default, Serialize)]
 struct ConnectedPrincipal {
     connection_ids: HashSet<ConnectionId>,
+    /// Whether the user reported themselves as away. Cleared once all of their
+    /// connections are gone.
+    away: bool,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, PartialOrd, PartialEq, Eq, Ord)]
@@ -39,6 +53,10 @@ pub struct Connection {
     pub user_id: UserId,
     pub admin: bool,
     pub zed_version: ZedVersion,
+    /// The RPC protocol version that was negotiated when the client connected.
+    pub protocol_version: u32,
+    #[serde(skip)]
+    last_typed_at: HashMap<ChannelId, Instant>,
 }
 
 impl ConnectionPool {
@@ -46,6 +64,7 @@ impl ConnectionPool {
         self.connections.clear();
         self.connected_users.clear();
         self.channels.clear();
+        self.contact_update_senders.clear();
     }
 
     pub fn connection(&mut self, connection_id: ConnectionId) -> Option<&Connection> {
@@ -59,6 +78,7 @@ impl ConnectionPool {
         user_id: UserId,
         admin: bool,
         zed_version: ZedVersion,
+        protocol_version: u32,
     ) {
         self.connections.insert(
             connection_id,
@@ -66,6 +86,8 @@ impl ConnectionPool {
                 user_id,
                 admin,
                 zed_version,
+                protocol_version,
+                last_typed_at: HashMap::default(),
             },
         );
         let connected_user = self.connected_users.entry(user_id).or_default();
@@ -91,10 +113,35 @@ impl ConnectionPool {
         Ok(())
     }
 
+    /// Records that the given connection is typing in a channel. Returns `false` if a typing
+    /// event from this connection was already recorded in the last [`TYPING_COALESCE_INTERVAL`],
+    /// in which case there's no need to relay this one.
+    pub fn record_typing(
+        &mut self,
+        connection_id: ConnectionId,
+        channel_id: ChannelId,
+        now: Instant,
+    ) -> bool {
+        let Some(connection) = self.connections.get_mut(&connection_id) else {
+            return false;
+        };
+        if let Some(last_typed_at) = connection.last_typed_at.get(&channel_id) {
+            if now.saturating_duration_since(*last_typed_at) < TYPING_COALESCE_INTERVAL {
+                return false;
+            }
+        }
+        connection.last_typed_at.insert(channel_id, now);
+        true
+    }
+
     pub fn connections(&self) -> impl Iterator<Item = &Connection> {
         self.connections.values()
     }
 
+    pub fn connection_ids(&self) -> impl Iterator<Item = ConnectionId> + '_ {
+        self.connections.keys().copied()
+    }
+
     pub fn user_connections(&self, user_id: UserId) -> impl Iterator<Item = &Connection> + '_ {
         self.connected_users
             .get(&user_id)
@@ -147,6 +194,55 @@ impl ConnectionPool {
         self.channels.unsubscribe(user_id, channel_id);
     }
 
+    /// Marks the given user's contacts as needing an update. Returns `true` if no task is
+    /// sending the user's updates yet, in which case the caller should start one.
+    pub fn queue_contact_update(&mut self, user_id: UserId) -> bool {
+        self.contact_update_senders.insert(user_id, true).is_none()
+    }
+
+    /// Called by the task sending the given user's updates before it reads their status.
+    /// Returns `false` if nothing changed since the last send, in which case the task is
+    /// done and the next change starts a new one.
+    pub fn take_contact_update(&mut self, user_id: UserId) -> bool {
+        match self.contact_update_senders.get_mut(&user_id) {
+            Some(changed) if *changed => {
+                *changed = false;
+                true
+            }
+            _ => {
+                self.contact_update_senders.remove(&user_id);
+                false
+            }
+        }
+    }
+
+    /// Records the presence a user reported. Returns `false` if the user isn't
+    /// connected or their presence didn't change.
+    pub fn set_user_presence(&mut self, user_id: UserId, status: proto::PresenceStatus) -> bool {
+        let Some(connected_user) = self.connected_users.get_mut(&user_id) else {
+            return false;
+        };
+        let away = match status {
+            proto::PresenceStatus::Online => false,
+            proto::PresenceStatus::Away => true,
+            // Users go offline by disconnecting.
+            proto::PresenceStatus::Offline => return false,
+        };
+        if connected_user.away == away {
+            return false;
+        }
+        connected_user.away = away;
+        true
+    }
+
+    pub fn user_presence(&self, user_id: UserId) -> proto::PresenceStatus {
+        match self.connected_users.get(&user_id) {
+            Some(connected_user) if connected_user.away => proto::PresenceStatus::Away,
+            Some(_) => proto::PresenceStatus::Online,
+            None => proto::PresenceStatus::Offline,
+        }
+    }
+
     pub fn is_user_online(&self, user_id: UserId) -> bool {
         !self
             .connected_users
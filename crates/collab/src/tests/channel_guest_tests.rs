@@ -289,3 +289,77 @@ async fn test_channel_requires_zed_cla(cx_a: &mut TestAppContext, cx_b: &mut Tes
     assert!(room_b.read_with(cx_b, |room, _| room.can_share_projects()));
     assert!(room_b.read_with(cx_b, |room, _| room.can_use_microphone()));
 }
+
+#[gpui::test]
+async fn test_channel_guest_buffer_edits_are_rejected(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    let channel_id = server
+        .make_public_channel("the-channel", &client_a, cx_a)
+        .await;
+
+    let project_a = client_a.build_test_project(cx_a).await;
+    active_call_a
+        .update(cx_a, |call, cx| call.join_channel(channel_id, cx))
+        .await
+        .unwrap();
+    active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    cx_a.executor().run_until_parked();
+
+    // Client B joins the channel as a guest, and so can only read the project.
+    cx_b.update(|cx| workspace::join_channel(channel_id, client_b.app_state.clone(), None, cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    let active_call_b = cx_b.read(ActiveCall::global);
+    let project_b =
+        active_call_b.read_with(cx_b, |call, _| call.location().unwrap().upgrade().unwrap());
+    assert!(project_b.read_with(cx_b, |project, cx| project.is_read_only(cx)));
+
+    let worktree_id = project_a.read_with(cx_a, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let buffer_a = project_a
+        .update(cx_a, |project, cx| {
+            project.open_buffer((worktree_id, "1.txt"), cx)
+        })
+        .await
+        .unwrap();
+    let buffer_b = project_b
+        .update(cx_b, |project, cx| {
+            project.open_buffer((worktree_id, "1.txt"), cx)
+        })
+        .await
+        .unwrap();
+
+    // Edits that bypass the editor's read-only check are still rejected by the server.
+    buffer_b.update(cx_b, |buffer, cx| buffer.edit([(0..0, "guest ")], None, cx));
+    executor.run_until_parked();
+    assert_eq!(
+        buffer_a.read_with(cx_a, |buffer, _| buffer.text()),
+        "one\none\none"
+    );
+
+    // The host can still edit, and the guest sees those edits.
+    buffer_a.update(cx_a, |buffer, cx| {
+        let len = buffer.len();
+        buffer.edit([(len..len, "\nhost")], None, cx)
+    });
+    executor.run_until_parked();
+    assert_eq!(
+        buffer_a.read_with(cx_a, |buffer, _| buffer.text()),
+        "one\none\none\nhost"
+    );
+    assert!(buffer_b.read_with(cx_b, |buffer, _| buffer.text().ends_with("\nhost")));
+}
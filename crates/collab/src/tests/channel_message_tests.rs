@@ -1,8 +1,18 @@
-use crate::{rpc::RECONNECT_TIMEOUT, tests::TestServer};
-use channel::{ChannelChat, ChannelMessageId, MessageParams};
+use crate::{
+    db,
+    rpc::{SendChannelMessageRateLimit, RECONNECT_TIMEOUT},
+    tests::TestServer,
+    RateLimit,
+};
+use channel::{
+    ChannelChat, ChannelMessageId, MessageParams, RATE_LIMITED_RESEND_DELAY, TYPING_TIMEOUT,
+};
 use collab_ui::chat_panel::ChatPanel;
 use gpui::{BackgroundExecutor, Model, TestAppContext};
-use rpc::Notification;
+use rpc::{
+    proto::{self, ErrorCode},
+    ErrorExt, Notification,
+};
 use workspace::dock::Panel;
 
 #[gpui::test]
@@ -272,19 +282,42 @@ async fn test_remove_channel_message(
         .await
         .unwrap();
 
-    // Client B sees that the message is gone.
+    // Client B sees that the message was deleted. Its body is cleared, but it keeps
+    // its place in the conversation.
     executor.run_until_parked();
-    let expected_messages = &["one", "three"];
+    let expected_messages = &["one", "", "three"];
     assert_messages(&channel_chat_a, expected_messages, cx_a);
     assert_messages(&channel_chat_b, expected_messages, cx_b);
+    assert_deleted_messages(&channel_chat_a, &[false, true, false], cx_a);
+    assert_deleted_messages(&channel_chat_b, &[false, true, false], cx_b);
 
-    // Client C joins the channel chat, and does not see the deleted message.
+    // Client C joins the channel chat, and sees the deleted message's tombstone.
     let channel_chat_c = client_c
         .channel_store()
         .update(cx_c, |store, cx| store.open_channel_chat(channel_id, cx))
         .await
         .unwrap();
     assert_messages(&channel_chat_c, expected_messages, cx_c);
+    assert_deleted_messages(&channel_chat_c, &[false, true, false], cx_c);
+
+    // Deleted messages can't be edited.
+    let result = channel_chat_a
+        .update(cx_a, |c, cx| {
+            c.update_message(
+                msg_id_2,
+                MessageParams {
+                    text: "two again".into(),
+                    reply_to_message_id: None,
+                    mentions: Vec::new(),
+                },
+                cx,
+            )
+            .unwrap()
+        })
+        .await;
+    assert!(result.is_err());
+    executor.run_until_parked();
+    assert_messages(&channel_chat_b, expected_messages, cx_b);
 
     // Ensure we remove the notifications when the message is removed
     client_b.notification_store().read_with(cx_b, |store, _| {
@@ -294,6 +327,19 @@ async fn test_remove_channel_message(
     });
 }
 
+#[track_caller]
+fn assert_deleted_messages(chat: &Model<ChannelChat>, deleted: &[bool], cx: &mut TestAppContext) {
+    assert_eq!(
+        chat.read_with(cx, |chat, _| {
+            chat.messages()
+                .iter()
+                .map(|m| m.deleted)
+                .collect::<Vec<_>>()
+        }),
+        deleted
+    );
+}
+
 #[track_caller]
 fn assert_messages(chat: &Model<ChannelChat>, messages: &[&str], cx: &mut TestAppContext) {
     assert_eq!(
@@ -723,3 +769,506 @@ async fn test_chat_editing(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext)
         assert_eq!(store.notification_count(), 1);
     });
 }
+
+#[gpui::test]
+async fn test_editing_another_users_message_is_rejected(
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    let msg_id = channel_chat_a
+        .update(cx_a, |c, cx| {
+            c.send_message(
+                MessageParams {
+                    text: "Initial message".into(),
+                    reply_to_message_id: None,
+                    mentions: Vec::new(),
+                },
+                cx,
+            )
+            .unwrap()
+        })
+        .await
+        .unwrap();
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    // Client B can't edit client A's message, and its optimistic edit is reverted.
+    let result = channel_chat_b
+        .update(cx_b, |c, cx| {
+            c.update_message(
+                msg_id,
+                MessageParams {
+                    text: "Hijacked".into(),
+                    reply_to_message_id: None,
+                    mentions: Vec::new(),
+                },
+                cx,
+            )
+            .unwrap()
+        })
+        .await;
+    assert!(result.is_err());
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    for (channel_chat, cx) in [(&channel_chat_a, &mut *cx_a), (&channel_chat_b, &mut *cx_b)] {
+        channel_chat.update(cx, |channel_chat, _| {
+            let message = channel_chat.find_loaded_message(msg_id).unwrap();
+            assert_eq!(message.body, "Initial message");
+            assert!(message.edited_at.is_none());
+        });
+    }
+
+    // Client A can edit its own message, and client B observes it as edited.
+    channel_chat_a
+        .update(cx_a, |c, cx| {
+            c.update_message(
+                msg_id,
+                MessageParams {
+                    text: "Edited message".into(),
+                    reply_to_message_id: None,
+                    mentions: Vec::new(),
+                },
+                cx,
+            )
+            .unwrap()
+        })
+        .await
+        .unwrap();
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    channel_chat_b.update(cx_b, |channel_chat, _| {
+        let message = channel_chat.find_loaded_message(msg_id).unwrap();
+        assert_eq!(message.body, "Edited message");
+        assert!(message.edited_at.is_some());
+    });
+}
+
+#[gpui::test]
+async fn test_chat_edits_are_validated(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    let msg_id = channel_chat_a
+        .update(cx_a, |c, cx| {
+            c.send_message(
+                MessageParams {
+                    text: "Initial message".into(),
+                    reply_to_message_id: None,
+                    mentions: Vec::new(),
+                },
+                cx,
+            )
+            .unwrap()
+        })
+        .await
+        .unwrap();
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    // Edits are held to the same rules as new messages.
+    for invalid_body in ["   ".to_string(), "a".repeat(1025)] {
+        let result = channel_chat_a
+            .update(cx_a, |c, cx| {
+                c.update_message(
+                    msg_id,
+                    MessageParams {
+                        text: invalid_body,
+                        reply_to_message_id: None,
+                        mentions: Vec::new(),
+                    },
+                    cx,
+                )
+                .unwrap()
+            })
+            .await;
+        assert!(result.is_err());
+        cx_a.run_until_parked();
+        cx_b.run_until_parked();
+
+        for (channel_chat, cx) in [(&channel_chat_a, &mut *cx_a), (&channel_chat_b, &mut *cx_b)] {
+            channel_chat.update(cx, |channel_chat, _| {
+                let message = channel_chat.find_loaded_message(msg_id).unwrap();
+                assert_eq!(message.body, "Initial message");
+                assert!(message.edited_at.is_none());
+            });
+        }
+    }
+}
+
+#[gpui::test]
+async fn test_channel_typing_indicators(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    client_b
+        .channel_store()
+        .read_with(cx_b, |store, _| store.send_typing(channel_id))
+        .unwrap();
+    cx_a.run_until_parked();
+
+    client_a.channel_store().read_with(cx_a, |store, _| {
+        assert_eq!(
+            store.typing_user_ids(channel_id).collect::<Vec<_>>(),
+            [client_b.user_id().unwrap()]
+        );
+    });
+    client_b.channel_store().read_with(cx_b, |store, _| {
+        assert_eq!(store.typing_user_ids(channel_id).count(), 0);
+    });
+
+    // The indicator clears once the user stops typing.
+    cx_a.executor().advance_clock(TYPING_TIMEOUT);
+    cx_a.run_until_parked();
+    client_a.channel_store().read_with(cx_a, |store, _| {
+        assert_eq!(store.typing_user_ids(channel_id).count(), 0);
+    });
+}
+
+#[gpui::test]
+async fn test_chat_rate_limiting(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    let send_message =
+        |channel_chat: &Model<ChannelChat>, text: String, cx: &mut TestAppContext| {
+            channel_chat.update(cx, |chat, cx| {
+                chat.send_message(text.as_str().into(), cx).unwrap()
+            })
+        };
+
+    let limit = SendChannelMessageRateLimit.capacity();
+    for i in 0..limit {
+        send_message(&channel_chat_a, format!("message {i}"), cx_a)
+            .await
+            .unwrap();
+    }
+    let error = send_message(&channel_chat_a, "one too many".into(), cx_a)
+        .await
+        .unwrap_err();
+    assert_eq!(error.error_code(), ErrorCode::RateLimitExceeded);
+
+    // The limit is per user.
+    send_message(&channel_chat_b, "hello".into(), cx_b)
+        .await
+        .unwrap();
+
+    // Tokens are refilled as time passes on the server.
+    server
+        .app_state
+        .rate_limiter
+        .advance_clock(SendChannelMessageRateLimit.refill_duration());
+    send_message(&channel_chat_a, "back again".into(), cx_a)
+        .await
+        .unwrap();
+}
+
+#[gpui::test]
+async fn test_rate_limited_pending_messages_are_resent(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    server.forbid_connections();
+    server.disconnect_client(client_a.peer_id().unwrap());
+
+    // While disconnected, client A queues more messages than it is allowed to send at once.
+    let messages = (0..SendChannelMessageRateLimit.capacity() + 10)
+        .map(|i| format!("message {i}"))
+        .collect::<Vec<_>>();
+    for message in &messages {
+        channel_chat_a
+            .update(cx_a, |c, cx| {
+                c.send_message(message.as_str().into(), cx).unwrap()
+            })
+            .await
+            .unwrap_err();
+    }
+
+    // When client A reconnects, the server accepts messages until the limit is reached.
+    server.allow_connections();
+    executor.advance_clock(RECONNECT_TIMEOUT);
+    channel_chat_a.read_with(cx_a, |chat, _| {
+        assert!(chat.pending_messages().count() > 0);
+    });
+
+    // The rest are kept, and resent once the rate limit has refilled.
+    server
+        .app_state
+        .rate_limiter
+        .advance_clock(SendChannelMessageRateLimit.refill_duration());
+    executor.advance_clock(RATE_LIMITED_RESEND_DELAY);
+
+    let messages = messages.iter().map(String::as_str).collect::<Vec<_>>();
+    channel_chat_a.read_with(cx_a, |chat, _| {
+        assert_eq!(chat.pending_messages().count(), 0);
+    });
+    assert_messages(&channel_chat_a, &messages, cx_a);
+    assert_messages(&channel_chat_b, &messages, cx_b);
+}
+
+#[gpui::test]
+async fn test_chat_unread_count(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    for text in ["one", "two", "three"] {
+        channel_chat_a
+            .update(cx_a, |chat, cx| chat.send_message(text.into(), cx).unwrap())
+            .await
+            .unwrap();
+    }
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    // Messages from other users are unread until they're acknowledged.
+    channel_chat_a.read_with(cx_a, |chat, cx| assert_eq!(chat.unread_count(cx), 0));
+    channel_chat_b.read_with(cx_b, |chat, cx| assert_eq!(chat.unread_count(cx), 3));
+
+    channel_chat_b.update(cx_b, |chat, cx| chat.acknowledge_last_message(cx));
+    cx_b.run_until_parked();
+    channel_chat_b.read_with(cx_b, |chat, cx| assert_eq!(chat.unread_count(cx), 0));
+
+    channel_chat_a
+        .update(cx_a, |chat, cx| {
+            chat.send_message("four".into(), cx).unwrap()
+        })
+        .await
+        .unwrap();
+    cx_b.run_until_parked();
+    channel_chat_b.read_with(cx_b, |chat, cx| assert_eq!(chat.unread_count(cx), 1));
+}
+
+#[gpui::test]
+async fn test_resent_channel_messages_are_deduplicated(
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    // Simulate a client retrying a message after a flaky connection.
+    let request = proto::SendChannelMessage {
+        channel_id: channel_id.0,
+        body: "hello".into(),
+        nonce: Some(42.into()),
+        mentions: Vec::new(),
+        reply_to_message_id: None,
+    };
+    let first = client_a.client().request(request.clone()).await.unwrap();
+    let second = client_a
+        .client()
+        .request(proto::SendChannelMessage {
+            body: "hello again".into(),
+            ..request
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        first.message, second.message,
+        "resending a nonce returns the stored message"
+    );
+
+    cx_a.executor().run_until_parked();
+    channel_chat_b.update(cx_b, |chat, _| {
+        assert_eq!(chat.message_count(), 1);
+        assert_eq!(chat.message(0).body, "hello");
+    });
+
+    let messages = server
+        .app_state
+        .db
+        .get_channel_messages(
+            db::ChannelId::from_proto(channel_id.0),
+            db::UserId::from_proto(client_a.user_id().unwrap()),
+            10,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 1);
+}
+
+#[gpui::test]
+async fn test_resent_channel_messages_are_not_rate_limited(cx_a: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+
+    let channel_id = server
+        .make_channel("the-channel", None, (&client_a, cx_a), &mut [])
+        .await;
+    client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    let send_message = |body: &str, nonce: u128| {
+        client_a.client().request(proto::SendChannelMessage {
+            channel_id: channel_id.0,
+            body: body.into(),
+            nonce: Some(nonce.into()),
+            mentions: Vec::new(),
+            reply_to_message_id: None,
+        })
+    };
+
+    let limit = SendChannelMessageRateLimit.capacity();
+    let first = send_message("message 0", 0).await.unwrap();
+    for i in 1..limit {
+        send_message(&format!("message {i}"), i as u128)
+            .await
+            .unwrap();
+    }
+    let error = send_message("one too many", limit as u128)
+        .await
+        .unwrap_err();
+    assert_eq!(error.error_code(), ErrorCode::RateLimitExceeded);
+
+    // Retrying a message that was already stored still returns it once the limit is reached.
+    let retried = send_message("message 0", 0).await.unwrap();
+    assert_eq!(retried.message, first.message);
+}
@@ -1458,3 +1458,46 @@ fn assert_channels_list_shape(
     });
     pretty_assertions::assert_eq!(actual, expected_channels);
 }
+
+#[gpui::test]
+async fn test_many_channels_are_loaded_in_order(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+) {
+    const CHANNEL_COUNT: usize = 150;
+
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let user_a = client_a.user_id().unwrap();
+
+    // Create the channels out of order, so that the client has to sort them.
+    let db = &server.app_state.db;
+    for ix in 0..CHANNEL_COUNT {
+        let ix = ix * 7 % CHANNEL_COUNT;
+        db.create_root_channel(&format!("channel-{ix:03}"), UserId::from_proto(user_a))
+            .await
+            .unwrap();
+    }
+
+    client_a
+        .channel_store()
+        .update(cx_a, |channel_store, _| channel_store.initialize());
+    executor.run_until_parked();
+
+    client_a
+        .channel_store()
+        .read_with(cx_a, |channel_store, _| {
+            assert_eq!(channel_store.channel_count(), CHANNEL_COUNT);
+            let names = channel_store
+                .ordered_channels()
+                .map(|(depth, channel)| {
+                    assert_eq!(depth, 0);
+                    channel.name.to_string()
+                })
+                .collect::<Vec<_>>();
+            let expected_names = (0..CHANNEL_COUNT)
+                .map(|ix| format!("channel-{ix:03}"))
+                .collect::<Vec<_>>();
+            assert_eq!(names, expected_names);
+        });
+}
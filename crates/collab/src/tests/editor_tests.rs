@@ -268,6 +268,93 @@ async fn test_newline_above_or_below_does_not_move_guest_cursor(
     "});
 }
 
+#[gpui::test(iterations = 10)]
+async fn test_undo_only_reverts_own_edits(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let mut server = TestServer::start(cx_a.executor()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let executor = cx_a.executor();
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree("/dir", json!({ "a.txt": "one two\n" }))
+        .await;
+    let (project_a, worktree_id) = client_a.build_local_project("/dir", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+
+    let buffer_a = project_a
+        .update(cx_a, |p, cx| p.open_buffer((worktree_id, "a.txt"), cx))
+        .await
+        .unwrap();
+    let cx_a = cx_a.add_empty_window();
+    let editor_a = cx_a.new_view(|cx| Editor::for_buffer(buffer_a, Some(project_a), cx));
+    let mut editor_cx_a = EditorTestContext {
+        cx: cx_a.clone(),
+        window: cx_a.handle(),
+        editor: editor_a,
+        assertion_cx: AssertionContextManager::new(),
+    };
+
+    let buffer_b = project_b
+        .update(cx_b, |p, cx| p.open_buffer((worktree_id, "a.txt"), cx))
+        .await
+        .unwrap();
+    let cx_b = cx_b.add_empty_window();
+    let editor_b = cx_b.new_view(|cx| Editor::for_buffer(buffer_b, Some(project_b), cx));
+    let mut editor_cx_b = EditorTestContext {
+        cx: cx_b.clone(),
+        window: cx_b.handle(),
+        editor: editor_b,
+        assertion_cx: AssertionContextManager::new(),
+    };
+
+    // Each collaborator makes an edit.
+    editor_cx_a.set_selections_state(indoc! {"
+        oneˇ two
+    "});
+    editor_cx_a.update_editor(|editor, cx| editor.insert("!", cx));
+    executor.run_until_parked();
+    editor_cx_b.set_selections_state(indoc! {"
+        one! twoˇ
+    "});
+    editor_cx_b.update_editor(|editor, cx| editor.insert("?", cx));
+    executor.run_until_parked();
+    assert_eq!(editor_cx_a.buffer_text(), "one! two?\n");
+    assert_eq!(editor_cx_b.buffer_text(), "one! two?\n");
+
+    // Undoing on the guest only reverts the guest's edit, even though the host's edit
+    // is interleaved with it.
+    editor_cx_b.update_editor(|editor, cx| editor.undo(&editor::actions::Undo, cx));
+    executor.run_until_parked();
+    assert_eq!(editor_cx_a.buffer_text(), "one! two\n");
+    assert_eq!(editor_cx_b.buffer_text(), "one! two\n");
+
+    // Undoing again on the guest is a no-op, since the remaining edit is the host's.
+    editor_cx_b.update_editor(|editor, cx| editor.undo(&editor::actions::Undo, cx));
+    executor.run_until_parked();
+    assert_eq!(editor_cx_a.buffer_text(), "one! two\n");
+    assert_eq!(editor_cx_b.buffer_text(), "one! two\n");
+
+    editor_cx_b.update_editor(|editor, cx| editor.redo(&editor::actions::Redo, cx));
+    executor.run_until_parked();
+    assert_eq!(editor_cx_a.buffer_text(), "one! two?\n");
+    assert_eq!(editor_cx_b.buffer_text(), "one! two?\n");
+
+    // Undoing on the host only reverts the host's edit.
+    editor_cx_a.update_editor(|editor, cx| editor.undo(&editor::actions::Undo, cx));
+    executor.run_until_parked();
+    assert_eq!(editor_cx_a.buffer_text(), "one two?\n");
+    assert_eq!(editor_cx_b.buffer_text(), "one two?\n");
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_collaborating_with_completion(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
     let mut server = TestServer::start(cx_a.executor()).await;
@@ -1637,6 +1637,50 @@ async fn test_following_stops_on_unshare(cx_a: &mut TestAppContext, cx_b: &mut T
     })
 }
 
+#[gpui::test]
+async fn test_following_cursor_does_not_edit_buffers(
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let (_server, client_a, client_b, channel_id) = TestServer::start2(cx_a, cx_b).await;
+
+    let (workspace_a, cx_a) = client_a.build_test_workspace(cx_a).await;
+    client_a
+        .host_workspace(&workspace_a, channel_id, cx_a)
+        .await;
+    let (workspace_b, cx_b) = client_b.join_workspace(channel_id, cx_b).await;
+
+    cx_a.simulate_keystrokes("cmd-p");
+    cx_a.run_until_parked();
+    cx_a.simulate_keystrokes("2 enter");
+
+    let editor_a = workspace_a.update(cx_a, |workspace, cx| {
+        workspace.active_item_as::<Editor>(cx).unwrap()
+    });
+    let editor_b = workspace_b.update(cx_b, |workspace, cx| {
+        workspace.active_item_as::<Editor>(cx).unwrap()
+    });
+    let buffer_b = editor_b.update(cx_b, |editor, cx| {
+        editor.buffer().read(cx).as_singleton().unwrap()
+    });
+    let version_b = buffer_b.update(cx_b, |buffer, _| buffer.version());
+
+    // Moving the leader's cursor is relayed to the follower as follow state only,
+    // leaving the follower's copy of the buffer untouched.
+    for offset in [1, 2, 0] {
+        editor_a.update(cx_a, |editor, cx| {
+            editor.change_selections(None, cx, |s| s.select_ranges([offset..offset]))
+        });
+        cx_a.executor()
+            .advance_clock(workspace::item::LEADER_UPDATE_THROTTLE);
+        cx_a.run_until_parked();
+        editor_b.update(cx_b, |editor, cx| {
+            assert_eq!(editor.selections.ranges(cx), vec![offset..offset])
+        });
+    }
+    buffer_b.update(cx_b, |buffer, _| assert_eq!(buffer.version(), version_b));
+}
+
 #[gpui::test]
 async fn test_following_into_excluded_file(
     mut cx_a: &mut TestAppContext,
@@ -1,5 +1,9 @@
 use crate::{
-    rpc::{CLEANUP_TIMEOUT, RECONNECT_TIMEOUT},
+    db::NewUserParams,
+    executor::Executor,
+    rpc::{
+        handle_metrics, Principal, ZedVersion, CLEANUP_TIMEOUT, RECONNECT_TIMEOUT, SHUTDOWN_TIMEOUT,
+    },
     tests::{
         channel_id, following_tests::join_channel, room_participants, rust_lang, RoomParticipants,
         TestClient, TestServer,
@@ -7,14 +11,19 @@ use crate::{
 };
 use anyhow::{anyhow, Result};
 use assistant::{ContextStore, PromptBuilder};
+use axum::Extension;
 use call::{room, ActiveCall, ParticipantLocation, Room};
-use client::{User, RECEIVE_TIMEOUT};
+use client::{Connection, User, RECEIVE_TIMEOUT};
+use collab_ui::collab_panel::{self, CollabPanel};
 use collections::{HashMap, HashSet};
 use fs::{FakeFs, Fs as _, RemoveOptions};
-use futures::{channel::mpsc, StreamExt as _};
+use futures::{
+    channel::{mpsc, oneshot},
+    StreamExt as _,
+};
 use git::repository::GitFileStatus;
 use gpui::{
-    px, size, AppContext, BackgroundExecutor, Model, Modifiers, MouseButton, MouseDownEvent,
+    point, px, size, AppContext, BackgroundExecutor, Model, Modifiers, MouseButton, MouseDownEvent,
     TestAppContext, UpdateGlobal,
 };
 use language::{
@@ -33,6 +42,8 @@ use project::{
     HoverBlockKind, Project, ProjectPath,
 };
 use rand::prelude::*;
+use rpc::{ErrorCode, ErrorExt as _};
+use semantic_version::SemanticVersion;
 use serde_json::json;
 use settings::SettingsStore;
 use std::{
@@ -1434,6 +1445,75 @@ async fn test_unshare_project(
     });
 }
 
+#[gpui::test(iterations = 10)]
+async fn test_host_removes_project_guest(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+    cx_c: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let client_c = server.create_client(cx_c, "user_c").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b), (&client_c, cx_c)])
+        .await;
+
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree("/a", json!({ "a.txt": "a-contents" }))
+        .await;
+    let (project_a, _) = client_a.build_local_project("/a", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    let project_c = client_c.join_remote_project(project_id, cx_c).await;
+    executor.run_until_parked();
+
+    let peer_id_a = client_a.peer_id().unwrap();
+    let peer_id_b = client_b.peer_id().unwrap();
+
+    // Guests can't remove other participants from the project.
+    project_c
+        .update(cx_c, |project, cx| project.remove_guest(peer_id_b, cx))
+        .await
+        .unwrap_err();
+    client_c
+        .request(rpc::proto::RemoveProjectGuest {
+            project_id,
+            peer_id: Some(peer_id_b),
+        })
+        .await
+        .unwrap_err();
+
+    // The host can't remove itself.
+    project_a
+        .update(cx_a, |project, cx| project.remove_guest(peer_id_a, cx))
+        .await
+        .unwrap_err();
+
+    // When the host removes client B, only client B is disconnected from the project.
+    project_a
+        .update(cx_a, |project, cx| project.remove_guest(peer_id_b, cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    project_b.read_with(cx_b, |project, cx| assert!(project.is_disconnected(cx)));
+    project_c.read_with(cx_c, |project, cx| {
+        assert!(!project.is_disconnected(cx));
+        assert!(!project.collaborators().contains_key(&peer_id_b));
+    });
+    project_a.read_with(cx_a, |project, _| {
+        assert!(!project.collaborators().contains_key(&peer_id_b));
+    });
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_project_reconnect(
     executor: BackgroundExecutor,
@@ -3583,6 +3663,64 @@ async fn test_editing_while_guest_opens_buffer(
     buffer_b.read_with(cx_b, |buf, _| assert_eq!(buf.text(), text));
 }
 
+#[gpui::test(iterations = 10)]
+async fn test_guest_opening_same_buffer_concurrently(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree("/dir", json!({ "a.txt": "a-contents" }))
+        .await;
+    let (project_a, worktree_id) = client_a.build_local_project("/dir", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+
+    // Open the same path twice as client B before either open completes.
+    let (open_buffer_1, open_buffer_2) = project_b.update(cx_b, |p, cx| {
+        (
+            p.open_buffer((worktree_id, "a.txt"), cx),
+            p.open_buffer((worktree_id, "a.txt"), cx),
+        )
+    });
+    let buffer_b_1 = cx_b.executor().spawn(open_buffer_1);
+    let buffer_b_2 = cx_b.executor().spawn(open_buffer_2);
+    let buffer_b_1 = buffer_b_1.await.unwrap();
+    let buffer_b_2 = buffer_b_2.await.unwrap();
+
+    // Both opens resolve to the same buffer.
+    assert_eq!(buffer_b_1.entity_id(), buffer_b_2.entity_id());
+    buffer_b_1.read_with(cx_b, |buf, _| assert_eq!(buf.text(), "a-contents"));
+
+    // Opening the path again once it's loaded also returns the same buffer.
+    let buffer_b_3 = project_b
+        .update(cx_b, |p, cx| p.open_buffer((worktree_id, "a.txt"), cx))
+        .await
+        .unwrap();
+    assert_eq!(buffer_b_3.entity_id(), buffer_b_1.entity_id());
+
+    // Edits made through either handle are seen by the host.
+    buffer_b_2.update(cx_b, |buf, cx| buf.edit([(0..0, "X")], None, cx));
+    executor.run_until_parked();
+    let buffer_a = project_a
+        .update(cx_a, |p, cx| p.open_buffer((worktree_id, "a.txt"), cx))
+        .await
+        .unwrap();
+    buffer_a.read_with(cx_a, |buf, _| assert_eq!(buf.text(), "Xa-contents"));
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_leaving_worktree_while_opening_buffer(
     executor: BackgroundExecutor,
@@ -3679,6 +3817,45 @@ async fn test_canceling_buffer_opening(
     buffer_b.read_with(cx_b, |buf, _| assert_eq!(buf.text(), "abc"));
 }
 
+#[gpui::test]
+async fn test_opening_buffer_from_unresponsive_host(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree(
+            "/dir",
+            json!({
+                "a.txt": "abc",
+            }),
+        )
+        .await;
+    let (project_a, worktree_id) = client_a.build_local_project("/dir", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+
+    // The host never finishes loading the buffer, so the server gives up on it.
+    client_a.fs().stall_loads();
+    let buffer_b = project_b.update(cx_b, |p, cx| p.open_buffer((worktree_id, "a.txt"), cx));
+    executor.run_until_parked();
+    executor.advance_clock(rpc::proto::DEFAULT_FORWARD_TIMEOUT);
+    let error = buffer_b.await.unwrap_err();
+    assert_eq!(error.error_code(), ErrorCode::Timeout);
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_leaving_project(
     executor: BackgroundExecutor,
@@ -4060,7 +4237,7 @@ async fn test_collaborating_with_diagnostics(
                 DiagnosticEntry {
                     range: Point::new(0, 4)..Point::new(0, 7),
                     diagnostic: Diagnostic {
-                        group_id: 2,
+                        group_id: 1,
                         message: "message 1".to_string(),
                         severity: lsp::DiagnosticSeverity::ERROR,
                         is_primary: true,
@@ -4070,7 +4247,7 @@ async fn test_collaborating_with_diagnostics(
                 DiagnosticEntry {
                     range: Point::new(0, 10)..Point::new(0, 13),
                     diagnostic: Diagnostic {
-                        group_id: 3,
+                        group_id: 2,
                         severity: lsp::DiagnosticSeverity::WARNING,
                         message: "message 2".to_string(),
                         is_primary: true,
@@ -5461,6 +5638,8 @@ async fn test_contacts(
         ]
     );
     assert_eq!(contacts(&client_d, cx_d), []);
+    assert!(is_online(&client_a, &client_c, cx_a));
+    assert!(!is_online(&client_d, &client_c, cx_d));
 
     server.disconnect_client(client_c.peer_id().unwrap());
     server.forbid_connections();
@@ -5472,6 +5651,8 @@ async fn test_contacts(
             ("user_c".to_string(), "offline", "free")
         ]
     );
+    assert!(!is_online(&client_a, &client_c, cx_a));
+    assert!(is_online(&client_a, &client_b, cx_a));
     assert_eq!(
         contacts(&client_b, cx_b),
         [
@@ -5832,6 +6013,240 @@ async fn test_contacts(
                 .collect()
         })
     }
+
+    fn is_online(client: &TestClient, contact: &TestClient, cx: &TestAppContext) -> bool {
+        let contact_id = contact.user_id().unwrap();
+        client
+            .user_store()
+            .read_with(cx, |store, _| store.is_online(contact_id))
+    }
+}
+
+#[gpui::test]
+async fn test_contact_presence(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .make_contacts(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    executor.run_until_parked();
+
+    let user_a_id = client_a.user_id().unwrap();
+    let presence_of_a = |cx: &TestAppContext| {
+        client_b
+            .user_store()
+            .read_with(cx, |store, _| store.presence(user_a_id))
+    };
+    assert_eq!(presence_of_a(cx_b), rpc::proto::PresenceStatus::Online);
+
+    client_a
+        .user_store()
+        .read_with(cx_a, |store, _| {
+            store.set_presence(rpc::proto::PresenceStatus::Away)
+        })
+        .unwrap();
+    executor.run_until_parked();
+    assert_eq!(presence_of_a(cx_b), rpc::proto::PresenceStatus::Away);
+
+    client_a
+        .user_store()
+        .read_with(cx_a, |store, _| {
+            store.set_presence(rpc::proto::PresenceStatus::Online)
+        })
+        .unwrap();
+    executor.run_until_parked();
+    assert_eq!(presence_of_a(cx_b), rpc::proto::PresenceStatus::Online);
+
+    // Going away and then losing the last connection makes the user offline.
+    client_a
+        .user_store()
+        .read_with(cx_a, |store, _| {
+            store.set_presence(rpc::proto::PresenceStatus::Away)
+        })
+        .unwrap();
+    executor.run_until_parked();
+    server.forbid_connections();
+    server.disconnect_client(client_a.peer_id().unwrap());
+    executor.advance_clock(RECEIVE_TIMEOUT + RECONNECT_TIMEOUT);
+    assert_eq!(presence_of_a(cx_b), rpc::proto::PresenceStatus::Offline);
+
+    // Reconnecting starts out online again.
+    server.allow_connections();
+    client_a
+        .authenticate_and_connect(false, &cx_a.to_async())
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    assert_eq!(presence_of_a(cx_b), rpc::proto::PresenceStatus::Online);
+
+    // Contacts that connect later learn about presence from their initial contacts update.
+    client_a
+        .user_store()
+        .read_with(cx_a, |store, _| {
+            store.set_presence(rpc::proto::PresenceStatus::Away)
+        })
+        .unwrap();
+    executor.run_until_parked();
+    server.forbid_connections();
+    server.disconnect_client(client_b.peer_id().unwrap());
+    executor.advance_clock(RECEIVE_TIMEOUT + RECONNECT_TIMEOUT);
+    server.allow_connections();
+    client_b
+        .authenticate_and_connect(false, &cx_b.to_async())
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    assert_eq!(presence_of_a(cx_b), rpc::proto::PresenceStatus::Away);
+}
+
+#[gpui::test(iterations = 10)]
+async fn test_contact_change_events(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let user_a_id = client_a.user_id().unwrap();
+    let user_b_id = client_b.user_id().unwrap();
+
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    cx_b.update({
+        let changes = changes.clone();
+        |cx| {
+            cx.subscribe(client_b.user_store(), move |_, event, _| {
+                if let client::Event::ContactChanged { user_id, change } = event {
+                    changes.borrow_mut().push((*user_id, *change));
+                }
+            })
+            .detach()
+        }
+    });
+
+    server
+        .make_contacts(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    executor.run_until_parked();
+    assert_eq!(
+        mem::take(&mut *changes.borrow_mut()),
+        [(user_a_id, client::ContactChange::Added)]
+    );
+    client_b.user_store().read_with(cx_b, |store, _| {
+        assert!(store.contact_for_id(user_a_id).unwrap().online);
+    });
+
+    // Contacts are cleared while reconnecting, and come back once reconnected.
+    server.disconnect_client(client_b.peer_id().unwrap());
+    executor.advance_clock(RECEIVE_TIMEOUT);
+    assert_eq!(
+        mem::take(&mut *changes.borrow_mut()),
+        [
+            (user_a_id, client::ContactChange::Removed),
+            (user_a_id, client::ContactChange::Added)
+        ]
+    );
+
+    server.forbid_connections();
+    server.disconnect_client(client_a.peer_id().unwrap());
+    executor.advance_clock(RECEIVE_TIMEOUT + RECONNECT_TIMEOUT);
+    assert_eq!(
+        mem::take(&mut *changes.borrow_mut()),
+        [(user_a_id, client::ContactChange::Updated)]
+    );
+    client_b.user_store().read_with(cx_b, |store, _| {
+        assert!(!store.contact_for_id(user_a_id).unwrap().online);
+    });
+
+    client_b
+        .user_store()
+        .update(cx_b, |store, cx| store.remove_contact(user_a_id, cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    assert_eq!(
+        mem::take(&mut *changes.borrow_mut()),
+        [(user_a_id, client::ContactChange::Removed)]
+    );
+    client_b.user_store().read_with(cx_b, |store, _| {
+        assert!(store.contact_for_id(user_a_id).is_none());
+        assert!(store.contact_for_id(user_b_id).is_none());
+    });
+}
+
+#[gpui::test]
+async fn test_contact_updates_are_debounced(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+    cx_c: &mut TestAppContext,
+) {
+    let mut server = TestServer::start_with_config(executor.clone(), |config| {
+        config.contact_update_debounce_ms = None;
+    })
+    .await;
+    let debounce = server.app_state.config.contact_update_debounce();
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let client_c = server.create_client(cx_c, "user_c").await;
+    server
+        .make_contacts(&mut [(&client_a, cx_a), (&client_b, cx_b), (&client_c, cx_c)])
+        .await;
+    executor.advance_clock(debounce);
+
+    let user_b_id = client_b.user_id().unwrap();
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    cx_c.update({
+        let changes = changes.clone();
+        |cx| {
+            cx.subscribe(client_c.user_store(), move |_, event, _| {
+                if let client::Event::ContactChanged { user_id, change } = event {
+                    changes.borrow_mut().push((*user_id, *change));
+                }
+            })
+            .detach()
+        }
+    });
+    let is_b_busy = |cx: &TestAppContext| {
+        client_c.user_store().read_with(cx, |store, _| {
+            store
+                .contacts()
+                .iter()
+                .any(|contact| contact.user.id == user_b_id && contact.busy)
+        })
+    };
+
+    // Being called and then joining the call both change B's status, but C only hears
+    // about it once, after the debounce window.
+    let active_call_a = cx_a.read(ActiveCall::global);
+    active_call_a
+        .update(cx_a, |call, cx| call.invite(user_b_id, None, cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    let active_call_b = cx_b.read(ActiveCall::global);
+    active_call_b
+        .update(cx_b, |call, cx| call.accept_incoming(cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    assert!(!is_b_busy(cx_c));
+
+    executor.advance_clock(debounce);
+    assert!(is_b_busy(cx_c));
+    assert_eq!(
+        changes
+            .borrow()
+            .iter()
+            .filter(|(user_id, _)| *user_id == user_b_id)
+            .collect::<Vec<_>>(),
+        [&(user_b_id, client::ContactChange::Updated)]
+    );
 }
 
 #[gpui::test(iterations = 10)]
@@ -6667,3 +7082,414 @@ async fn test_remote_git_branches(
 
     assert_eq!(host_branch.as_ref(), "totally-new-branch");
 }
+
+#[gpui::test]
+async fn test_collab_panel_filter(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+    cx_c: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let client_c = server.create_client(cx_c, "user_c").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    server
+        .make_contacts(&mut [(&client_a, cx_a), (&client_c, cx_c)])
+        .await;
+
+    client_b
+        .fs()
+        .insert_tree("/zed-repo", json!({ "a.txt": "a-contents" }))
+        .await;
+    let (project_b, _) = client_b.build_local_project("/zed-repo", cx_b).await;
+    cx_b.read(ActiveCall::global)
+        .update(cx_b, |call, cx| call.share_project(project_b.clone(), cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    let (workspace_a, cx_a) = client_a.build_test_workspace(cx_a).await;
+    let panel = workspace_a.update(cx_a, |workspace, cx| CollabPanel::new(workspace, cx));
+    cx_a.run_until_parked();
+
+    let mut entry_count = |query: &str| {
+        panel.update(cx_a, |panel, cx| panel.set_filter_query(query, cx));
+        cx_a.run_until_parked();
+        panel.read_with(cx_a, |panel, _| panel.entry_count())
+    };
+
+    let unfiltered_count = entry_count("");
+    assert!(entry_count("user_c") < unfiltered_count);
+
+    // Participants are also matched by the names of the worktrees they are sharing,
+    // case-insensitively. User B and their shared project are shown.
+    let unmatched_count = entry_count("nothing-matches");
+    assert_eq!(entry_count("ZED-REPO"), unmatched_count + 2);
+
+    assert_eq!(entry_count(""), unfiltered_count);
+}
+
+#[gpui::test]
+async fn test_graceful_shutdown(executor: BackgroundExecutor, cx_a: &mut TestAppContext) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    assert!(client_a.status().borrow().is_connected());
+
+    // The client receives the shutdown hint and drops its connection to reconnect.
+    server.server.begin_shutdown();
+    executor.run_until_parked();
+    assert!(!client_a.status().borrow().is_connected());
+
+    assert!(
+        server
+            .server
+            .wait_for_connections_to_close(
+                SHUTDOWN_TIMEOUT,
+                Executor::Deterministic(executor.clone())
+            )
+            .await
+    );
+
+    // New connections are rejected while the server is shutting down.
+    executor.advance_clock(RECEIVE_TIMEOUT);
+    executor.run_until_parked();
+    assert!(!client_a.status().borrow().is_connected());
+}
+
+#[gpui::test]
+async fn test_collab_panel_contact_order(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+    cx_c: &mut TestAppContext,
+    cx_d: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let client_c = server.create_client(cx_c, "user_c").await;
+    let client_d = server.create_client(cx_d, "user_d").await;
+    server
+        .make_contacts(&mut [
+            (&client_a, cx_a),
+            (&client_b, cx_b),
+            (&client_c, cx_c),
+            (&client_d, cx_d),
+        ])
+        .await;
+
+    let (workspace_a, cx_a) = client_a.build_test_workspace(cx_a).await;
+    let panel = workspace_a.update(cx_a, |workspace, cx| CollabPanel::new(workspace, cx));
+    cx_a.run_until_parked();
+    panel.read_with(cx_a, |panel, _| {
+        assert_eq!(panel.contact_logins(), ["user_b", "user_c", "user_d"]);
+    });
+
+    // Contacts that are in a call are shown first, ordered alphabetically.
+    let user_d = client_d.user_id().unwrap();
+    cx_c.read(ActiveCall::global)
+        .update(cx_c, |call, cx| call.invite(user_d, None, cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    cx_d.read(ActiveCall::global)
+        .update(cx_d, |call, cx| call.accept_incoming(cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    panel.read_with(cx_a, |panel, _| {
+        assert_eq!(panel.contact_logins(), ["user_c", "user_d", "user_b"]);
+    });
+
+    // Offline contacts are unaffected.
+    server.forbid_connections();
+    server.disconnect_client(client_c.peer_id().unwrap());
+    executor.advance_clock(RECEIVE_TIMEOUT + RECONNECT_TIMEOUT);
+    executor.run_until_parked();
+    panel.read_with(cx_a, |panel, _| {
+        assert_eq!(panel.contact_logins(), ["user_d", "user_b", "user_c"]);
+    });
+}
+
+#[gpui::test]
+async fn test_collab_panel_project_activated_event(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+
+    client_b
+        .fs()
+        .insert_tree("/b", json!({ "b.txt": "b-contents" }))
+        .await;
+    let (project_b, _) = client_b.build_local_project("/b", cx_b).await;
+    let project_id = cx_b
+        .read(ActiveCall::global)
+        .update(cx_b, |call, cx| call.share_project(project_b.clone(), cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    let (workspace_a, cx_a) = client_a.build_test_workspace(cx_a).await;
+    let panel = workspace_a.update(cx_a, |workspace, cx| {
+        let panel = CollabPanel::new(workspace, cx);
+        workspace.add_panel(panel.clone(), cx);
+        workspace.toggle_panel_focus::<CollabPanel>(cx);
+        panel
+    });
+    let events = Rc::new(RefCell::new(Vec::new()));
+    cx_a.update(|cx| {
+        let events = events.clone();
+        cx.subscribe(&panel, move |_, event: &collab_panel::Event, _| {
+            events.borrow_mut().push(event.clone())
+        })
+        .detach();
+    });
+    cx_a.run_until_parked();
+
+    // Clicking user B's project joins it and notifies subscribers of the panel.
+    let project_bounds = cx_a
+        .debug_bounds("ICON-Folder")
+        .expect("shared project should be rendered in the panel");
+    cx_a.simulate_click(project_bounds.center(), Modifiers::none());
+    cx_a.run_until_parked();
+    assert_eq!(
+        events.borrow().as_slice(),
+        [collab_panel::Event::ProjectActivated {
+            project_id,
+            kind: collab_panel::ProjectKind::Joined,
+        }]
+    );
+}
+
+#[gpui::test]
+async fn test_collab_panel_project_context_menu(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+
+    client_b
+        .fs()
+        .insert_tree("/b", json!({ "b.txt": "b-contents" }))
+        .await;
+    let (project_b, _) = client_b.build_local_project("/b", cx_b).await;
+    cx_b.read(ActiveCall::global)
+        .update(cx_b, |call, cx| call.share_project(project_b.clone(), cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    let (workspace_a, cx_a) = client_a.build_test_workspace(cx_a).await;
+    workspace_a.update(cx_a, |workspace, cx| {
+        let panel = CollabPanel::new(workspace, cx);
+        workspace.add_panel(panel, cx);
+        workspace.toggle_panel_focus::<CollabPanel>(cx);
+    });
+    cx_a.run_until_parked();
+
+    let project_bounds = cx_a
+        .debug_bounds("ICON-Folder")
+        .expect("shared project should be rendered in the panel");
+    cx_a.simulate_event(MouseDownEvent {
+        button: MouseButton::Right,
+        position: project_bounds.center(),
+        modifiers: Modifiers::default(),
+        click_count: 1,
+        first_mouse: false,
+    });
+    assert!(cx_a.debug_bounds("MENU_ITEM-Open Project").is_some());
+    assert!(cx_a.debug_bounds("MENU_ITEM-Follow user_b").is_some());
+    assert!(cx_a.debug_bounds("MENU_ITEM-Leave Call").is_some());
+    // The call isn't in a channel, so there is no link to copy.
+    assert!(cx_a.debug_bounds("MENU_ITEM-Copy Channel Link").is_none());
+
+    // Clicking outside of the menu dismisses it.
+    cx_a.simulate_event(MouseDownEvent {
+        button: MouseButton::Left,
+        position: point(px(1.), px(1.)),
+        modifiers: Modifiers::default(),
+        click_count: 1,
+        first_mouse: false,
+    });
+    cx_a.run_until_parked();
+    assert!(cx_a.debug_bounds("MENU_ITEM-Leave Call").is_none());
+}
+
+#[gpui::test]
+async fn test_silent_connections_are_dropped(executor: BackgroundExecutor) {
+    let server = TestServer::start(executor.clone()).await;
+    let db = &server.app_state.db;
+    let user_id = db
+        .create_user(
+            "silent@example.com",
+            false,
+            NewUserParams {
+                github_login: "silent".into(),
+                github_user_id: 1000,
+            },
+        )
+        .await
+        .unwrap()
+        .user_id;
+    let user = db.get_user_by_id(user_id).await.unwrap().unwrap();
+
+    // Connect a client that never reads from or writes to its end of the connection,
+    // as if its process had hung.
+    let (_client_conn, server_conn, _killed) = Connection::in_memory(executor.clone());
+    let (connection_id_tx, connection_id_rx) = oneshot::channel();
+    executor
+        .spawn(server.server.handle_connection(
+            server_conn,
+            "silent".into(),
+            Principal::User(user),
+            ZedVersion(SemanticVersion::new(1, 0, 0)),
+            rpc::PROTOCOL_VERSION,
+            None,
+            Some(connection_id_tx),
+            Executor::Deterministic(executor.clone()),
+        ))
+        .detach();
+    let connection_id = connection_id_rx.await.unwrap();
+    executor.run_until_parked();
+    assert!(server
+        .server
+        .connection_pool
+        .lock()
+        .connection(connection_id)
+        .is_some());
+
+    // The server keeps pinging the client, but never hears back.
+    executor.advance_clock(server.app_state.config.rpc_receive_timeout() / 2);
+    executor.run_until_parked();
+    assert!(server
+        .server
+        .connection_pool
+        .lock()
+        .connection(connection_id)
+        .is_some());
+
+    executor.advance_clock(server.app_state.config.rpc_receive_timeout());
+    executor.run_until_parked();
+    let mut pool = server.server.connection_pool.lock();
+    assert!(pool.connection(connection_id).is_none());
+    assert_eq!(pool.user_connection_ids(user_id).count(), 0);
+}
+
+#[gpui::test]
+async fn test_metrics(executor: BackgroundExecutor, cx_a: &mut TestAppContext) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    client_a
+        .client()
+        .request(rpc::proto::Ping {})
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    let metrics = handle_metrics(Extension(server.server.clone()))
+        .await
+        .unwrap();
+    let lines = metrics.lines().collect::<Vec<_>>();
+    assert!(lines.contains(&"connections 1"), "{metrics}");
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.starts_with("rpc_handler_duration_seconds_count{message=\"Ping\"}")),
+        "{metrics}"
+    );
+    assert!(metrics.contains("channel_messages_sent"), "{metrics}");
+}
+
+#[gpui::test]
+async fn test_message_handling_spans(executor: BackgroundExecutor, cx_a: &mut TestAppContext) {
+    use tracing::{
+        field::{Field, Visit},
+        span,
+    };
+    use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+    #[derive(Clone, Default)]
+    struct SpanRecorder {
+        spans: Arc<Mutex<HashMap<span::Id, HashMap<String, String>>>>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    impl<S: tracing::Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SpanRecorder {
+        fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, _: Context<'_, S>) {
+            if attrs.metadata().name() == "receive message" {
+                let mut fields = HashMap::default();
+                attrs.record(&mut FieldVisitor(&mut fields));
+                self.spans.lock().insert(id.clone(), fields);
+            }
+        }
+
+        fn on_record(&self, id: &span::Id, values: &span::Record<'_>, _: Context<'_, S>) {
+            if let Some(fields) = self.spans.lock().get_mut(id) {
+                values.record(&mut FieldVisitor(fields));
+            }
+        }
+    }
+
+    let recorder = SpanRecorder::default();
+    let _subscriber =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    client_a
+        .client()
+        .request(rpc::proto::Ping {})
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    let spans = recorder.spans.lock();
+    let ping_span = spans
+        .values()
+        .find(|fields| fields.get("type_name").map(String::as_str) == Some("Ping"))
+        .expect("no span was recorded for the ping");
+    assert_eq!(
+        ping_span.get("user_id"),
+        Some(&client_a.user_id().unwrap().to_string())
+    );
+    assert!(ping_span.contains_key("connection_id"));
+    for duration in [
+        "total_duration_ms",
+        "processing_duration_ms",
+        "queue_duration_ms",
+    ] {
+        assert!(
+            ping_span.contains_key(duration),
+            "{duration} wasn't recorded"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use collab_ui::notification_panel::NotificationPanel;
 use gpui::{BackgroundExecutor, TestAppContext};
 use notifications::NotificationEvent;
 use parking_lot::Mutex;
@@ -158,3 +159,52 @@ async fn test_notifications(
         assert_eq!(entry.response, Some(true));
     });
 }
+
+#[gpui::test]
+async fn test_notification_panel_keyboard_selection(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+    cx_c: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let client_c = server.create_client(cx_c, "user_c").await;
+
+    let (workspace_b, cx_b) = client_b.build_test_workspace(cx_b).await;
+    let panel = workspace_b.update(cx_b, |workspace, cx| {
+        let panel = NotificationPanel::new(workspace, cx);
+        workspace.add_panel(panel.clone(), cx);
+        workspace.toggle_panel_focus::<NotificationPanel>(cx);
+        panel
+    });
+
+    // Clients A and C both send contact requests, giving client B two notifications.
+    client_a
+        .user_store()
+        .update(cx_a, |store, cx| store.request_contact(client_b.id(), cx))
+        .await
+        .unwrap();
+    client_c
+        .user_store()
+        .update(cx_c, |store, cx| store.request_contact(client_b.id(), cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    panel.update(cx_b, |panel, _| {
+        assert_eq!(panel.selected_notification_ix(), None);
+    });
+
+    // The selection stops at the last notification.
+    cx_b.simulate_keystrokes("down down down");
+    panel.update(cx_b, |panel, _| {
+        assert_eq!(panel.selected_notification_ix(), Some(1));
+    });
+
+    // And at the first one.
+    cx_b.simulate_keystrokes("up up up");
+    panel.update(cx_b, |panel, _| {
+        assert_eq!(panel.selected_notification_ix(), Some(0));
+    });
+}
@@ -48,7 +48,7 @@ use workspace::{Workspace, WorkspaceStore};
 pub struct TestServer {
     pub app_state: Arc<AppState>,
     pub test_live_kit_server: Arc<live_kit_client::TestServer>,
-    server: Arc<Server>,
+    pub server: Arc<Server>,
     next_github_user_id: i32,
     connection_killers: Arc<Mutex<HashMap<PeerId, Arc<AtomicBool>>>>,
     forbid_connections: Arc<AtomicBool>,
@@ -79,6 +79,13 @@ pub struct ContactsSummary {
 
 impl TestServer {
     pub async fn start(deterministic: BackgroundExecutor) -> Self {
+        Self::start_with_config(deterministic, |_| {}).await
+    }
+
+    pub async fn start_with_config(
+        deterministic: BackgroundExecutor,
+        configure: impl FnOnce(&mut Config),
+    ) -> Self {
         static NEXT_LIVE_KIT_SERVER_ID: AtomicUsize = AtomicUsize::new(0);
 
         let use_postgres = env::var("USE_POSTGRES").ok();
@@ -97,7 +104,9 @@ impl TestServer {
         )
         .unwrap();
         let executor = Executor::Deterministic(deterministic.clone());
-        let app_state = Self::build_app_state(&test_db, &live_kit_server, executor.clone()).await;
+        let mut app_state =
+            Self::build_app_state(&test_db, &live_kit_server, executor.clone()).await;
+        configure(&mut Arc::get_mut(&mut app_state).unwrap().config);
         let epoch = app_state
             .db
             .create_server(&app_state.config.zed_environment)
@@ -243,6 +252,7 @@ impl TestServer {
                                 client_name,
                                 Principal::User(user),
                                 ZedVersion(SemanticVersion::new(1, 0, 0)),
+                                rpc::PROTOCOL_VERSION,
                                 None,
                                 Some(connection_id_tx),
                                 Executor::Deterministic(cx.background_executor().clone()),
@@ -527,6 +537,9 @@ impl TestServer {
                 llm_api_secret: None,
                 rust_log: None,
                 log_json: None,
+                log_stdout: None,
+                log_file: None,
+                log_file_max_bytes: None,
                 zed_environment: "test".into(),
                 blob_store_url: None,
                 blob_store_region: None,
@@ -550,6 +563,10 @@ impl TestServer {
                 stripe_api_key: None,
                 supermaven_admin_api_key: None,
                 user_backfiller_github_access_token: None,
+                rpc_keepalive_interval_ms: None,
+                rpc_receive_timeout_ms: None,
+                // Send contact updates right away, so tests don't need to advance the clock.
+                contact_update_debounce_ms: Some(0),
             },
         })
     }
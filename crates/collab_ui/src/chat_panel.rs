@@ -8,9 +8,9 @@ use db::kvp::KEY_VALUE_STORE;
 use editor::{actions, Editor};
 use gpui::{
     actions, div, list, prelude::*, px, Action, AppContext, AsyncWindowContext, ClipboardItem,
-    CursorStyle, DismissEvent, ElementId, EventEmitter, FocusHandle, FocusableView, FontWeight,
-    HighlightStyle, ListOffset, ListScrollEvent, ListState, Model, Render, Stateful, Subscription,
-    Task, View, ViewContext, VisualContext, WeakView,
+    CursorStyle, DismissEvent, ElementId, EventEmitter, FocusHandle, FocusableView, FontStyle,
+    FontWeight, HighlightStyle, ListOffset, ListScrollEvent, ListState, Model, Render, Stateful,
+    Subscription, Task, View, ViewContext, VisualContext, WeakView,
 };
 use language::LanguageRegistry;
 use menu::Confirm;
@@ -35,6 +35,7 @@ mod message_editor;
 
 const MESSAGE_LOADING_THRESHOLD: usize = 50;
 const CHAT_PANEL_KEY: &str = "ChatPanel";
+const MESSAGE_DELETED: &str = "Message deleted";
 
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(|workspace: &mut Workspace, _| {
@@ -307,7 +308,7 @@ impl ChatPanel {
         reply_to_message: &Option<ChannelMessage>,
         cx: &mut ViewContext<Self>,
     ) -> impl IntoElement {
-        let reply_to_message = match reply_to_message {
+        let reply_to_message = match reply_to_message.as_ref().filter(|m| !m.deleted) {
             None => {
                 return div().child(
                     h_flex()
@@ -317,13 +318,11 @@ impl ChatPanel {
                         .gap_x_1()
                         .rounded_md()
                         .child(Icon::new(IconName::ReplyArrowRight).color(Color::Muted))
-                        .when(reply_to_message.is_none(), |el| {
-                            el.child(
-                                Label::new("Message has been deleted...")
-                                    .size(LabelSize::XSmall)
-                                    .color(Color::Muted),
-                            )
-                        }),
+                        .child(
+                            Label::new("Message has been deleted...")
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted),
+                        ),
                 )
             }
             Some(val) => val,
@@ -559,10 +558,17 @@ impl ChatPanel {
                     )
                 },
             )
-            .child(
-                self.render_popover_buttons(cx, message_id, can_delete_message, can_edit_message)
+            .when(!message.deleted, |this| {
+                this.child(
+                    self.render_popover_buttons(
+                        cx,
+                        message_id,
+                        can_delete_message,
+                        can_edit_message,
+                    )
                     .mt_neg_2p5(),
-            )
+                )
+            })
     }
 
     fn has_open_menu(&self, message_id: Option<u64>) -> bool {
@@ -748,6 +754,19 @@ impl ChatPanel {
             })
             .collect::<Vec<_>>();
 
+        if message.deleted {
+            let mut rich_text = RichText::new(MESSAGE_DELETED.to_string(), &[], language_registry);
+            rich_text.highlights.push((
+                0..rich_text.text.len(),
+                Highlight::Highlight(HighlightStyle {
+                    color: Some(cx.theme().colors().text_muted),
+                    font_style: Some(FontStyle::Italic),
+                    ..Default::default()
+                }),
+            ));
+            return rich_text;
+        }
+
         const MESSAGE_EDITED: &str = " (edited)";
 
         let mut body = message.body.clone();
@@ -1182,6 +1201,7 @@ mod tests {
             mentions: vec![(ranges[0].clone(), 101), (ranges[1].clone(), 102)],
             reply_to_message_id: None,
             edited_at: None,
+            deleted: false,
         };
 
         let message = ChatPanel::render_markdown_with_mentions(
@@ -1220,6 +1240,52 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_render_deleted_message(cx: &mut AppContext) {
+        let settings = settings::SettingsStore::test(cx);
+        cx.set_global(settings);
+        theme::init(theme::LoadThemes::JustBase, cx);
+
+        let language_registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
+        let message = channel::ChannelMessage {
+            id: ChannelMessageId::Saved(0),
+            body: String::new(),
+            timestamp: OffsetDateTime::now_utc(),
+            sender: Arc::new(client::User {
+                github_login: "fgh".into(),
+                avatar_uri: "avatar_fgh".into(),
+                id: 103,
+            }),
+            nonce: 5,
+            mentions: Vec::new(),
+            reply_to_message_id: None,
+            edited_at: None,
+            deleted: true,
+        };
+
+        let message = ChatPanel::render_markdown_with_mentions(
+            &language_registry,
+            102,
+            &message,
+            UtcOffset::UTC,
+            cx,
+        );
+
+        assert_eq!(message.text, MESSAGE_DELETED);
+        assert_eq!(
+            message.highlights,
+            vec![(
+                0..MESSAGE_DELETED.len(),
+                HighlightStyle {
+                    color: Some(cx.theme().colors().text_muted),
+                    font_style: Some(gpui::FontStyle::Italic),
+                    ..Default::default()
+                }
+                .into()
+            )]
+        );
+    }
+
     #[gpui::test]
     fn test_render_markdown_with_auto_detect_links(cx: &mut AppContext) {
         let language_registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
@@ -1236,6 +1302,7 @@ mod tests {
             mentions: Vec::new(),
             reply_to_message_id: None,
             edited_at: None,
+            deleted: false,
         };
 
         let message = ChatPanel::render_markdown_with_mentions(
@@ -1283,6 +1350,7 @@ mod tests {
             mentions: Vec::new(),
             reply_to_message_id: None,
             edited_at: None,
+            deleted: false,
         };
 
         let message = ChatPanel::render_markdown_with_mentions(
@@ -9,7 +9,7 @@ use client::{ChannelId, Client, Contact, User, UserStore};
 use contact_finder::ContactFinder;
 use db::kvp::KEY_VALUE_STORE;
 use editor::{Editor, EditorElement, EditorStyle};
-use fuzzy::{match_strings, StringMatchCandidate};
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
 use gpui::{
     actions, anchored, canvas, deferred, div, fill, list, point, prelude::*, px, AnyElement,
     AppContext, AsyncWindowContext, Bounds, ClickEvent, ClipboardItem, DismissEvent, Div,
@@ -105,6 +105,21 @@ impl ChannelEditingState {
     }
 }
 
+/// Events emitted by the collab panel in addition to [`PanelEvent`]s.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// A project in the current call was opened from the panel.
+    ProjectActivated { project_id: u64, kind: ProjectKind },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectKind {
+    /// A project shared by the current user.
+    Shared,
+    /// A project shared by another participant, which the current user joined.
+    Joined,
+}
+
 pub struct CollabPanel {
     width: Option<Pixels>,
     fs: Arc<dyn Fs>,
@@ -450,6 +465,31 @@ impl CollabPanel {
                     &Default::default(),
                     executor.clone(),
                 ));
+
+                // Also show participants that are sharing a worktree whose name contains the query.
+                let lowercase_query = query.to_lowercase();
+                if !lowercase_query.is_empty() {
+                    for participant in room.remote_participants().values() {
+                        let user_id = participant.user.id as usize;
+                        if matches.iter().any(|mat| mat.candidate_id == user_id) {
+                            continue;
+                        }
+                        let shares_matching_worktree = participant.projects.iter().any(|project| {
+                            project.worktree_root_names.iter().any(|root_name| {
+                                root_name.to_lowercase().contains(&lowercase_query)
+                            })
+                        });
+                        if shares_matching_worktree {
+                            matches.push(StringMatch {
+                                candidate_id: user_id,
+                                score: 0.,
+                                positions: Vec::new(),
+                                string: participant.user.github_login.clone(),
+                            });
+                        }
+                    }
+                }
+
                 matches.sort_by(|a, b| {
                     let a_is_guest = room.role_for_user(a.candidate_id as u64)
                         == Some(proto::ChannelRole::Guest);
@@ -725,10 +765,13 @@ impl CollabPanel {
                 executor.clone(),
             ));
 
-            let (online_contacts, offline_contacts) = matches
+            let (mut online_contacts, offline_contacts) = matches
                 .iter()
                 .partition::<Vec<_>, _>(|mat| contacts[mat.candidate_id].online);
 
+            // Float contacts who are in a call to the top, keeping the existing order otherwise.
+            online_contacts.sort_by_key(|mat| !contacts[mat.candidate_id].busy);
+
             for (matches, section) in [
                 (online_contacts, Section::Online),
                 (offline_contacts, Section::Offline),
@@ -897,13 +940,15 @@ impl CollabPanel {
         ListItem::new(project_id as usize)
             .selected(is_selected)
             .on_click(cx.listener(move |this, _, cx| {
-                this.workspace
-                    .update(cx, |workspace, cx| {
-                        let app_state = workspace.app_state().clone();
-                        workspace::join_in_room_project(project_id, host_user_id, app_state, cx)
-                            .detach_and_prompt_err("Failed to join project", cx, |_, _| None);
-                    })
-                    .ok();
+                this.activate_participant_project(project_id, host_user_id, cx);
+            }))
+            .on_secondary_mouse_down(cx.listener(move |this, event: &MouseDownEvent, cx| {
+                this.deploy_participant_project_context_menu(
+                    event.position,
+                    project_id,
+                    host_user_id,
+                    cx,
+                )
             }))
             .start_slot(
                 h_flex()
@@ -915,6 +960,32 @@ impl CollabPanel {
             .tooltip(move |cx| Tooltip::text(format!("Open {}", project_name), cx))
     }
 
+    fn activate_participant_project(
+        &mut self,
+        project_id: u64,
+        host_user_id: u64,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let app_state = workspace.read(cx).app_state().clone();
+        workspace::join_in_room_project(project_id, host_user_id, app_state, cx)
+            .detach_and_prompt_err("Failed to join project", cx, |_, _| None);
+
+        let is_local = self
+            .user_store
+            .read(cx)
+            .current_user()
+            .map_or(false, |user| user.id == host_user_id);
+        let kind = if is_local {
+            ProjectKind::Shared
+        } else {
+            ProjectKind::Joined
+        };
+        cx.emit(Event::ProjectActivated { project_id, kind });
+    }
+
     fn render_participant_screen(
         &self,
         peer_id: Option<PeerId>,
@@ -1140,6 +1211,74 @@ impl CollabPanel {
         self.context_menu = Some((context_menu, position, subscription));
     }
 
+    fn deploy_participant_project_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        project_id: u64,
+        host_user_id: u64,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let this = cx.view().clone();
+        let room = ActiveCall::global(cx).read(cx).room().cloned();
+        let Some(room) = room else {
+            return;
+        };
+        let room = room.read(cx);
+        let channel_id = room.channel_id();
+        let host = room
+            .remote_participants()
+            .get(&host_user_id)
+            .map(|participant| (participant.peer_id, participant.user.github_login.clone()));
+
+        let context_menu = ContextMenu::build(cx, |mut context_menu, cx| {
+            context_menu = context_menu.entry(
+                "Open Project",
+                None,
+                cx.handler_for(&this, move |this, cx| {
+                    this.activate_participant_project(project_id, host_user_id, cx)
+                }),
+            );
+            if let Some((peer_id, github_login)) = host {
+                context_menu = context_menu.entry(
+                    format!("Follow {}", github_login),
+                    None,
+                    cx.handler_for(&this, move |this, cx| {
+                        this.workspace
+                            .update(cx, |workspace, cx| workspace.follow(peer_id, cx))
+                            .ok();
+                    }),
+                );
+            }
+            if let Some(channel_id) = channel_id {
+                context_menu = context_menu.entry(
+                    "Copy Channel Link",
+                    None,
+                    cx.handler_for(&this, move |this, cx| {
+                        this.copy_channel_link(channel_id, cx)
+                    }),
+                );
+            }
+            context_menu
+                .separator()
+                .entry("Leave Call", None, Self::leave_call)
+        });
+
+        cx.focus_view(&context_menu);
+        let subscription =
+            cx.subscribe(&context_menu, |this, _, _: &DismissEvent, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(cx)
+                }) {
+                    cx.focus_self();
+                }
+                this.context_menu.take();
+                cx.notify();
+            });
+        self.context_menu = Some((context_menu, position, subscription));
+
+        cx.notify();
+    }
+
     fn deploy_channel_context_menu(
         &mut self,
         position: Point<Pixels>,
@@ -1352,6 +1491,28 @@ impl CollabPanel {
         cx.notify();
     }
 
+    /// Replaces the filter query, updating the entries to only show matching ones.
+    pub fn set_filter_query(&mut self, query: &str, cx: &mut ViewContext<Self>) {
+        self.filter_editor
+            .update(cx, |editor, cx| editor.set_text(query, cx));
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn contact_logins(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry {
+                ListEntry::Contact { contact, .. } => Some(contact.user.github_login.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
     fn reset_filter_editor_text(&mut self, cx: &mut ViewContext<Self>) -> bool {
         self.filter_editor.update(cx, |editor, cx| {
             if editor.buffer().read(cx).len(cx) > 0 {
@@ -1431,20 +1592,7 @@ impl CollabPanel {
                         host_user_id,
                         ..
                     } => {
-                        if let Some(workspace) = self.workspace.upgrade() {
-                            let app_state = workspace.read(cx).app_state().clone();
-                            workspace::join_in_room_project(
-                                *project_id,
-                                *host_user_id,
-                                app_state,
-                                cx,
-                            )
-                            .detach_and_prompt_err(
-                                "Failed to join project",
-                                cx,
-                                |_, _| None,
-                            );
-                        }
+                        self.activate_participant_project(*project_id, *host_user_id, cx);
                     }
                     ListEntry::ParticipantScreen { peer_id, .. } => {
                         let Some(peer_id) = peer_id else {
@@ -2276,7 +2424,7 @@ impl CollabPanel {
                 h_flex()
                     .w_full()
                     .justify_between()
-                    .child(Label::new(github_login.clone()))
+                    .child(Label::new(github_login.clone()).truncate())
                     .when(calling, |el| {
                         el.child(Label::new("Calling").color(Color::Muted))
                     })
@@ -2304,6 +2452,10 @@ impl CollabPanel {
                     this.deploy_contact_context_menu(event.position, contact.clone(), cx);
                 }
             }))
+            .tooltip({
+                let github_login = github_login.clone();
+                move |cx| Tooltip::text(github_login.clone(), cx)
+            })
             .start_slot(
                 // todo handle contacts with no avatar
                 Avatar::new(contact.user.avatar_uri.clone())
@@ -2387,7 +2539,7 @@ impl CollabPanel {
                 h_flex()
                     .w_full()
                     .justify_between()
-                    .child(Label::new(github_login.clone()))
+                    .child(Label::new(github_login.clone()).truncate())
                     .child(h_flex().children(controls)),
             )
             .start_slot(Avatar::new(user.avatar_uri.clone()))
@@ -2490,25 +2642,15 @@ impl CollabPanel {
         let face_pile = if participants.is_empty() {
             None
         } else {
-            let extra_count = participants.len().saturating_sub(FACEPILE_LIMIT);
-            let result = Facepile::new(
-                participants
-                    .iter()
-                    .map(|user| Avatar::new(user.avatar_uri.clone()).into_any_element())
-                    .take(FACEPILE_LIMIT)
-                    .chain(if extra_count > 0 {
-                        Some(
-                            Label::new(format!("+{extra_count}"))
-                                .ml_2()
-                                .into_any_element(),
-                        )
-                    } else {
-                        None
-                    })
-                    .collect::<SmallVec<_>>(),
-            );
-
-            Some(result)
+            Some(
+                Facepile::new(
+                    participants
+                        .iter()
+                        .map(|user| Avatar::new(user.avatar_uri.clone()).into_any_element())
+                        .collect::<SmallVec<_>>(),
+                )
+                .max_faces(FACEPILE_LIMIT),
+            )
         };
 
         let width = self.width.unwrap_or(px(240.));
@@ -2747,6 +2889,8 @@ impl Render for CollabPanel {
 
 impl EventEmitter<PanelEvent> for CollabPanel {}
 
+impl EventEmitter<Event> for CollabPanel {}
+
 impl Panel for CollabPanel {
     fn position(&self, cx: &gpui::WindowContext) -> DockPosition {
         CollaborationPanelSettings::get_global(cx).dock
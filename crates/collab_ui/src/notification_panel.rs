@@ -514,6 +514,21 @@ impl NotificationPanel {
         }
     }
 
+    fn select_next(&mut self, _: &menu::SelectNext, cx: &mut ViewContext<Self>) {
+        self.notification_list.select_next();
+        cx.notify();
+    }
+
+    fn select_prev(&mut self, _: &menu::SelectPrev, cx: &mut ViewContext<Self>) {
+        self.notification_list.select_prev();
+        cx.notify();
+    }
+
+    /// The index of the notification selected with the keyboard, if any.
+    pub fn selected_notification_ix(&self) -> Option<usize> {
+        self.notification_list.selected()
+    }
+
     fn add_toast(&mut self, entry: &NotificationEntry, cx: &mut ViewContext<Self>) {
         if self.is_showing_notification(&entry.notification, cx) {
             return;
@@ -581,6 +596,10 @@ impl NotificationPanel {
 impl Render for NotificationPanel {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         v_flex()
+            .key_context("NotificationPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
             .size_full()
             .child(
                 h_flex()
@@ -640,7 +659,11 @@ impl Render for NotificationPanel {
                         ),
                     )
                 } else {
-                    this.child(list(self.notification_list.clone()).size_full())
+                    this.child(
+                        list(self.notification_list.clone())
+                            .selected_background(cx.theme().colors().ghost_element_selected)
+                            .size_full(),
+                    )
                 }
             })
     }
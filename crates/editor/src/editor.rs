@@ -607,6 +607,7 @@ pub struct Editor {
     collapse_matches: bool,
     autoindent_mode: Option<AutoindentMode>,
     workspace: Option<(WeakView<Workspace>, Option<WorkspaceId>)>,
+    restore_cursor_position_when_added: bool,
     input_enabled: bool,
     use_modal_editing: bool,
     read_only: bool,
@@ -2013,6 +2014,7 @@ impl Editor {
             autoindent_mode: Some(AutoindentMode::EachLine),
             collapse_matches: false,
             workspace: None,
+            restore_cursor_position_when_added: false,
             input_enabled: true,
             use_modal_editing: mode == EditorMode::Full,
             read_only: false,
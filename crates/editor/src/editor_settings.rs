@@ -33,6 +33,7 @@ pub struct EditorSettings {
     pub search: SearchSettings,
     pub auto_signature_help: bool,
     pub show_signature_help_after_edits: bool,
+    pub restore_cursor_on_reopen: bool,
     pub jupyter: Jupyter,
 }
 
@@ -282,6 +283,11 @@ pub struct EditorSettingsContent {
     /// Default: true
     pub show_signature_help_after_edits: Option<bool>,
 
+    /// Whether to restore the last cursor position and scroll offset when reopening a file.
+    ///
+    /// Default: true
+    pub restore_cursor_on_reopen: Option<bool>,
+
     /// Jupyter REPL settings.
     pub jupyter: Option<JupyterContent>,
 }
@@ -32,9 +32,9 @@ use std::{
     any::TypeId,
     borrow::Cow,
     cmp::{self, Ordering},
-    iter,
+    iter, mem,
     ops::Range,
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 use text::{BufferId, Selection};
@@ -691,6 +691,10 @@ impl Item for Editor {
         self.push_to_nav_history(selection.head(), None, cx);
     }
 
+    fn on_removed(&mut self, cx: &mut ViewContext<Self>) {
+        self.remember_cursor_position(cx);
+    }
+
     fn workspace_deactivated(&mut self, cx: &mut ViewContext<Self>) {
         self.hide_hovered_link(cx);
     }
@@ -878,8 +882,11 @@ impl Item for Editor {
         Some(breadcrumbs)
     }
 
-    fn added_to_workspace(&mut self, workspace: &mut Workspace, _: &mut ViewContext<Self>) {
+    fn added_to_workspace(&mut self, workspace: &mut Workspace, cx: &mut ViewContext<Self>) {
         self.workspace = Some((workspace.weak_handle(), workspace.database_id()));
+        if mem::take(&mut self.restore_cursor_position_when_added) {
+            self.restore_cursor_position(cx);
+        }
     }
 
     fn to_item_events(event: &EditorEvent, mut f: impl FnMut(ItemEvent)) {
@@ -1174,7 +1181,83 @@ impl ProjectItem for Editor {
         buffer: Model<Buffer>,
         cx: &mut ViewContext<Self>,
     ) -> Self {
-        Self::for_buffer(buffer, Some(project), cx)
+        let mut editor = Self::for_buffer(buffer, Some(project), cx);
+        editor.restore_cursor_position_when_added = true;
+        editor
+    }
+}
+
+const MAX_REMEMBERED_CURSOR_POSITIONS: usize = 1024;
+
+impl Editor {
+    /// The workspace, worktree root and worktree-relative path under which this editor's
+    /// cursor position is remembered, if it edits a single file in a persisted workspace.
+    fn cursor_position_key(&self, cx: &AppContext) -> Option<(WorkspaceId, PathBuf, PathBuf)> {
+        let workspace_id = self.workspace.as_ref()?.1?;
+        let buffer = self.buffer.read(cx).as_singleton()?;
+        let file = buffer.read(cx).file()?;
+        let worktree = self
+            .project
+            .as_ref()?
+            .read(cx)
+            .worktree_for_id(file.worktree_id(cx), cx)?;
+        let worktree_root = worktree.read(cx).abs_path().to_path_buf();
+        Some((workspace_id, worktree_root, file.path().to_path_buf()))
+    }
+
+    fn remember_cursor_position(&mut self, cx: &mut ViewContext<Self>) {
+        let Some((workspace_id, worktree_root, path)) = self.cursor_position_key(cx) else {
+            return;
+        };
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let cursor = self.selections.newest_anchor().head().to_point(&snapshot);
+        let scroll_anchor = self.scroll_manager.anchor();
+        let scroll_top_row = scroll_anchor.anchor.to_point(&snapshot).row;
+
+        cx.foreground_executor()
+            .spawn(async move {
+                DB.save_cursor_position(
+                    workspace_id,
+                    worktree_root,
+                    path,
+                    cursor.row,
+                    cursor.column,
+                    scroll_top_row,
+                    scroll_anchor.offset.x,
+                    scroll_anchor.offset.y,
+                )
+                .await?;
+                DB.prune_cursor_positions(workspace_id, MAX_REMEMBERED_CURSOR_POSITIONS)
+                    .await
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn restore_cursor_position(&mut self, cx: &mut ViewContext<Self>) {
+        if !EditorSettings::get_global(cx).restore_cursor_on_reopen {
+            return;
+        }
+        let Some((workspace_id, worktree_root, path)) = self.cursor_position_key(cx) else {
+            return;
+        };
+        let Ok(Some((cursor_row, cursor_column, scroll_top_row, x, y))) =
+            DB.get_cursor_position(workspace_id, worktree_root, path)
+        else {
+            return;
+        };
+
+        // The file may have changed since it was closed, so clamp to its current contents.
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let cursor = snapshot.clip_point(Point::new(cursor_row, cursor_column), Bias::Left);
+        let scroll_top = snapshot.clip_point(Point::new(scroll_top_row, 0), Bias::Left);
+        self.change_selections(None, cx, |s| s.select_ranges([cursor..cursor]));
+        self.set_scroll_anchor(
+            ScrollAnchor {
+                anchor: snapshot.anchor_before(scroll_top),
+                offset: gpui::Point::new(x, y),
+            },
+            cx,
+        );
     }
 }
 
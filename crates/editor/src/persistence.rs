@@ -90,6 +90,17 @@ define_connection!(
     //   mtime_seconds: Option<i64>,
     //   mtime_nanos: Option<i32>,
     // )
+    //
+    // editor_cursor_positions(
+    //   workspace_id: usize,
+    //   worktree_root: PathBuf,
+    //   path: PathBuf,
+    //   cursor_row: usize,
+    //   cursor_column: usize,
+    //   scroll_top_row: usize,
+    //   scroll_horizontal_offset: f32,
+    //   scroll_vertical_offset: f32,
+    // )
     pub static ref DB: EditorDb<WorkspaceDb> =
         &[sql! (
             CREATE TABLE editors(
@@ -137,6 +148,22 @@ define_connection!(
             ALTER TABLE editors ADD COLUMN mtime_seconds INTEGER DEFAULT NULL;
             ALTER TABLE editors ADD COLUMN mtime_nanos INTEGER DEFAULT NULL;
         ),
+        sql! (
+            CREATE TABLE editor_cursor_positions(
+                workspace_id INTEGER NOT NULL,
+                worktree_root BLOB NOT NULL,
+                path BLOB NOT NULL,
+                cursor_row INTEGER NOT NULL,
+                cursor_column INTEGER NOT NULL,
+                scroll_top_row INTEGER NOT NULL,
+                scroll_horizontal_offset REAL NOT NULL,
+                scroll_vertical_offset REAL NOT NULL,
+                PRIMARY KEY(workspace_id, worktree_root, path),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            ) STRICT;
+        ),
         ];
 );
 
@@ -191,6 +218,50 @@ impl EditorDb {
         }
     }
 
+    // Returns the cursor row and column, the scroll top row, and offset
+    query! {
+        pub fn get_cursor_position(
+            workspace_id: WorkspaceId,
+            worktree_root: PathBuf,
+            path: PathBuf
+        ) -> Result<Option<(u32, u32, u32, f32, f32)>> {
+            SELECT cursor_row, cursor_column, scroll_top_row, scroll_horizontal_offset, scroll_vertical_offset
+            FROM editor_cursor_positions
+            WHERE workspace_id = ? AND worktree_root = ? AND path = ?
+        }
+    }
+
+    query! {
+        pub async fn save_cursor_position(
+            workspace_id: WorkspaceId,
+            worktree_root: PathBuf,
+            path: PathBuf,
+            cursor_row: u32,
+            cursor_column: u32,
+            scroll_top_row: u32,
+            horizontal_offset: f32,
+            vertical_offset: f32
+        ) -> Result<()> {
+            INSERT OR REPLACE INTO editor_cursor_positions
+                (workspace_id, worktree_root, path, cursor_row, cursor_column, scroll_top_row, scroll_horizontal_offset, scroll_vertical_offset)
+            VALUES
+                (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+        }
+    }
+
+    // Keeps only the most recently saved cursor positions of a workspace.
+    query! {
+        pub async fn prune_cursor_positions(workspace_id: WorkspaceId, limit: usize) -> Result<()> {
+            DELETE FROM editor_cursor_positions
+            WHERE workspace_id = ?1 AND rowid NOT IN (
+                SELECT rowid FROM editor_cursor_positions
+                WHERE workspace_id = ?1
+                ORDER BY rowid DESC
+                LIMIT ?2
+            )
+        }
+    }
+
     pub async fn delete_unloaded_items(
         &self,
         workspace: WorkspaceId,
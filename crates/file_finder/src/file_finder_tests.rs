@@ -409,6 +409,78 @@ async fn test_matching_cancellation(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_stale_search_results_are_discarded(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/dir",
+            json!({
+                "hello": "",
+                "halogen-light": "",
+                "happiness": "",
+                "hi": "",
+                "hiccup": "",
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/dir".as_ref()], cx).await;
+
+    let (picker, _, cx) = build_find_picker(project, cx);
+
+    // Issue two searches in quick succession. The first one is cancelled by the second, and
+    // whichever order they finish in, only the latest query's results are shown.
+    let (first_search_id, first_search, second_search) = picker.update(cx, |picker, cx| {
+        let first_search_id = picker.delegate.search_count;
+        let first_search = picker.delegate.spawn_search(test_path_position("hi"), cx);
+        let second_search = picker.delegate.spawn_search(test_path_position("happ"), cx);
+        (first_search_id, first_search, second_search)
+    });
+    second_search.await;
+    first_search.await;
+
+    picker.update(cx, |picker, cx| {
+        assert_eq!(
+            picker
+                .delegate
+                .latest_search_query
+                .as_ref()
+                .unwrap()
+                .raw_query,
+            "happ"
+        );
+        assert_eq!(
+            collect_search_matches(picker).search_paths_only(),
+            vec![PathBuf::from("happiness")]
+        );
+
+        // Results from the older search arriving late don't replace the newer ones.
+        picker.delegate.set_search_matches(
+            first_search_id,
+            false,
+            test_path_position("hi"),
+            Vec::new(),
+            cx,
+        );
+        assert_eq!(
+            picker
+                .delegate
+                .latest_search_query
+                .as_ref()
+                .unwrap()
+                .raw_query,
+            "happ"
+        );
+        assert_eq!(
+            collect_search_matches(picker).search_paths_only(),
+            vec![PathBuf::from("happiness")]
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_ignored_root(cx: &mut TestAppContext) {
     let app_state = init_test(cx);
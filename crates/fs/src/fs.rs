@@ -135,6 +135,11 @@ impl dyn Fs {
         GlobalFs::global(cx).0.clone()
     }
 
+    /// Returns the global [`Fs`], if one has been set.
+    pub fn try_global(cx: &AppContext) -> Option<Arc<Self>> {
+        cx.try_global::<GlobalFs>().map(|fs| fs.0.clone())
+    }
+
     /// Sets the global [`Fs`].
     pub fn set_global(fs: Arc<Self>, cx: &mut AppContext) {
         cx.set_global(GlobalFs(fs));
@@ -801,6 +806,11 @@ impl Watcher for RealWatcher {
     }
 }
 
+/// The number of symlinks that `FakeFs` will follow while resolving a path,
+/// matching Linux's `MAXSYMLINKS`.
+#[cfg(any(test, feature = "test-support"))]
+const MAX_SYMLINK_DEPTH: usize = 40;
+
 #[cfg(any(test, feature = "test-support"))]
 pub struct FakeFs {
     // Use an unfair lock to ensure tests are deterministic.
@@ -816,6 +826,7 @@ struct FakeFsState {
     git_event_tx: smol::channel::Sender<PathBuf>,
     event_txs: Vec<smol::channel::Sender<Vec<PathEvent>>>,
     events_paused: bool,
+    loads_stalled: bool,
     buffered_events: Vec<PathEvent>,
     metadata_call_count: usize,
     read_dir_call_count: usize,
@@ -864,6 +875,7 @@ impl FakeFsState {
         let mut path = target.to_path_buf();
         let mut canonical_path = PathBuf::new();
         let mut entry_stack = Vec::new();
+        let mut symlink_count = 0;
         'outer: loop {
             let mut path_components = path.components().peekable();
             let mut prefix = None;
@@ -896,6 +908,12 @@ impl FakeFsState {
                             if path_components.peek().is_some() || follow_symlink {
                                 let entry = entry.lock();
                                 if let FakeFsEntry::Symlink { target, .. } = &*entry {
+                                    // Like the OS, give up on symlink chains that are too
+                                    // long to be anything but a loop.
+                                    symlink_count += 1;
+                                    if symlink_count > MAX_SYMLINK_DEPTH {
+                                        return None;
+                                    }
                                     let mut target = target.clone();
                                     target.extend(path_components);
                                     path = target;
@@ -988,6 +1006,7 @@ impl FakeFs {
                 event_txs: Default::default(),
                 buffered_events: Vec::new(),
                 events_paused: false,
+                loads_stalled: false,
                 read_dir_call_count: 0,
                 metadata_call_count: 0,
             }),
@@ -1114,6 +1133,9 @@ impl FakeFs {
         let path = path.as_ref();
         let path = normalize_path(path);
         self.simulate_random_delay().await;
+        if self.state.lock().loads_stalled {
+            futures::future::pending::<()>().await;
+        }
         let state = self.state.lock();
         let entry = state.read_path(&path)?;
         let entry = entry.lock();
@@ -1124,6 +1146,11 @@ impl FakeFs {
         self.state.lock().events_paused = true;
     }
 
+    /// Makes every subsequent file load hang forever, like a disk that stopped responding.
+    pub fn stall_loads(&self) {
+        self.state.lock().loads_stalled = true;
+    }
+
     pub fn buffered_event_count(&self) -> usize {
         self.state.lock().buffered_events.len()
     }
@@ -2083,6 +2110,39 @@ mod tests {
             "D",
         );
     }
+
+    #[test]
+    fn test_real_fs_watch() {
+        let dir = TempDir::new().unwrap();
+        // Canonicalize so that paths match the ones reported by the platform, e.g. `/private/var` on macOS.
+        let root = dir.path().canonicalize().unwrap();
+        let fs = RealFs::default();
+
+        smol::block_on(async {
+            let (mut events, _watcher) = fs.watch(&root, Duration::from_millis(10)).await;
+            std::fs::write(root.join("file.txt"), "hello").unwrap();
+
+            let observed_file = async {
+                while let Some(events) = events.next().await {
+                    if events
+                        .iter()
+                        .any(|event| event.path == root.join("file.txt"))
+                    {
+                        return true;
+                    }
+                }
+                false
+            };
+            let timeout = async {
+                smol::Timer::after(Duration::from_secs(5)).await;
+                false
+            };
+            assert!(
+                smol::future::or(observed_file, timeout).await,
+                "file creation was not observed"
+            );
+        });
+    }
 }
 
 #[cfg(target_os = "linux")]
@@ -383,6 +383,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_match_path_ranking() {
+        let paths = vec!["flip.rs", "fooplan.rs", "foo/plan.rs", "src/fpl.rs"];
+
+        // Consecutive matches after a path separator rank highest, then matches at the start of a
+        // path segment, then matches within a segment. `flip.rs` doesn't contain the query's
+        // characters in order, so it doesn't match at all.
+        assert_eq!(
+            match_single_path_query("fpl", false, &paths),
+            vec![
+                ("src/fpl.rs", vec![4, 5, 6]),
+                ("foo/plan.rs", vec![0, 4, 5]),
+                ("fooplan.rs", vec![0, 3, 4]),
+            ]
+        );
+    }
+
     #[test]
     fn test_lowercase_longer_than_uppercase() {
         // This character has more chars in lower-case than in upper-case.
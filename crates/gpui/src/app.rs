@@ -30,9 +30,9 @@ use util::ResultExt;
 use crate::{
     current_platform, hash, init_app_menus, Action, ActionRegistry, Any, AnyView, AnyWindowHandle,
     Asset, AssetSource, BackgroundExecutor, ClipboardItem, Context, DispatchPhase, DisplayId,
-    Entity, EventEmitter, ForegroundExecutor, Global, KeyBinding, Keymap, Keystroke, LayoutId,
-    Menu, MenuItem, OwnedMenu, PathPromptOptions, Pixels, Platform, PlatformDisplay, Point,
-    PromptBuilder, PromptHandle, PromptLevel, Render, RenderablePromptHandle, Reservation,
+    Entity, EventEmitter, ForegroundExecutor, Global, KeyBinding, KeyDownEvent, Keymap, Keystroke,
+    LayoutId, Menu, MenuItem, OwnedMenu, PathPromptOptions, Pixels, Platform, PlatformDisplay,
+    Point, PromptBuilder, PromptHandle, PromptLevel, Render, RenderablePromptHandle, Reservation,
     SharedString, SubscriberSet, Subscription, SvgRenderer, Task, TextSystem, View, ViewContext,
     Window, WindowAppearance, WindowContext, WindowHandle, WindowId,
 };
@@ -214,6 +214,8 @@ type Handler = Box<dyn FnMut(&mut AppContext) -> bool + 'static>;
 type Listener = Box<dyn FnMut(&dyn Any, &mut AppContext) -> bool + 'static>;
 pub(crate) type KeystrokeObserver =
     Box<dyn FnMut(&KeystrokeEvent, &mut WindowContext) -> bool + 'static>;
+pub(crate) type WindowKeyHandler =
+    Box<dyn FnMut(&KeyDownEvent, &mut WindowContext) -> bool + 'static>;
 type QuitHandler = Box<dyn FnOnce(&mut AppContext) -> LocalBoxFuture<'static, ()> + 'static>;
 type ReleaseListener = Box<dyn FnOnce(&mut dyn Any, &mut AppContext) + 'static>;
 type NewViewListener = Box<dyn FnMut(AnyView, &mut WindowContext) + 'static>;
@@ -252,6 +254,7 @@ pub struct AppContext {
     // TypeId is the type of the event that the listener callback expects
     pub(crate) event_listeners: SubscriberSet<EntityId, (TypeId, Listener)>,
     pub(crate) keystroke_observers: SubscriberSet<(), KeystrokeObserver>,
+    pub(crate) window_key_handlers: SubscriberSet<WindowId, WindowKeyHandler>,
     pub(crate) release_listeners: SubscriberSet<EntityId, ReleaseListener>,
     pub(crate) global_observers: SubscriberSet<TypeId, Handler>,
     pub(crate) quit_observers: SubscriberSet<(), QuitHandler>,
@@ -310,6 +313,7 @@ impl AppContext {
                 event_listeners: SubscriberSet::new(),
                 release_listeners: SubscriberSet::new(),
                 keystroke_observers: SubscriberSet::new(),
+                window_key_handlers: SubscriberSet::new(),
                 global_observers: SubscriberSet::new(),
                 quit_observers: SubscriberSet::new(),
                 layout_id_buffer: Default::default(),
@@ -743,6 +747,22 @@ impl AppContext {
                         event,
                     } => self.apply_emit_effect(emitter, event_type, event),
 
+                    Effect::EmitAfterNotify {
+                        emitter,
+                        event_type,
+                        event,
+                    } => {
+                        if self.pending_notifications.contains(&emitter) {
+                            self.pending_effects.push_back(Effect::EmitAfterNotify {
+                                emitter,
+                                event_type,
+                                event,
+                            });
+                        } else {
+                            self.apply_emit_effect(emitter, event_type, event);
+                        }
+                    }
+
                     Effect::Refresh => {
                         self.apply_refresh_effect();
                     }
@@ -968,7 +988,9 @@ impl AppContext {
         self.globals_by_type.drain();
     }
 
-    /// Remove the global of the given type from the app context. Does not notify global observers.
+    /// Remove the global of the given type from the app context, notifying global observers.
+    /// Panics if a global for that type has not been assigned, see [`Self::has_global`].
+    #[track_caller]
     pub fn remove_global<G: Global>(&mut self) -> G {
         let global_type = TypeId::of::<G>();
         self.push_effect(Effect::NotifyGlobalObservers { global_type });
@@ -1111,6 +1133,21 @@ impl AppContext {
         )
     }
 
+    /// Register a callback to be invoked when a key is pressed in the given window, before the
+    /// key is dispatched to any element or action. Returning `true` from the callback consumes
+    /// the key, halting any further dispatch.
+    pub fn on_window_key(
+        &mut self,
+        window: AnyWindowHandle,
+        f: impl FnMut(&KeyDownEvent, &mut WindowContext) -> bool + 'static,
+    ) -> Subscription {
+        let (subscription, activate) = self
+            .window_key_handlers
+            .insert(window.window_id(), Box::new(f));
+        activate();
+        subscription
+    }
+
     /// Register key bindings.
     pub fn bind_keys(&mut self, bindings: impl IntoIterator<Item = KeyBinding>) {
         self.keymap.borrow_mut().add_bindings(bindings);
@@ -1499,6 +1536,11 @@ pub(crate) enum Effect {
         event_type: TypeId,
         event: Box<dyn Any>,
     },
+    EmitAfterNotify {
+        emitter: EntityId,
+        event_type: TypeId,
+        event: Box<dyn Any>,
+    },
     Refresh,
     NotifyGlobalObservers {
         global_type: TypeId,
@@ -163,6 +163,26 @@ impl AsyncAppContext {
         self.foreground_executor.spawn(f(self.clone()))
     }
 
+    /// Run the given future on the background executor, then invoke `on_complete` with its output
+    /// back on the foreground, in the context of the app. Use this to offload CPU-bound work
+    /// whose result needs to be applied to models or windows. The returned task resolves to an
+    /// error if the app was released before the work completed.
+    pub fn background_spawn<T, R>(
+        &self,
+        work: impl Future<Output = T> + Send + 'static,
+        on_complete: impl FnOnce(T, &mut AppContext) -> R + 'static,
+    ) -> Task<Result<R>>
+    where
+        T: Send + 'static,
+        R: 'static,
+    {
+        let work = self.background_executor.spawn(work);
+        self.spawn(|cx| async move {
+            let output = work.await;
+            cx.update(|cx| on_complete(output, cx))
+        })
+    }
+
     /// Determine whether global state of the specified type has been assigned.
     /// Returns an error if the `AppContext` has been dropped.
     pub fn has_global<G: Global>(&self) -> Result<bool> {
@@ -415,3 +435,27 @@ impl VisualContext for AsyncWindowContext {
             .update(self, |_, cx| view.update(cx, |_, cx| cx.emit(DismissEvent)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as gpui, Context as _, TestAppContext};
+
+    #[gpui::test]
+    async fn test_background_spawn(cx: &mut TestAppContext) {
+        let model = cx.new_model(|_| 0u64);
+        let task = cx
+            .to_async()
+            .background_spawn(async { (1..=1000u64).sum::<u64>() }, {
+                let model = model.clone();
+                move |sum, cx| {
+                    model.update(cx, |value, cx| {
+                        *value = sum;
+                        cx.notify();
+                    });
+                    sum * 2
+                }
+            });
+        assert_eq!(task.await.unwrap(), 1001000);
+        assert_eq!(model.read_with(cx, |value, _| *value), 500500);
+    }
+}
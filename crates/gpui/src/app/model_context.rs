@@ -218,6 +218,21 @@ impl<'a, T> ModelContext<'a, T> {
             event: Box::new(event),
         });
     }
+
+    /// Emit an event like [`ModelContext::emit`], but only deliver it once this model has no
+    /// pending notification, including notifications queued while this event waits.
+    /// Use this when observers must see the model's updated state before subscribers see the event.
+    pub fn emit_after_notify<Evt>(&mut self, event: Evt)
+    where
+        T: EventEmitter<Evt>,
+        Evt: 'static,
+    {
+        self.app.pending_effects.push_back(Effect::EmitAfterNotify {
+            emitter: self.model_state.entity_id,
+            event_type: TypeId::of::<Evt>(),
+            event: Box::new(event),
+        });
+    }
 }
 
 impl<'a, T> Context for ModelContext<'a, T> {
@@ -291,3 +306,85 @@ impl<T> BorrowMut<AppContext> for ModelContext<'_, T> {
         self.app
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as gpui, Context as _, EventEmitter, TestAppContext};
+    use std::{cell::RefCell, rc::Rc};
+
+    struct Counter(usize);
+
+    impl EventEmitter<usize> for Counter {}
+
+    #[gpui::test]
+    fn test_emit_ordering(cx: &mut TestAppContext) {
+        let counter = cx.new_model(|_| Counter(0));
+        let log = Rc::new(RefCell::new(Vec::new()));
+        cx.update(|cx| {
+            cx.observe(&counter, {
+                let log = log.clone();
+                move |counter, cx| {
+                    log.borrow_mut()
+                        .push(format!("notified {}", counter.read(cx).0))
+                }
+            })
+            .detach();
+            cx.subscribe(&counter, {
+                let log = log.clone();
+                move |_, event: &usize, _| log.borrow_mut().push(format!("event {event}"))
+            })
+            .detach();
+        });
+
+        // By default, events and notifications are delivered in the order they were queued.
+        counter.update(cx, |counter, cx| {
+            counter.0 = 1;
+            cx.emit(1);
+            cx.notify();
+        });
+        assert_eq!(log.take(), ["event 1", "notified 1"]);
+
+        counter.update(cx, |counter, cx| {
+            counter.0 = 2;
+            cx.emit_after_notify(2);
+            cx.notify();
+        });
+        assert_eq!(log.take(), ["notified 2", "event 2"]);
+
+        // Notifications from other models don't hold the event back.
+        let other = cx.new_model(|_| Counter(0));
+        cx.update(|cx| {
+            cx.observe(&other, {
+                let log = log.clone();
+                move |other, cx| {
+                    let count = other.update(cx, |other, cx| {
+                        if other.0 < 3 {
+                            other.0 += 1;
+                            cx.notify();
+                        }
+                        other.0
+                    });
+                    log.borrow_mut().push(format!("other {count}"));
+                }
+            })
+            .detach();
+        });
+        counter.update(cx, |counter, cx| {
+            counter.0 = 3;
+            other.update(cx, |_, cx| cx.notify());
+            cx.emit_after_notify(3);
+            cx.notify();
+        });
+        assert_eq!(
+            log.take(),
+            [
+                "other 1",
+                "notified 3",
+                "other 2",
+                "event 3",
+                "other 3",
+                "other 3"
+            ]
+        );
+    }
+}
@@ -1,11 +1,11 @@
 use crate::{
     Action, AnyView, AnyWindowHandle, AppCell, AppContext, AsyncAppContext, AvailableSpace,
-    BackgroundExecutor, BorrowAppContext, Bounds, ClipboardItem, Context, DrawPhase, Drawable,
-    Element, Empty, Entity, EventEmitter, ForegroundExecutor, Global, InputEvent, Keystroke, Model,
-    ModelContext, Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent,
-    MouseUpEvent, Pixels, Platform, Point, Render, Result, Size, Task, TestDispatcher,
-    TestPlatform, TestWindow, TextSystem, View, ViewContext, VisualContext, WindowBounds,
-    WindowContext, WindowHandle, WindowOptions,
+    BackgroundExecutor, BorrowAppContext, Bounds, ClipboardItem, Context, DisplayId, DrawPhase,
+    Drawable, Element, Empty, Entity, EventEmitter, ForegroundExecutor, Global, InputEvent,
+    Keystroke, Model, ModelContext, Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent,
+    MouseMoveEvent, MouseUpEvent, Pixels, Platform, PlatformDisplay, Point, Render, Result, Size,
+    Task, TestDispatcher, TestDisplay, TestPlatform, TestWindow, TextSystem, View, ViewContext,
+    VisualContext, WindowBounds, WindowContext, WindowHandle, WindowOptions,
 };
 use anyhow::{anyhow, bail};
 use futures::{channel::oneshot, Stream, StreamExt};
@@ -282,6 +282,26 @@ impl TestAppContext {
         self.test_platform.opened_url.borrow().clone()
     }
 
+    /// Replaces the displays reported by the platform, with the first one being the primary
+    /// display. Returns the ids assigned to each display, in order.
+    pub fn simulate_displays(&self, displays: Vec<(Bounds<Pixels>, f32)>) -> Vec<DisplayId> {
+        let displays = displays
+            .into_iter()
+            .enumerate()
+            .map(|(ix, (bounds, scale_factor))| {
+                TestDisplay::with_bounds(DisplayId(ix as u32 + 1), bounds, scale_factor)
+            })
+            .collect::<Vec<_>>();
+        let ids = displays.iter().map(|display| display.id()).collect();
+        self.test_platform.simulate_displays(
+            displays
+                .into_iter()
+                .map(|display| Rc::new(display) as Rc<dyn PlatformDisplay>)
+                .collect(),
+        );
+        ids
+    }
+
     /// Simulates the user resizing the window to the new size.
     pub fn simulate_window_resize(&self, window_handle: AnyWindowHandle, size: Size<Pixels>) {
         self.test_window(window_handle).simulate_resize(size);
@@ -42,19 +42,23 @@ impl fmt::Debug for Rgba {
 }
 
 impl Rgba {
-    /// Create a new [`Rgba`] color by blending this and another color together
+    /// Create a new [`Rgba`] color by compositing `other` over this color
+    /// using the "source-over" operator.
     pub fn blend(&self, other: Rgba) -> Self {
         if other.a >= 1.0 {
             other
         } else if other.a <= 0.0 {
-            return *self;
+            *self
         } else {
-            return Rgba {
-                r: (self.r * (1.0 - other.a)) + (other.r * other.a),
-                g: (self.g * (1.0 - other.a)) + (other.g * other.a),
-                b: (self.b * (1.0 - other.a)) + (other.b * other.a),
-                a: self.a,
-            };
+            let a = other.a + self.a * (1.0 - other.a);
+            let composite =
+                |dst: f32, src: f32| (src * other.a + dst * self.a * (1.0 - other.a)) / a;
+            Rgba {
+                r: composite(self.r, other.r),
+                g: composite(self.g, other.g),
+                b: composite(self.b, other.b),
+                a,
+            }
         }
     }
 }
@@ -484,6 +488,30 @@ impl Hsla {
             a: self.a * factor.clamp(0., 1.),
         }
     }
+
+    /// Returns a new HSLA color with the same hue, saturation, and lightness, but with the given
+    /// alpha value, clamped between 0.0 and 1.0.
+    pub fn alpha(&self, a: f32) -> Self {
+        Hsla {
+            a: a.clamp(0., 1.),
+            ..*self
+        }
+    }
+
+    /// Returns a new HSLA color whose lightness is increased by the given amount, clamped so that
+    /// the lightness doesn't exceed 1.0.
+    pub fn lighten(&self, amount: f32) -> Self {
+        Hsla {
+            l: (self.l + amount).clamp(0., 1.),
+            ..*self
+        }
+    }
+
+    /// Returns a new HSLA color whose lightness is decreased by the given amount, clamped so that
+    /// the lightness doesn't go below 0.0.
+    pub fn darken(&self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
 }
 
 impl From<Rgba> for Hsla {
@@ -584,4 +612,87 @@ mod tests {
 
         assert_eq!(actual, rgba(0xdeadbeef))
     }
+
+    #[test]
+    fn test_blend_source_over() {
+        let assert_rgba_eq = |actual: Rgba, expected: Rgba| {
+            for (actual, expected) in [
+                (actual.r, expected.r),
+                (actual.g, expected.g),
+                (actual.b, expected.b),
+                (actual.a, expected.a),
+            ] {
+                assert!(
+                    (actual - expected).abs() < 1e-5,
+                    "expected {expected:?}, got {actual:?}"
+                );
+            }
+        };
+        let red = Rgba {
+            r: 1.,
+            g: 0.,
+            b: 0.,
+            a: 1.,
+        };
+        let translucent_blue = Rgba {
+            r: 0.,
+            g: 0.,
+            b: 1.,
+            a: 0.5,
+        };
+
+        // Opaque and fully transparent sources replace or preserve the destination.
+        assert_rgba_eq(translucent_blue.blend(red), red);
+        assert_rgba_eq(
+            red.blend(Rgba {
+                a: 0.,
+                ..translucent_blue
+            }),
+            red,
+        );
+
+        // A translucent source over an opaque destination.
+        assert_rgba_eq(
+            red.blend(translucent_blue),
+            Rgba {
+                r: 0.5,
+                g: 0.,
+                b: 0.5,
+                a: 1.,
+            },
+        );
+
+        // A translucent source over a translucent destination.
+        assert_rgba_eq(
+            Rgba { a: 0.5, ..red }.blend(translucent_blue),
+            Rgba {
+                r: 1. / 3.,
+                g: 0.,
+                b: 2. / 3.,
+                a: 0.75,
+            },
+        );
+        assert_rgba_eq(
+            Rgba::from(Hsla::from(Rgba { a: 0.5, ..red }).blend(Hsla::from(translucent_blue))),
+            Rgba {
+                r: 1. / 3.,
+                g: 0.,
+                b: 2. / 3.,
+                a: 0.75,
+            },
+        );
+    }
+
+    #[test]
+    fn test_alpha_and_lightness_are_clamped() {
+        let color = hsla(0.5, 0.5, 0.5, 0.5);
+        assert_eq!(color.alpha(0.25).a, 0.25);
+        assert_eq!(color.alpha(2.).a, 1.);
+        assert_eq!(color.alpha(-1.).a, 0.);
+
+        assert_eq!(color.lighten(0.25), hsla(0.5, 0.5, 0.75, 0.5));
+        assert_eq!(color.lighten(1.), hsla(0.5, 0.5, 1., 0.5));
+        assert_eq!(color.darken(0.25), hsla(0.5, 0.5, 0.25, 0.5));
+        assert_eq!(color.darken(1.), hsla(0.5, 0.5, 0., 0.5));
+    }
 }
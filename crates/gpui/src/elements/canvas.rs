@@ -1,8 +1,8 @@
 use refineable::Refineable as _;
 
 use crate::{
-    Bounds, Element, ElementId, GlobalElementId, IntoElement, Pixels, Style, StyleRefinement,
-    Styled, WindowContext,
+    Bounds, DispatchPhase, Element, ElementId, GlobalElementId, Hitbox, IntoElement, MouseButton,
+    MouseDownEvent, Pixels, Style, StyleRefinement, Styled, WindowContext,
 };
 
 /// Construct a canvas element with the given paint callback.
@@ -15,6 +15,7 @@ pub fn canvas<T>(
         prepaint: Some(Box::new(prepaint)),
         paint: Some(Box::new(paint)),
         style: StyleRefinement::default(),
+        mouse_down_listeners: Vec::new(),
     }
 }
 
@@ -24,6 +25,24 @@ pub struct Canvas<T> {
     prepaint: Option<Box<dyn FnOnce(Bounds<Pixels>, &mut WindowContext) -> T>>,
     paint: Option<Box<dyn FnOnce(Bounds<Pixels>, T, &mut WindowContext)>>,
     style: StyleRefinement,
+    mouse_down_listeners: Vec<(
+        MouseButton,
+        Box<dyn Fn(&MouseDownEvent, &mut WindowContext)>,
+    )>,
+}
+
+impl<T> Canvas<T> {
+    /// Register a listener to be called when the given mouse button is pressed within
+    /// the bounds of this canvas. The canvas only participates in hit-testing when at
+    /// least one listener has been registered.
+    pub fn on_mouse_down(
+        mut self,
+        button: MouseButton,
+        listener: impl Fn(&MouseDownEvent, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.mouse_down_listeners.push((button, Box::new(listener)));
+        self
+    }
 }
 
 impl<T: 'static> IntoElement for Canvas<T> {
@@ -36,7 +55,7 @@ impl<T: 'static> IntoElement for Canvas<T> {
 
 impl<T: 'static> Element for Canvas<T> {
     type RequestLayoutState = Style;
-    type PrepaintState = Option<T>;
+    type PrepaintState = (Option<T>, Option<Hitbox>);
 
     fn id(&self) -> Option<ElementId> {
         None
@@ -59,8 +78,13 @@ impl<T: 'static> Element for Canvas<T> {
         bounds: Bounds<Pixels>,
         _request_layout: &mut Style,
         cx: &mut WindowContext,
-    ) -> Option<T> {
-        Some(self.prepaint.take().unwrap()(bounds, cx))
+    ) -> Self::PrepaintState {
+        let hitbox = if self.mouse_down_listeners.is_empty() {
+            None
+        } else {
+            Some(cx.insert_hitbox(bounds, false))
+        };
+        (Some(self.prepaint.take().unwrap()(bounds, cx)), hitbox)
     }
 
     fn paint(
@@ -71,6 +95,21 @@ impl<T: 'static> Element for Canvas<T> {
         prepaint: &mut Self::PrepaintState,
         cx: &mut WindowContext,
     ) {
+        let (prepaint, hitbox) = prepaint;
+        if let Some(hitbox) = hitbox.take() {
+            for (button, listener) in self.mouse_down_listeners.drain(..) {
+                let hitbox = hitbox.clone();
+                cx.on_mouse_event(move |event: &MouseDownEvent, phase, cx| {
+                    if phase == DispatchPhase::Bubble
+                        && event.button == button
+                        && hitbox.is_hovered(cx)
+                    {
+                        listener(event, cx);
+                    }
+                });
+            }
+        }
+
         let prepaint = prepaint.take().unwrap();
         style.paint(bounds, cx, |cx| {
             (self.paint.take().unwrap())(bounds, prepaint, cx)
@@ -83,3 +122,43 @@ impl<T> Styled for Canvas<T> {
         &mut self.style
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use crate::{
+        self as gpui, canvas, div, point, px, size, MouseButton, MouseDownEvent, ParentElement,
+        Styled, TestAppContext,
+    };
+
+    #[gpui::test]
+    fn test_canvas_mouse_down(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let clicks = Rc::new(Cell::new(0));
+
+        cx.draw(point(px(0.), px(0.)), size(px(100.), px(100.)), |_| {
+            let clicks = clicks.clone();
+            div().child(
+                canvas(|_, _| {}, |_, _, _| {})
+                    .size(px(20.))
+                    .on_mouse_down(MouseButton::Left, move |_, _| clicks.set(clicks.get() + 1)),
+            )
+        });
+
+        let mouse_down = |x, button| MouseDownEvent {
+            position: point(px(x), px(10.)),
+            button,
+            click_count: 1,
+            ..Default::default()
+        };
+
+        cx.simulate_event(mouse_down(10., MouseButton::Left));
+        assert_eq!(clicks.get(), 1);
+
+        // Presses outside the canvas or with another button are ignored.
+        cx.simulate_event(mouse_down(50., MouseButton::Left));
+        cx.simulate_event(mouse_down(10., MouseButton::Right));
+        assert_eq!(clicks.get(), 1);
+    }
+}
@@ -434,6 +434,33 @@ impl Interactivity {
             .push(Box::new(move |event, cx| listener(event, cx)));
     }
 
+    /// Bind the given callback to double click events of this element. When a double click
+    /// listener is registered, the second click of a double click isn't dispatched to the
+    /// element's click listeners.
+    /// The imperative API equivalent to [`StatefulInteractiveElement::on_double_click`]
+    ///
+    /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to a view's state from this callback.
+    pub fn on_double_click(&mut self, listener: impl Fn(&ClickEvent, &mut WindowContext) + 'static)
+    where
+        Self: Sized,
+    {
+        self.double_click_listeners
+            .push(Box::new(move |event, cx| listener(event, cx)));
+    }
+
+    /// Bind the given callback to right click events of this element, i.e. the right mouse button
+    /// being pressed and released over it.
+    /// The imperative API equivalent to [`StatefulInteractiveElement::on_right_click`]
+    ///
+    /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to a view's state from this callback.
+    pub fn on_right_click(&mut self, listener: impl Fn(&ClickEvent, &mut WindowContext) + 'static)
+    where
+        Self: Sized,
+    {
+        self.right_click_listeners
+            .push(Box::new(move |event, cx| listener(event, cx)));
+    }
+
     /// On drag initiation, this callback will be used to create a new view to render the dragged value for a
     /// drag and drop operation. This API should also be used as the equivalent of 'on drag start' with
     /// the [`Self::on_drag_move`] API
@@ -475,6 +502,13 @@ impl Interactivity {
         self.hover_listener = Some(Box::new(listener));
     }
 
+    /// Delay notifying the hover listener that the hover started by the given duration. If the
+    /// hover ends before the delay elapses, the listener isn't notified at all.
+    /// The imperative API equivalent to [`StatefulInteractiveElement::hover_delay`]
+    pub fn hover_delay(&mut self, delay: Duration) {
+        self.hover_delay = Some(delay);
+    }
+
     /// Use the given callback to construct a new tooltip view when the mouse hovers over this element.
     /// The imperative API equivalent to [`InteractiveElement::tooltip`]
     pub fn tooltip(&mut self, build_tooltip: impl Fn(&mut WindowContext) -> AnyView + 'static)
@@ -952,6 +986,39 @@ pub trait StatefulInteractiveElement: InteractiveElement {
         self
     }
 
+    /// Bind the given callback to double click events of this element. When a double click
+    /// listener is registered, the second click of a double click isn't dispatched to the
+    /// element's click listeners.
+    /// The fluent API equivalent to [`Interactivity::on_double_click`]
+    ///
+    /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to a view's state from this callback.
+    fn on_double_click(
+        mut self,
+        listener: impl Fn(&ClickEvent, &mut WindowContext) + 'static,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().on_double_click(listener);
+        self
+    }
+
+    /// Bind the given callback to right click events of this element, i.e. the right mouse button
+    /// being pressed and released over it.
+    /// The fluent API equivalent to [`Interactivity::on_right_click`]
+    ///
+    /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to a view's state from this callback.
+    fn on_right_click(
+        mut self,
+        listener: impl Fn(&ClickEvent, &mut WindowContext) + 'static,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().on_right_click(listener);
+        self
+    }
+
     /// On drag initiation, this callback will be used to create a new view to render the dragged value for a
     /// drag and drop operation. This API should also be used as the equivalent of 'on drag start' with
     /// the [`Self::on_drag_move`] API
@@ -985,6 +1052,17 @@ pub trait StatefulInteractiveElement: InteractiveElement {
         self
     }
 
+    /// Delay notifying the hover listener that the hover started by the given duration. If the
+    /// hover ends before the delay elapses, the listener isn't notified at all.
+    /// The fluent API equivalent to [`Interactivity::hover_delay`]
+    fn hover_delay(mut self, delay: Duration) -> Self
+    where
+        Self: Sized,
+    {
+        self.interactivity().hover_delay(delay);
+        self
+    }
+
     /// Use the given callback to construct a new tooltip view when the mouse hovers over this element.
     /// The fluent API equivalent to [`Interactivity::tooltip`]
     fn tooltip(mut self, build_tooltip: impl Fn(&mut WindowContext) -> AnyView + 'static) -> Self
@@ -1272,8 +1350,11 @@ pub struct Interactivity {
     pub(crate) drop_listeners: Vec<(TypeId, DropListener)>,
     pub(crate) can_drop_predicate: Option<CanDropPredicate>,
     pub(crate) click_listeners: Vec<ClickListener>,
+    pub(crate) double_click_listeners: Vec<ClickListener>,
+    pub(crate) right_click_listeners: Vec<ClickListener>,
     pub(crate) drag_listener: Option<(Box<dyn Any>, DragListener)>,
     pub(crate) hover_listener: Option<Box<dyn Fn(&bool, &mut WindowContext)>>,
+    pub(crate) hover_delay: Option<Duration>,
     pub(crate) tooltip_builder: Option<TooltipBuilder>,
     pub(crate) occlude_mouse: bool,
 
@@ -1411,6 +1492,8 @@ impl Interactivity {
             || !self.mouse_down_listeners.is_empty()
             || !self.mouse_move_listeners.is_empty()
             || !self.click_listeners.is_empty()
+            || !self.double_click_listeners.is_empty()
+            || !self.right_click_listeners.is_empty()
             || !self.scroll_wheel_listeners.is_empty()
             || self.drag_listener.is_some()
             || !self.drop_listeners.is_empty()
@@ -1725,6 +1808,8 @@ impl Interactivity {
         let mut drag_listener = mem::take(&mut self.drag_listener);
         let drop_listeners = mem::take(&mut self.drop_listeners);
         let click_listeners = mem::take(&mut self.click_listeners);
+        let double_click_listeners = mem::take(&mut self.double_click_listeners);
+        let right_click_listeners = mem::take(&mut self.right_click_listeners);
         let can_drop_predicate = mem::take(&mut self.can_drop_predicate);
 
         if !drop_listeners.is_empty() {
@@ -1760,7 +1845,10 @@ impl Interactivity {
         }
 
         if let Some(element_state) = element_state {
-            if !click_listeners.is_empty() || drag_listener.is_some() {
+            if !click_listeners.is_empty()
+                || !double_click_listeners.is_empty()
+                || drag_listener.is_some()
+            {
                 let pending_mouse_down = element_state
                     .pending_mouse_down
                     .get_or_insert_with(Default::default)
@@ -1838,7 +1926,14 @@ impl Interactivity {
                                     down: mouse_down,
                                     up: event.clone(),
                                 };
-                                for listener in &click_listeners {
+                                let listeners = if mouse_click.is_double_click()
+                                    && !double_click_listeners.is_empty()
+                                {
+                                    &double_click_listeners
+                                } else {
+                                    &click_listeners
+                                };
+                                for listener in listeners {
                                     listener(&mouse_click, cx);
                                 }
                             }
@@ -1847,6 +1942,47 @@ impl Interactivity {
                 });
             }
 
+            if !right_click_listeners.is_empty() {
+                let pending_right_mouse_down = element_state
+                    .pending_right_mouse_down
+                    .get_or_insert_with(Default::default)
+                    .clone();
+
+                cx.on_mouse_event({
+                    let pending_right_mouse_down = pending_right_mouse_down.clone();
+                    let hitbox = hitbox.clone();
+                    move |event: &MouseDownEvent, phase, cx| {
+                        if phase == DispatchPhase::Bubble
+                            && event.button == MouseButton::Right
+                            && hitbox.is_hovered(cx)
+                        {
+                            *pending_right_mouse_down.borrow_mut() = Some(event.clone());
+                        }
+                    }
+                });
+
+                cx.on_mouse_event({
+                    let hitbox = hitbox.clone();
+                    move |event: &MouseUpEvent, phase, cx| {
+                        if phase != DispatchPhase::Bubble || event.button != MouseButton::Right {
+                            return;
+                        }
+                        let Some(mouse_down) = pending_right_mouse_down.borrow_mut().take() else {
+                            return;
+                        };
+                        if hitbox.is_hovered(cx) {
+                            let mouse_click = ClickEvent {
+                                down: mouse_down,
+                                up: event.clone(),
+                            };
+                            for listener in &right_click_listeners {
+                                listener(&mouse_click, cx);
+                            }
+                        }
+                    }
+                });
+            }
+
             if let Some(hover_listener) = self.hover_listener.take() {
                 let hitbox = hitbox.clone();
                 let was_hovered = element_state
@@ -1857,6 +1993,12 @@ impl Interactivity {
                     .pending_mouse_down
                     .get_or_insert_with(Default::default)
                     .clone();
+                let hover_delay = self.hover_delay;
+                let delayed_hover = element_state
+                    .delayed_hover
+                    .get_or_insert_with(Default::default)
+                    .clone();
+                let hover_listener: Rc<dyn Fn(&bool, &mut WindowContext)> = hover_listener.into();
 
                 cx.on_mouse_event(move |_: &MouseMoveEvent, phase, cx| {
                     if phase != DispatchPhase::Bubble {
@@ -1871,7 +2013,36 @@ impl Interactivity {
                         *was_hovered = is_hovered;
                         drop(was_hovered);
 
-                        hover_listener(&is_hovered, cx);
+                        let Some(hover_delay) = hover_delay else {
+                            hover_listener(&is_hovered, cx);
+                            return;
+                        };
+
+                        if is_hovered {
+                            let task = cx.spawn({
+                                let delayed_hover = delayed_hover.clone();
+                                let hover_listener = hover_listener.clone();
+                                move |mut cx| async move {
+                                    cx.background_executor().timer(hover_delay).await;
+                                    cx.update(|cx| {
+                                        delayed_hover.borrow_mut().notified = true;
+                                        hover_listener(&true, cx);
+                                    })
+                                    .ok();
+                                }
+                            });
+                            delayed_hover.borrow_mut().task = Some(task);
+                        } else {
+                            // Dropping the pending task cancels the hover if it hasn't started yet.
+                            let notified = {
+                                let mut delayed_hover = delayed_hover.borrow_mut();
+                                delayed_hover.task.take();
+                                mem::take(&mut delayed_hover.notified)
+                            };
+                            if notified {
+                                hover_listener(&false, cx);
+                            }
+                        }
                     }
                 });
             }
@@ -2218,11 +2389,20 @@ pub struct InteractiveElementState {
     pub(crate) focus_handle: Option<FocusHandle>,
     pub(crate) clicked_state: Option<Rc<RefCell<ElementClickedState>>>,
     pub(crate) hover_state: Option<Rc<RefCell<bool>>>,
+    pub(crate) delayed_hover: Option<Rc<RefCell<DelayedHoverState>>>,
     pub(crate) pending_mouse_down: Option<Rc<RefCell<Option<MouseDownEvent>>>>,
+    pub(crate) pending_right_mouse_down: Option<Rc<RefCell<Option<MouseDownEvent>>>>,
     pub(crate) scroll_offset: Option<Rc<RefCell<Point<Pixels>>>>,
     pub(crate) active_tooltip: Option<Rc<RefCell<Option<ActiveTooltip>>>>,
 }
 
+/// The state of a hover listener whose notification is delayed.
+#[derive(Default)]
+pub(crate) struct DelayedHoverState {
+    task: Option<Task<()>>,
+    notified: bool,
+}
+
 /// The current active tooltip
 pub struct ActiveTooltip {
     pub(crate) tooltip: Option<AnyTooltip>,
@@ -1,5 +1,5 @@
 use crate::{
-    px, AbsoluteLength, AppContext, Asset, Bounds, DefiniteLength, Element, ElementId,
+    px, AbsoluteLength, AnyElement, AppContext, Asset, Bounds, DefiniteLength, Element, ElementId,
     GlobalElementId, Hitbox, Image, InteractiveElement, Interactivity, IntoElement, LayoutId,
     Length, ObjectFit, Pixels, RenderImage, SharedString, SharedUri, StyleRefinement, Styled,
     SvgSize, UriOrPath, WindowContext,
@@ -98,6 +98,8 @@ pub struct Img {
     source: ImageSource,
     grayscale: bool,
     object_fit: ObjectFit,
+    loading: Option<Box<dyn Fn() -> AnyElement>>,
+    fallback: Option<Box<dyn Fn() -> AnyElement>>,
 }
 
 /// Create a new image element.
@@ -107,6 +109,8 @@ pub fn img(source: impl Into<ImageSource>) -> Img {
         source: source.into(),
         grayscale: false,
         object_fit: ObjectFit::Contain,
+        loading: None,
+        fallback: None,
     }
 }
 
@@ -130,6 +134,24 @@ impl Img {
         self.object_fit = object_fit;
         self
     }
+
+    /// Set the element to render in place of the image while it is being loaded.
+    pub fn with_loading(mut self, loading: impl Fn() -> AnyElement + 'static) -> Self {
+        self.loading = Some(Box::new(loading));
+        self
+    }
+
+    /// Set the element to render in place of the image if it fails to load.
+    pub fn with_fallback(mut self, fallback: impl Fn() -> AnyElement + 'static) -> Self {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+}
+
+/// The placeholder rendered in place of an image that isn't available yet.
+struct ImgLayoutState {
+    frame_index: usize,
+    placeholder: Option<AnyElement>,
 }
 
 /// The image state between frames
@@ -139,7 +161,7 @@ struct ImgState {
 }
 
 impl Element for Img {
-    type RequestLayoutState = usize;
+    type RequestLayoutState = ImgLayoutState;
     type PrepaintState = Option<Hitbox>;
 
     fn id(&self) -> Option<ElementId> {
@@ -160,11 +182,25 @@ impl Element for Img {
             });
 
             let frame_index = state.as_ref().map(|state| state.frame_index).unwrap_or(0);
+            let mut placeholder = None;
 
             let layout_id = self
                 .interactivity
                 .request_layout(global_id, cx, |mut style, cx| {
-                    if let Some(data) = self.source.use_data(cx) {
+                    let data = match self.source.use_data(cx) {
+                        Some(Ok(data)) => Some(data),
+                        Some(Err(error)) => {
+                            log::error!("failed to load image: {error}");
+                            placeholder = self.fallback.as_ref().map(|fallback| fallback());
+                            None
+                        }
+                        None => {
+                            placeholder = self.loading.as_ref().map(|loading| loading());
+                            None
+                        }
+                    };
+
+                    if let Some(data) = data {
                         if let Some(state) = &mut state {
                             let frame_count = data.frame_count();
                             if frame_count > 1 {
@@ -218,10 +254,22 @@ impl Element for Img {
                         }
                     }
 
-                    cx.request_layout(style, [])
+                    let placeholder_layout_id = placeholder
+                        .as_mut()
+                        .map(|placeholder| placeholder.request_layout(cx));
+                    cx.request_layout(style, placeholder_layout_id)
                 });
 
-            ((layout_id, frame_index), state)
+            (
+                (
+                    layout_id,
+                    ImgLayoutState {
+                        frame_index,
+                        placeholder,
+                    },
+                ),
+                state,
+            )
         })
     }
 
@@ -229,36 +277,41 @@ impl Element for Img {
         &mut self,
         global_id: Option<&GlobalElementId>,
         bounds: Bounds<Pixels>,
-        _request_layout: &mut Self::RequestLayoutState,
+        layout_state: &mut Self::RequestLayoutState,
         cx: &mut WindowContext,
     ) -> Option<Hitbox> {
         self.interactivity
-            .prepaint(global_id, bounds, bounds.size, cx, |_, _, hitbox, _| hitbox)
+            .prepaint(global_id, bounds, bounds.size, cx, |_, _, hitbox, cx| {
+                if let Some(placeholder) = layout_state.placeholder.as_mut() {
+                    placeholder.prepaint(cx);
+                }
+                hitbox
+            })
     }
 
     fn paint(
         &mut self,
         global_id: Option<&GlobalElementId>,
         bounds: Bounds<Pixels>,
-        frame_index: &mut Self::RequestLayoutState,
+        layout_state: &mut Self::RequestLayoutState,
         hitbox: &mut Self::PrepaintState,
         cx: &mut WindowContext,
     ) {
         let source = self.source.clone();
         self.interactivity
             .paint(global_id, bounds, hitbox.as_ref(), cx, |style, cx| {
+                if let Some(placeholder) = layout_state.placeholder.as_mut() {
+                    placeholder.paint(cx);
+                    return;
+                }
+
                 let corner_radii = style.corner_radii.to_pixels(bounds.size, cx.rem_size());
+                let frame_index = layout_state.frame_index;
 
-                if let Some(data) = source.use_data(cx) {
-                    let new_bounds = self.object_fit.get_bounds(bounds, data.size(*frame_index));
-                    cx.paint_image(
-                        new_bounds,
-                        corner_radii,
-                        data.clone(),
-                        *frame_index,
-                        self.grayscale,
-                    )
-                    .log_err();
+                if let Some(Ok(data)) = source.use_data(cx) {
+                    let new_bounds = self.object_fit.get_bounds(bounds, data.size(frame_index));
+                    cx.paint_image(new_bounds, corner_radii, data, frame_index, self.grayscale)
+                        .log_err();
                 }
             })
     }
@@ -285,7 +338,11 @@ impl InteractiveElement for Img {
 }
 
 impl ImageSource {
-    pub(crate) fn use_data(&self, cx: &mut WindowContext) -> Option<Arc<RenderImage>> {
+    /// Returns `None` while the image is still loading.
+    pub(crate) fn use_data(
+        &self,
+        cx: &mut WindowContext,
+    ) -> Option<Result<Arc<RenderImage>, ImageCacheError>> {
         match self {
             ImageSource::Uri(_) | ImageSource::Embedded(_) | ImageSource::File(_) => {
                 let uri_or_path: UriOrPath = match self {
@@ -295,11 +352,13 @@ impl ImageSource {
                     _ => unreachable!(),
                 };
 
-                cx.use_asset::<ImageAsset>(&uri_or_path)?.log_err()
+                cx.use_asset::<ImageAsset>(&uri_or_path)
             }
 
-            ImageSource::Render(data) => Some(data.to_owned()),
-            ImageSource::Image(data) => cx.use_asset::<ImageDecoder>(data)?.log_err(),
+            ImageSource::Render(data) => Some(Ok(data.to_owned())),
+            ImageSource::Image(data) => cx
+                .use_asset::<ImageDecoder>(data)
+                .map(|result| result.map_err(ImageCacheError::Other)),
         }
     }
 
@@ -469,6 +528,9 @@ pub enum ImageCacheError {
     /// An error that occurred while processing an SVG.
     #[error("svg error: {0}")]
     Usvg(Arc<usvg::Error>),
+    /// An error that occurred while decoding in-memory image data.
+    #[error("{0}")]
+    Other(Arc<anyhow::Error>),
 }
 
 impl From<std::io::Error> for ImageCacheError {
@@ -488,3 +550,54 @@ impl From<usvg::Error> for ImageCacheError {
         Self::Usvg(Arc::new(error))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+    use image::{Frame, ImageBuffer};
+    use smallvec::SmallVec;
+
+    use crate::{
+        self as gpui, div, img, point, px, size, ImageSource, IntoElement, RenderImage, Styled,
+        TestAppContext, VisualTestContext,
+    };
+
+    #[gpui::test]
+    fn test_img_placeholders(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let placeholders = Rc::new(RefCell::new(Vec::new()));
+
+        let draw = |source: ImageSource, cx: &mut VisualTestContext| {
+            placeholders.borrow_mut().clear();
+            cx.draw(point(px(0.), px(0.)), size(px(100.), px(100.)), |_| {
+                img(source)
+                    .size(px(10.))
+                    .with_loading({
+                        let placeholders = placeholders.clone();
+                        move || {
+                            placeholders.borrow_mut().push("loading");
+                            div().into_any_element()
+                        }
+                    })
+                    .with_fallback({
+                        let placeholders = placeholders.clone();
+                        move || {
+                            placeholders.borrow_mut().push("fallback");
+                            div().into_any_element()
+                        }
+                    })
+            });
+            placeholders.borrow().clone()
+        };
+
+        // The loading placeholder is shown until the asset has been fetched, and the
+        // fallback is shown once loading fails.
+        assert_eq!(draw("missing.png".into(), cx), ["loading"]);
+        cx.run_until_parked();
+        assert_eq!(draw("missing.png".into(), cx), ["fallback"]);
+
+        let image = RenderImage::new(SmallVec::from_elem(Frame::new(ImageBuffer::new(1, 1)), 1));
+        assert!(draw(Arc::new(image).into(), cx).is_empty());
+    }
+}
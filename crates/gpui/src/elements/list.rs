@@ -8,9 +8,9 @@
 //! If all of your elements are the same height, see [`UniformList`] for a simpler API
 
 use crate::{
-    point, px, size, AnyElement, AvailableSpace, Bounds, ContentMask, DispatchPhase, Edges,
-    Element, FocusHandle, GlobalElementId, Hitbox, IntoElement, Pixels, Point, ScrollWheelEvent,
-    Size, Style, StyleRefinement, Styled, WindowContext,
+    fill, point, px, size, AnyElement, AvailableSpace, Bounds, ContentMask, DispatchPhase, Edges,
+    Element, FocusHandle, GlobalElementId, Hitbox, Hsla, IntoElement, Pixels, Point,
+    ScrollWheelEvent, Size, Style, StyleRefinement, Styled, WindowContext,
 };
 use collections::VecDeque;
 use refineable::Refineable as _;
@@ -24,6 +24,7 @@ pub fn list(state: ListState) -> List {
         state,
         style: StyleRefinement::default(),
         sizing_behavior: ListSizingBehavior::default(),
+        selected_background: None,
     }
 }
 
@@ -32,6 +33,7 @@ pub struct List {
     state: ListState,
     style: StyleRefinement,
     sizing_behavior: ListSizingBehavior,
+    selected_background: Option<Hsla>,
 }
 
 impl List {
@@ -40,6 +42,12 @@ impl List {
         self.sizing_behavior = behavior;
         self
     }
+
+    /// Paint the given background behind the item selected via [`ListState::select`].
+    pub fn selected_background(mut self, color: impl Into<Hsla>) -> Self {
+        self.selected_background = Some(color.into());
+        self
+    }
 }
 
 /// The list state that views must hold on behalf of the list element.
@@ -55,6 +63,7 @@ struct StateInner {
     alignment: ListAlignment,
     overdraw: Pixels,
     reset: bool,
+    selected_ix: Option<usize>,
     #[allow(clippy::type_complexity)]
     scroll_handler: Option<Box<dyn FnMut(&ListScrollEvent, &mut WindowContext)>>,
 }
@@ -198,6 +207,7 @@ impl ListState {
             overdraw,
             scroll_handler: None,
             reset: false,
+            selected_ix: None,
         })));
         this.splice(0..0, item_count);
         this
@@ -206,6 +216,7 @@ impl ListState {
     /// Reset this instantiation of the list state.
     ///
     /// Note that this will cause scroll events to be dropped until the next paint.
+    /// The selected item, if any, is preserved and clamped to the new item count.
     pub fn reset(&self, element_count: usize) {
         let old_count = {
             let state = &mut *self.0.borrow_mut();
@@ -255,6 +266,11 @@ impl ListState {
         drop(old_items);
         state.items = new_items;
 
+        let item_count = state.items.summary().count;
+        state.selected_ix = state
+            .selected_ix
+            .and_then(|ix| item_count.checked_sub(1).map(|last_ix| ix.min(last_ix)));
+
         if let Some(ListOffset {
             item_ix,
             offset_in_item,
@@ -326,6 +342,44 @@ impl ListState {
         state.logical_scroll_top = Some(scroll_top);
     }
 
+    /// The index of the selected item, if any.
+    pub fn selected(&self) -> Option<usize> {
+        self.0.borrow().selected_ix
+    }
+
+    /// Select the given item, clamping it to the last item in the list,
+    /// and scroll it into view.
+    pub fn select(&self, ix: Option<usize>) {
+        let selected_ix = {
+            let state = &mut *self.0.borrow_mut();
+            let item_count = state.items.summary().count;
+            state.selected_ix =
+                ix.and_then(|ix| item_count.checked_sub(1).map(|last_ix| ix.min(last_ix)));
+            state.selected_ix
+        };
+
+        if let Some(ix) = selected_ix {
+            self.scroll_to_reveal_item(ix);
+        }
+    }
+
+    /// Select the item after the selected one, stopping at the last item.
+    /// Selects the first item if nothing is selected.
+    pub fn select_next(&self) {
+        let ix = self.selected().map_or(0, |ix| ix + 1);
+        self.select(Some(ix));
+    }
+
+    /// Select the item before the selected one, stopping at the first item.
+    /// Selects the last item if nothing is selected.
+    pub fn select_prev(&self) {
+        let ix = match self.selected() {
+            Some(ix) => ix.saturating_sub(1),
+            None => self.item_count().saturating_sub(1),
+        };
+        self.select(Some(ix));
+    }
+
     /// Get the bounds for the given item in window coordinates, if it's
     /// been rendered.
     pub fn bounds_for_item(&self, ix: usize) -> Option<Bounds<Pixels>> {
@@ -400,7 +454,7 @@ impl StateInner {
         }
 
         if self.scroll_handler.is_some() {
-            let visible_range = self.visible_range(height, scroll_top);
+            let visible_range = self.visible_range(height, &self.logical_scroll_top());
             self.scroll_handler.as_mut().unwrap()(
                 &ListScrollEvent {
                     visible_range,
@@ -830,6 +884,16 @@ impl Element for List {
         cx: &mut crate::WindowContext,
     ) {
         cx.with_content_mask(Some(ContentMask { bounds }), |cx| {
+            if let Some(background) = self.selected_background {
+                if let Some(item_bounds) = self
+                    .state
+                    .selected()
+                    .and_then(|ix| self.state.bounds_for_item(ix))
+                {
+                    cx.paint_quad(fill(item_bounds, background));
+                }
+            }
+
             for item in &mut prepaint.layout.item_layouts {
                 item.element.paint(cx);
             }
@@ -981,4 +1045,151 @@ mod test {
         assert_eq!(state.logical_scroll_top().item_ix, 0);
         assert_eq!(state.logical_scroll_top().offset_in_item, px(0.));
     }
+
+    #[gpui::test]
+    fn test_selection_clamps_to_items(cx: &mut TestAppContext) {
+        use crate::{div, list, point, px, size, Bounds, Element, ListState, Styled};
+
+        let cx = cx.add_empty_window();
+
+        let state = ListState::new(3, crate::ListAlignment::Top, px(10.), |_, _| {
+            div().h(px(10.)).w_full().into_any()
+        });
+        assert_eq!(state.selected(), None);
+
+        cx.draw(point(px(0.), px(0.)), size(px(100.), px(20.)), |_| {
+            list(state.clone()).w_full().h_full()
+        });
+
+        state.select_next();
+        assert_eq!(state.selected(), Some(0));
+        state.select_next();
+        state.select_next();
+        state.select_next();
+        assert_eq!(state.selected(), Some(2));
+
+        // The selected item is scrolled into view.
+        assert_eq!(
+            state.bounds_for_item(2),
+            Some(Bounds::from_corners(
+                point(px(0.), px(10.)),
+                point(px(100.), px(20.))
+            ))
+        );
+
+        state.select_prev();
+        state.select_prev();
+        state.select_prev();
+        assert_eq!(state.selected(), Some(0));
+        assert_eq!(state.logical_scroll_top().item_ix, 0);
+
+        // Resetting preserves the selection, clamping it to the new item count.
+        state.select(Some(2));
+        state.reset(5);
+        assert_eq!(state.selected(), Some(2));
+        state.reset(2);
+        assert_eq!(state.selected(), Some(1));
+        state.reset(0);
+        assert_eq!(state.selected(), None);
+    }
+
+    #[gpui::test]
+    fn test_layout_after_window_resize(cx: &mut TestAppContext) {
+        use crate::{
+            div, list, px, size, IntoElement, ListState, ParentElement as _, Render, Styled,
+            ViewContext,
+        };
+        use std::{cell::RefCell, rc::Rc};
+
+        struct TestList(ListState);
+
+        impl Render for TestList {
+            fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+                div().size_full().child(list(self.0.clone()).size_full())
+            }
+        }
+
+        let rendered_items = Rc::new(RefCell::new(Vec::new()));
+        let state = ListState::new(10, crate::ListAlignment::Top, px(0.), {
+            let rendered_items = rendered_items.clone();
+            move |ix, _| {
+                rendered_items.borrow_mut().push(ix);
+                div().h(px(10.)).w_full().into_any()
+            }
+        });
+
+        let (_, cx) = cx.add_window_view(|_| TestList(state.clone()));
+        cx.simulate_resize(size(px(100.), px(20.)));
+        cx.run_until_parked();
+        rendered_items.borrow_mut().clear();
+
+        // Growing the window lays the list out again, revealing more items.
+        cx.simulate_resize(size(px(100.), px(40.)));
+        cx.run_until_parked();
+        assert_eq!(rendered_items.borrow().iter().max(), Some(&3));
+        assert!(state.bounds_for_item(3).is_some());
+        assert!(state.bounds_for_item(4).is_none());
+    }
+
+    #[gpui::test]
+    fn test_variable_height_items(cx: &mut TestAppContext) {
+        use crate::{div, list, point, px, size, Bounds, Element, ListState, Styled};
+        use std::{cell::RefCell, rc::Rc};
+
+        let cx = cx.add_empty_window();
+
+        // Items are 10, 20, 30, 40 and 50 pixels tall, for a total height of 150 pixels.
+        let state = ListState::new(5, crate::ListAlignment::Top, px(1000.), |ix, _| {
+            div().h(px(10. * (ix + 1) as f32)).w_full().into_any()
+        });
+        let visible_ranges = Rc::new(RefCell::new(Vec::new()));
+        state.set_scroll_handler({
+            let visible_ranges = visible_ranges.clone();
+            move |event, _| {
+                visible_ranges
+                    .borrow_mut()
+                    .push(event.visible_range.clone())
+            }
+        });
+
+        cx.draw(point(px(0.), px(0.)), size(px(100.), px(60.)), |_| {
+            list(state.clone()).w_full().h_full()
+        });
+        assert_eq!(
+            state.bounds_for_item(1),
+            Some(Bounds::from_corners(
+                point(px(0.), px(10.)),
+                point(px(100.), px(30.))
+            ))
+        );
+        assert_eq!(
+            state.bounds_for_item(4),
+            Some(Bounds::from_corners(
+                point(px(0.), px(100.)),
+                point(px(100.), px(150.))
+            ))
+        );
+
+        // Scrolling 45 pixels down reveals the bottom half of the third item through the fifth.
+        cx.simulate_event(ScrollWheelEvent {
+            position: point(px(1.), px(1.)),
+            delta: ScrollDelta::Pixels(point(px(0.), px(-45.))),
+            ..Default::default()
+        });
+        assert_eq!(visible_ranges.take(), [2..5]);
+        assert_eq!(state.logical_scroll_top().item_ix, 2);
+        assert_eq!(state.logical_scroll_top().offset_in_item, px(15.));
+
+        // Scrolling is clamped to the total height of the measured items.
+        cx.draw(point(px(0.), px(0.)), size(px(100.), px(60.)), |_| {
+            list(state.clone()).w_full().h_full()
+        });
+        cx.simulate_event(ScrollWheelEvent {
+            position: point(px(1.), px(1.)),
+            delta: ScrollDelta::Pixels(point(px(0.), px(-500.))),
+            ..Default::default()
+        });
+        assert_eq!(state.logical_scroll_top().item_ix, 3);
+        assert_eq!(state.logical_scroll_top().offset_in_item, px(30.));
+    }
 }
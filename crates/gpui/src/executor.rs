@@ -1,4 +1,5 @@
 use crate::{AppContext, PlatformDispatcher};
+use anyhow::anyhow;
 use futures::channel::mpsc;
 use smol::prelude::*;
 use std::{
@@ -69,6 +70,22 @@ impl<T> Task<T> {
     }
 }
 
+impl<T: 'static> Task<T> {
+    /// Returns a task that resolves to the output of this one, or to `None` if it
+    /// doesn't finish within the given duration, in which case this task is cancelled.
+    pub fn timeout(self, duration: Duration, cx: &AppContext) -> Task<Option<T>> {
+        let timer = cx.background_executor().timer(duration);
+        cx.foreground_executor().spawn(async move {
+            let output = async move { Some(self.await) };
+            let expired = async move {
+                timer.await;
+                None
+            };
+            output.or(expired).await
+        })
+    }
+}
+
 impl<E, T> Task<Result<T, E>>
 where
     T: 'static,
@@ -302,6 +319,39 @@ impl BackgroundExecutor {
     /// Scoped lets you start a number of tasks and waits
     /// for all of them to complete before returning.
     pub async fn scoped<'scope, F>(&self, scheduler: F)
+    where
+        F: FnOnce(&mut Scope<'scope>),
+    {
+        for error in self.run_scoped(scheduler).await {
+            log::error!("{error:?}");
+        }
+    }
+
+    /// Like [`Self::scoped`], but for tasks started with [`Scope::try_spawn`] that can fail.
+    /// Every task is run to completion, even if some of them fail, and then any errors
+    /// they returned are combined into one.
+    pub async fn try_scoped<'scope, F>(&self, scheduler: F) -> anyhow::Result<()>
+    where
+        F: FnOnce(&mut Scope<'scope>),
+    {
+        let mut errors = self.run_scoped(scheduler).await;
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.pop().unwrap()),
+            error_count => {
+                let messages = errors
+                    .iter()
+                    .map(|error| format!("{error:#}"))
+                    .collect::<Vec<_>>();
+                Err(anyhow!(
+                    "{error_count} scoped tasks failed: {}",
+                    messages.join("; ")
+                ))
+            }
+        }
+    }
+
+    async fn run_scoped<'scope, F>(&self, scheduler: F) -> Vec<anyhow::Error>
     where
         F: FnOnce(&mut Scope<'scope>),
     {
@@ -311,9 +361,13 @@ impl BackgroundExecutor {
             .into_iter()
             .map(|f| self.spawn(f))
             .collect::<Vec<_>>();
+        let mut errors = Vec::new();
         for task in spawned {
-            task.await;
+            if let Err(error) = task.await {
+                errors.push(error);
+            }
         }
+        errors
     }
 
     /// Get the current time.
@@ -459,7 +513,7 @@ impl ForegroundExecutor {
 /// Scope manages a set of tasks that are enqueued and waited on together. See [`BackgroundExecutor::scoped`].
 pub struct Scope<'a> {
     executor: BackgroundExecutor,
-    futures: Vec<Pin<Box<dyn Future<Output = ()> + Send + 'static>>>,
+    futures: Vec<Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send + 'static>>>,
     tx: Option<mpsc::Sender<()>>,
     rx: mpsc::Receiver<()>,
     lifetime: PhantomData<&'a ()>,
@@ -486,6 +540,18 @@ impl<'a> Scope<'a> {
     pub fn spawn<F>(&mut self, f: F)
     where
         F: Future<Output = ()> + Send + 'a,
+    {
+        self.try_spawn(async move {
+            f.await;
+            Ok(())
+        });
+    }
+
+    /// Spawn a future that can fail into this scope. Its error is returned from
+    /// [`BackgroundExecutor::try_scoped`], or logged when using [`BackgroundExecutor::scoped`].
+    pub fn try_spawn<F>(&mut self, f: F)
+    where
+        F: Future<Output = anyhow::Result<()>> + Send + 'a,
     {
         let tx = self.tx.clone().unwrap();
 
@@ -493,11 +559,12 @@ impl<'a> Scope<'a> {
         // dropping this `Scope` blocks until all of the futures have resolved.
         let f = unsafe {
             mem::transmute::<
-                Pin<Box<dyn Future<Output = ()> + Send + 'a>>,
-                Pin<Box<dyn Future<Output = ()> + Send + 'static>>,
+                Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send + 'a>>,
+                Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send + 'static>>,
             >(Box::pin(async move {
-                f.await;
+                let result = f.await;
                 drop(tx);
+                result
             }))
         };
         self.futures.push(f);
@@ -513,3 +580,88 @@ impl<'a> Drop for Scope<'a> {
         self.executor.block(self.rx.next());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as gpui, TestAppContext};
+    use std::sync::atomic::AtomicBool;
+
+    #[gpui::test]
+    async fn test_task_timeout(cx: &mut TestAppContext) {
+        let executor = cx.executor();
+        let spawn_sleeper = |duration: Duration, finished: Arc<AtomicBool>| {
+            let executor = executor.clone();
+            executor.spawn({
+                let executor = executor.clone();
+                async move {
+                    executor.timer(duration).await;
+                    finished.store(true, SeqCst);
+                    duration
+                }
+            })
+        };
+
+        // A task that finishes before the deadline resolves to its output.
+        let finished = Arc::new(AtomicBool::new(false));
+        let task = spawn_sleeper(Duration::from_secs(1), finished.clone());
+        let task = cx.update(|cx| task.timeout(Duration::from_secs(2), cx));
+        executor.advance_clock(Duration::from_secs(1));
+        executor.run_until_parked();
+        assert_eq!(task.await, Some(Duration::from_secs(1)));
+        assert!(finished.load(SeqCst));
+
+        // A task that misses the deadline resolves to `None` and is cancelled.
+        let finished = Arc::new(AtomicBool::new(false));
+        let task = spawn_sleeper(Duration::from_secs(3), finished.clone());
+        let task = cx.update(|cx| task.timeout(Duration::from_secs(2), cx));
+        executor.advance_clock(Duration::from_secs(2));
+        executor.run_until_parked();
+        assert_eq!(task.await, None);
+        executor.advance_clock(Duration::from_secs(2));
+        executor.run_until_parked();
+        assert!(!finished.load(SeqCst));
+    }
+
+    #[gpui::test]
+    async fn test_try_scoped(cx: &mut TestAppContext) {
+        let executor = cx.executor();
+        let completed = AtomicUsize::new(0);
+        let result = executor
+            .try_scoped(|scope| {
+                for i in 0..4 {
+                    let completed = &completed;
+                    scope.try_spawn(async move {
+                        completed.fetch_add(1, SeqCst);
+                        if i == 2 {
+                            Err(anyhow!("task {i} failed"))
+                        } else {
+                            Ok(())
+                        }
+                    });
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap_err().to_string(), "task 2 failed");
+        assert_eq!(completed.load(SeqCst), 4);
+
+        let result = executor
+            .try_scoped(|scope| {
+                scope.spawn(async {});
+                scope.try_spawn(async { Err(anyhow!("first")) });
+                scope.try_spawn(async { Err(anyhow!("second")) });
+            })
+            .await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "2 scoped tasks failed: first; second"
+        );
+
+        let result = executor
+            .try_scoped(|scope| {
+                scope.try_spawn(async { Ok(()) });
+            })
+            .await;
+        assert!(result.is_ok());
+    }
+}
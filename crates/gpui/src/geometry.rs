@@ -845,6 +845,38 @@ where
             && my_lower_right.y > other.origin.y
     }
 
+    /// Calculates the intersection of two `Bounds`, returning `None` if they don't intersect.
+    ///
+    /// Unlike [`Bounds::intersect`], this distinguishes bounds that don't overlap at all
+    /// from bounds that overlap in an empty region, which is useful for clipping and culling.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zed::{Bounds, Point, Size};
+    /// let bounds1 = Bounds {
+    ///     origin: Point { x: 0, y: 0 },
+    ///     size: Size { width: 10, height: 10 },
+    /// };
+    /// let bounds2 = Bounds {
+    ///     origin: Point { x: 5, y: 5 },
+    ///     size: Size { width: 10, height: 10 },
+    /// };
+    /// let bounds3 = Bounds {
+    ///     origin: Point { x: 20, y: 20 },
+    ///     size: Size { width: 10, height: 10 },
+    /// };
+    ///
+    /// assert_eq!(bounds1.checked_intersect(&bounds2), Some(Bounds {
+    ///     origin: Point { x: 5, y: 5 },
+    ///     size: Size { width: 5, height: 5 },
+    /// }));
+    /// assert_eq!(bounds1.checked_intersect(&bounds3), None);
+    /// ```
+    pub fn checked_intersect(&self, other: &Self) -> Option<Self> {
+        self.intersects(other).then(|| self.intersect(other))
+    }
+
     /// Dilates the bounds by a specified amount in all directions.
     ///
     /// This method expands the bounds by the given `amount`, increasing the size
@@ -3150,4 +3182,49 @@ mod tests {
         // Test Case 3: Bounds intersecting with themselves
         assert!(bounds1.intersects(&bounds1));
     }
+
+    #[test]
+    fn test_bounds_intersection_and_union() {
+        let bounds = |x: f32, y: f32, width: f32, height: f32| Bounds {
+            origin: Point { x, y },
+            size: Size { width, height },
+        };
+        let outer = bounds(0., 0., 10., 10.);
+
+        // Overlapping bounds
+        let overlapping = bounds(5., 5., 10., 10.);
+        assert_eq!(
+            outer.checked_intersect(&overlapping),
+            Some(bounds(5., 5., 5., 5.))
+        );
+        assert_eq!(outer.union(&overlapping), bounds(0., 0., 15., 15.));
+
+        // Disjoint bounds, including ones that only share an edge
+        let disjoint = bounds(20., 20., 5., 5.);
+        assert_eq!(outer.checked_intersect(&disjoint), None);
+        assert_eq!(outer.union(&disjoint), bounds(0., 0., 25., 25.));
+        let adjacent = bounds(10., 0., 5., 10.);
+        assert_eq!(outer.checked_intersect(&adjacent), None);
+
+        // Containing bounds
+        let inner = bounds(2., 3., 4., 5.);
+        assert_eq!(outer.checked_intersect(&inner), Some(inner));
+        assert_eq!(inner.checked_intersect(&outer), Some(inner));
+        assert_eq!(outer.union(&inner), outer);
+
+        // Zero-area bounds
+        let line = bounds(5., 0., 0., 10.);
+        assert_eq!(outer.checked_intersect(&line), Some(line));
+        assert_eq!(outer.union(&line), outer);
+        let point_bounds = bounds(20., 20., 0., 0.);
+        assert_eq!(outer.checked_intersect(&point_bounds), None);
+        assert_eq!(outer.union(&point_bounds), bounds(0., 0., 20., 20.));
+
+        // Containment of points, which includes the edges
+        assert!(outer.contains(&Point { x: 5., y: 5. }));
+        assert!(outer.contains(&Point { x: 10., y: 0. }));
+        assert!(!outer.contains(&Point { x: 10.5, y: 5. }));
+        assert!(line.contains(&Point { x: 5., y: 5. }));
+        assert!(!line.contains(&Point { x: 6., y: 5. }));
+    }
 }
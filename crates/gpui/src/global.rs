@@ -73,3 +73,39 @@ impl<T: Global> UpdateGlobal for T {
         cx.set_global(global)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as gpui, Global, TestAppContext};
+    use std::{cell::Cell, rc::Rc};
+
+    #[derive(Debug, PartialEq)]
+    struct Counter(usize);
+
+    impl Global for Counter {}
+
+    #[gpui::test]
+    fn test_global_lifecycle(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            assert!(!cx.has_global::<Counter>());
+            assert_eq!(cx.try_global::<Counter>(), None);
+
+            cx.set_global(Counter(1));
+            assert!(cx.has_global::<Counter>());
+            assert_eq!(cx.try_global::<Counter>(), Some(&Counter(1)));
+        });
+
+        let notifications = Rc::new(Cell::new(0));
+        let _subscription = cx.update(|cx| {
+            let notifications = notifications.clone();
+            cx.observe_global::<Counter>(move |_| notifications.set(notifications.get() + 1))
+        });
+
+        cx.update(|cx| {
+            assert_eq!(cx.remove_global::<Counter>(), Counter(1));
+            assert!(!cx.has_global::<Counter>());
+            assert_eq!(cx.try_global::<Counter>(), None);
+        });
+        assert_eq!(notifications.get(), 1);
+    }
+}
@@ -147,6 +147,18 @@ pub struct ClickEvent {
     pub up: MouseUpEvent,
 }
 
+impl ClickEvent {
+    /// The number of consecutive clicks that produced this event, as reported by the platform.
+    pub fn click_count(&self) -> usize {
+        self.up.click_count
+    }
+
+    /// Returns true if this event completes a double click.
+    pub fn is_double_click(&self) -> bool {
+        self.click_count() == 2
+    }
+}
+
 /// An enum representing the mouse button that was pressed.
 #[derive(Hash, PartialEq, Eq, Copy, Clone, Debug)]
 pub enum MouseButton {
@@ -466,9 +478,14 @@ impl PlatformInput {
 #[cfg(test)]
 mod test {
 
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
     use crate::{
-        self as gpui, div, FocusHandle, InteractiveElement, IntoElement, KeyBinding, Keystroke,
-        ParentElement, Render, TestAppContext, VisualContext,
+        self as gpui, blue, deferred, div, point, px, red, size, Bounds, FocusHandle,
+        InteractiveElement, IntoElement, KeyBinding, Keystroke, Modifiers, MouseButton,
+        MouseDownEvent, MouseUpEvent, ParentElement, Render, ScrollDelta, ScrollWheelEvent,
+        StatefulInteractiveElement, Styled, TestAppContext, VisualContext, VisualTestContext,
+        TOOLTIP_DELAY,
     };
 
     struct TestView {
@@ -535,4 +552,350 @@ mod test {
             })
             .unwrap();
     }
+
+    #[gpui::test]
+    fn test_window_key_handler(cx: &mut TestAppContext) {
+        let window = cx.update(|cx| {
+            cx.open_window(Default::default(), |cx| {
+                cx.new_view(|cx| TestView {
+                    saw_key_down: false,
+                    saw_action: false,
+                    focus_handle: cx.focus_handle(),
+                })
+            })
+            .unwrap()
+        });
+
+        cx.update(|cx| {
+            cx.bind_keys(vec![KeyBinding::new("ctrl-g", TestAction, Some("parent"))]);
+        });
+
+        window
+            .update(cx, |test_view, cx| cx.focus(&test_view.focus_handle))
+            .unwrap();
+
+        let intercepted = Rc::new(RefCell::new(Vec::new()));
+        let subscription = cx.update(|cx| {
+            let intercepted = intercepted.clone();
+            cx.on_window_key(*window, move |event, _| {
+                intercepted.borrow_mut().push(event.keystroke.unparse());
+                event.keystroke.key == "g"
+            })
+        });
+
+        cx.dispatch_keystroke(*window, Keystroke::parse("ctrl-g").unwrap());
+        window
+            .update(cx, |test_view, _| {
+                assert!(!test_view.saw_key_down);
+                assert!(!test_view.saw_action);
+            })
+            .unwrap();
+
+        cx.dispatch_keystroke(*window, Keystroke::parse("a").unwrap());
+        window
+            .update(cx, |test_view, _| {
+                assert!(test_view.saw_key_down);
+                assert!(!test_view.saw_action);
+            })
+            .unwrap();
+        assert_eq!(*intercepted.borrow(), ["ctrl-g", "a"]);
+
+        drop(subscription);
+        cx.dispatch_keystroke(*window, Keystroke::parse("ctrl-g").unwrap());
+        window
+            .update(cx, |test_view, _| assert!(test_view.saw_action))
+            .unwrap();
+        assert_eq!(*intercepted.borrow(), ["ctrl-g", "a"]);
+    }
+
+    #[gpui::test]
+    fn test_scroll_wheel_and_middle_click(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let scroll_deltas = Rc::new(RefCell::new(Vec::new()));
+        let middle_clicks = Rc::new(RefCell::new(Vec::new()));
+
+        cx.draw(point(px(0.), px(0.)), size(px(100.), px(100.)), |_| {
+            let scroll_deltas = scroll_deltas.clone();
+            let middle_clicks = middle_clicks.clone();
+            div()
+                .size_full()
+                .on_scroll_wheel(move |event, _| {
+                    scroll_deltas
+                        .borrow_mut()
+                        .push((event.delta.precise(), event.delta.pixel_delta(px(10.))))
+                })
+                .on_mouse_down(MouseButton::Middle, move |event, _| {
+                    middle_clicks.borrow_mut().push(event.position)
+                })
+        });
+
+        cx.simulate_event(ScrollWheelEvent {
+            position: point(px(10.), px(10.)),
+            delta: ScrollDelta::Pixels(point(px(0.), px(-30.))),
+            ..Default::default()
+        });
+        cx.simulate_event(ScrollWheelEvent {
+            position: point(px(10.), px(10.)),
+            delta: ScrollDelta::Lines(point(1., 2.)),
+            ..Default::default()
+        });
+        // Scrolling outside of the element's bounds isn't dispatched to it.
+        cx.simulate_event(ScrollWheelEvent {
+            position: point(px(150.), px(10.)),
+            delta: ScrollDelta::Lines(point(0., 5.)),
+            ..Default::default()
+        });
+        assert_eq!(
+            *scroll_deltas.borrow(),
+            [
+                (true, point(px(0.), px(-30.))),
+                (false, point(px(10.), px(20.))),
+            ]
+        );
+
+        cx.simulate_mouse_down(
+            point(px(20.), px(30.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_down(
+            point(px(40.), px(50.)),
+            MouseButton::Middle,
+            Modifiers::none(),
+        );
+        assert_eq!(*middle_clicks.borrow(), [point(px(40.), px(50.))]);
+    }
+
+    #[gpui::test]
+    fn test_click_count(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let clicks = Rc::new(RefCell::new(Vec::new()));
+
+        cx.draw(point(px(0.), px(0.)), size(px(100.), px(100.)), |_| {
+            let clicks = clicks.clone();
+            div().id("clickable").size_full().on_click(move |event, _| {
+                clicks
+                    .borrow_mut()
+                    .push((event.click_count(), event.is_double_click()))
+            })
+        });
+
+        let position = point(px(10.), px(10.));
+        for click_count in 1..=2 {
+            cx.simulate_event(MouseDownEvent {
+                position,
+                button: MouseButton::Left,
+                click_count,
+                ..Default::default()
+            });
+            cx.simulate_event(MouseUpEvent {
+                position,
+                button: MouseButton::Left,
+                click_count,
+                ..Default::default()
+            });
+        }
+        assert_eq!(*clicks.borrow(), [(1, false), (2, true)]);
+    }
+
+    #[gpui::test]
+    fn test_double_and_right_clicks(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let events = Rc::new(RefCell::new(Vec::new()));
+
+        cx.draw(point(px(0.), px(0.)), size(px(100.), px(100.)), |_| {
+            let events = events.clone();
+            div()
+                .id("clickable")
+                .size_full()
+                .on_click({
+                    let events = events.clone();
+                    move |_, _| events.borrow_mut().push("click")
+                })
+                .on_double_click({
+                    let events = events.clone();
+                    move |_, _| events.borrow_mut().push("double click")
+                })
+                .on_right_click(move |_, _| events.borrow_mut().push("right click"))
+        });
+
+        let position = point(px(10.), px(10.));
+        let click = |button, click_count, cx: &mut VisualTestContext| {
+            cx.simulate_event(MouseDownEvent {
+                position,
+                button,
+                click_count,
+                ..Default::default()
+            });
+            cx.simulate_event(MouseUpEvent {
+                position,
+                button,
+                click_count,
+                ..Default::default()
+            });
+        };
+
+        click(MouseButton::Left, 1, cx);
+        click(MouseButton::Left, 2, cx);
+        assert_eq!(*events.borrow(), ["click", "double click"]);
+
+        events.borrow_mut().clear();
+        click(MouseButton::Right, 1, cx);
+        assert_eq!(*events.borrow(), ["right click"]);
+
+        // Releasing the right button outside of the element doesn't count as a click.
+        events.borrow_mut().clear();
+        cx.simulate_event(MouseDownEvent {
+            position,
+            button: MouseButton::Right,
+            click_count: 1,
+            ..Default::default()
+        });
+        cx.simulate_event(MouseUpEvent {
+            position: point(px(150.), px(10.)),
+            button: MouseButton::Right,
+            click_count: 1,
+            ..Default::default()
+        });
+        assert!(events.borrow().is_empty());
+    }
+
+    #[gpui::test]
+    fn test_hover_delay(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let hovers = Rc::new(RefCell::new(Vec::new()));
+
+        cx.draw(point(px(0.), px(0.)), size(px(100.), px(100.)), |_| {
+            let hovers = hovers.clone();
+            div()
+                .id("hoverable")
+                .size_full()
+                .hover_delay(Duration::from_millis(500))
+                .on_hover(move |hovered, _| hovers.borrow_mut().push(*hovered))
+        });
+
+        let inside = point(px(10.), px(10.));
+        let outside = point(px(150.), px(10.));
+
+        // Leaving before the delay elapses cancels the hover entirely.
+        cx.simulate_mouse_move(inside, None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(200));
+        cx.run_until_parked();
+        cx.simulate_mouse_move(outside, None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_secs(1));
+        cx.run_until_parked();
+        assert!(hovers.borrow().is_empty());
+
+        cx.simulate_mouse_move(inside, None, Modifiers::none());
+        cx.executor().advance_clock(Duration::from_millis(499));
+        cx.run_until_parked();
+        assert!(hovers.borrow().is_empty());
+        cx.executor().advance_clock(Duration::from_millis(1));
+        cx.run_until_parked();
+        assert_eq!(*hovers.borrow(), [true]);
+
+        cx.simulate_mouse_move(outside, None, Modifiers::none());
+        assert_eq!(*hovers.borrow(), [true, false]);
+    }
+
+    struct TooltipOwner;
+
+    impl Render for TooltipOwner {
+        fn render(&mut self, _: &mut gpui::ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("owner")
+                .size_full()
+                .bg(blue())
+                .tooltip(|cx| cx.new_view(|_| TooltipContent).into())
+        }
+    }
+
+    struct TooltipContent;
+
+    impl Render for TooltipContent {
+        fn render(&mut self, _: &mut gpui::ViewContext<Self>) -> impl IntoElement {
+            div().w(px(150.)).h(px(20.)).bg(red())
+        }
+    }
+
+    #[gpui::test]
+    fn test_tooltip_painted_after_delay(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| TooltipOwner);
+        cx.simulate_resize(size(px(200.), px(100.)));
+        cx.run_until_parked();
+
+        let tooltip_quads = |cx: &mut VisualTestContext| {
+            cx.update(|cx| {
+                let quads = &cx.window.rendered_frame.scene.quads;
+                let owner = quads.iter().find(|quad| quad.background == blue());
+                quads
+                    .iter()
+                    .filter(|quad| quad.background == red())
+                    .map(|quad| {
+                        // Tooltips are drawn above the element that requested them.
+                        assert!(quad.order > owner.unwrap().order);
+                        quad.bounds
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+        let scale_factor = cx.update(|cx| cx.scale_factor());
+
+        cx.simulate_mouse_move(point(px(100.), px(10.)), None, Modifiers::none());
+        cx.executor()
+            .advance_clock(TOOLTIP_DELAY - Duration::from_millis(1));
+        cx.run_until_parked();
+        assert!(tooltip_quads(cx).is_empty());
+
+        cx.executor().advance_clock(Duration::from_millis(1));
+        cx.run_until_parked();
+        // The tooltip doesn't fit on either side of the cursor, so it is clamped to the window.
+        assert_eq!(
+            tooltip_quads(cx),
+            [Bounds::new(point(px(50.), px(11.)), size(px(150.), px(20.))).scale(scale_factor)]
+        );
+
+        cx.simulate_mouse_move(point(px(250.), px(10.)), None, Modifiers::none());
+        assert!(tooltip_quads(cx).is_empty());
+    }
+
+    #[gpui::test]
+    fn test_deferred_overlays_receive_events_first(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let events = Rc::new(RefCell::new(Vec::new()));
+
+        cx.draw(point(px(0.), px(0.)), size(px(100.), px(100.)), |_| {
+            let listener = |name: &'static str| {
+                let events = events.clone();
+                move |_: &MouseDownEvent, _: &mut gpui::WindowContext| {
+                    events.borrow_mut().push(name)
+                }
+            };
+            let overlay = |extent| div().absolute().top_0().left_0().size(px(extent)).occlude();
+
+            // The higher priority overlay is painted on top, even though it comes first.
+            div()
+                .size_full()
+                .on_mouse_down(MouseButton::Left, listener("base"))
+                .child(
+                    deferred(overlay(20.).on_mouse_down(MouseButton::Left, listener("high")))
+                        .with_priority(2),
+                )
+                .child(
+                    deferred(overlay(50.).on_mouse_down(MouseButton::Left, listener("low")))
+                        .with_priority(1),
+                )
+        });
+
+        for (x, expected) in [(10., "high"), (30., "low"), (70., "base")] {
+            events.borrow_mut().clear();
+            cx.simulate_event(MouseDownEvent {
+                position: point(px(x), px(10.)),
+                button: MouseButton::Left,
+                click_count: 1,
+                ..Default::default()
+            });
+            assert_eq!(*events.borrow(), [expected]);
+        }
+    }
 }
@@ -241,4 +241,25 @@ mod tests {
             .0
             .is_empty());
     }
+
+    #[test]
+    fn test_keymap_clear() {
+        let mut keymap = Keymap::default();
+        keymap.add_bindings([KeyBinding::new("ctrl-a", ActionAlpha {}, None)]);
+        let version = keymap.version();
+
+        // Reloading a keymap clears the old bindings before adding the new ones.
+        keymap.clear();
+        keymap.add_bindings([KeyBinding::new("ctrl-b", ActionAlpha {}, None)]);
+        assert!(keymap.version() != version);
+
+        assert!(keymap
+            .bindings_for_input(&[Keystroke::parse("ctrl-a").unwrap()], &[])
+            .0
+            .is_empty());
+        let (bindings, _) = keymap.bindings_for_input(&[Keystroke::parse("ctrl-b").unwrap()], &[]);
+        assert_eq!(bindings.len(), 1);
+        assert!(bindings[0].action().partial_eq(&ActionAlpha {}));
+        assert_eq!(keymap.bindings_for_action(&ActionAlpha {}).count(), 1);
+    }
 }
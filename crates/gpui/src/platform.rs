@@ -52,6 +52,7 @@ use std::{
     sync::Arc,
 };
 use strum::EnumIter;
+use util::ResultExt as _;
 use uuid::Uuid;
 
 pub use app_menu::*;
@@ -214,6 +215,9 @@ pub trait PlatformDisplay: Send + Sync + Debug {
     /// Get the bounds for this display
     fn bounds(&self) -> Bounds<Pixels>;
 
+    /// Get the ratio of physical pixels to logical pixels for this display
+    fn scale_factor(&self) -> f32;
+
     /// Get the default bounds for this display to place a window
     fn default_bounds(&self) -> Bounds<Pixels> {
         let center = self.bounds().center();
@@ -1105,7 +1109,23 @@ pub enum ClipboardEntry {
     Image(Image),
 }
 
+impl ClipboardEntry {
+    /// The MIME type of this entry's representation, e.g. `text/plain` or `image/png`.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ClipboardEntry::String(_) => "text/plain",
+            ClipboardEntry::Image(image) => image.format.mime_type(),
+        }
+    }
+}
+
 impl ClipboardItem {
+    /// Create a new ClipboardItem holding several representations of the same content,
+    /// e.g. the text and a rendered image of it.
+    pub fn new(entries: Vec<ClipboardEntry>) -> Self {
+        Self { entries }
+    }
+
     /// Create a new ClipboardItem::String with no associated metadata
     pub fn new_string(text: String) -> Self {
         Self {
@@ -1170,6 +1190,16 @@ impl ClipboardItem {
         }
     }
 
+    /// Returns the entry matching the earliest of the given MIME types, or `None`
+    /// if the item has no representation in any of them.
+    pub fn best_entry(&self, preferred_mime_types: &[&str]) -> Option<&ClipboardEntry> {
+        preferred_mime_types.iter().find_map(|mime_type| {
+            self.entries
+                .iter()
+                .find(|entry| entry.mime_type() == *mime_type)
+        })
+    }
+
     /// Get the item's entries
     pub fn entries(&self) -> &[ClipboardEntry] {
         &self.entries
@@ -1203,6 +1233,21 @@ pub enum ImageFormat {
     Tiff,
 }
 
+impl ImageFormat {
+    /// The MIME type for this image format, e.g. `image/png`.
+    pub const fn mime_type(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg => "image/jpeg",
+            ImageFormat::Webp => "image/webp",
+            ImageFormat::Gif => "image/gif",
+            ImageFormat::Svg => "image/svg+xml",
+            ImageFormat::Bmp => "image/bmp",
+            ImageFormat::Tiff => "image/tiff",
+        }
+    }
+}
+
 /// An image, with a format and certain bytes
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Image {
@@ -1228,7 +1273,7 @@ impl Image {
 
     /// Use the GPUI `use_asset` API to make this image renderable
     pub fn use_render_image(self: Arc<Self>, cx: &mut WindowContext) -> Option<Arc<RenderImage>> {
-        ImageSource::Image(self).use_data(cx)
+        ImageSource::Image(self).use_data(cx)?.log_err()
     }
 
     /// Convert the clipboard image to an `ImageData` object.
@@ -1346,3 +1391,86 @@ impl ClipboardString {
         hasher.finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as gpui, px, size, EmptyView, TestAppContext, VisualContext};
+
+    #[gpui::test]
+    fn test_clipboard_item_with_multiple_representations(cx: &mut TestAppContext) {
+        let image = Image {
+            format: ImageFormat::Png,
+            bytes: vec![1, 2, 3],
+            id: 1,
+        };
+        let item = ClipboardItem::new(vec![
+            ClipboardEntry::String(ClipboardString::new("avatar".to_string())),
+            ClipboardEntry::Image(image.clone()),
+        ]);
+        cx.write_to_clipboard(item.clone());
+
+        let read = cx.read_from_clipboard().unwrap();
+        assert_eq!(read, item);
+        assert_eq!(read.text(), Some("avatar".to_string()));
+        assert_eq!(
+            read.best_entry(&["image/png", "text/plain"]),
+            Some(&ClipboardEntry::Image(image))
+        );
+        assert_eq!(
+            read.best_entry(&["image/jpeg", "text/plain"]),
+            Some(&ClipboardEntry::String(ClipboardString::new(
+                "avatar".to_string()
+            )))
+        );
+        assert_eq!(read.best_entry(&["image/jpeg"]), None);
+    }
+
+    #[gpui::test]
+    fn test_opening_window_on_display(cx: &mut TestAppContext) {
+        let primary_bounds = Bounds::new(point(px(0.), px(0.)), size(px(1920.), px(1080.)));
+        let secondary_bounds = Bounds::new(point(px(1920.), px(0.)), size(px(2560.), px(1440.)));
+        let display_ids =
+            cx.simulate_displays(vec![(primary_bounds, 1.0), (secondary_bounds, 2.0)]);
+
+        cx.update(|cx| {
+            let displays = cx.displays();
+            assert_eq!(displays.len(), 2);
+            assert_eq!(displays[0].bounds(), primary_bounds);
+            assert_eq!(displays[0].scale_factor(), 1.0);
+            assert_eq!(displays[1].bounds(), secondary_bounds);
+            assert_eq!(displays[1].scale_factor(), 2.0);
+        });
+
+        let open_window = |display_id, cx: &mut TestAppContext| {
+            cx.update(|cx| {
+                cx.open_window(
+                    WindowOptions {
+                        display_id: Some(display_id),
+                        ..Default::default()
+                    },
+                    |cx| cx.new_view(|_| EmptyView),
+                )
+                .unwrap()
+            })
+        };
+
+        let window = open_window(display_ids[1], cx);
+        window
+            .update(cx, |_, cx| {
+                assert_eq!(cx.display().unwrap().id(), display_ids[1]);
+                assert_eq!(cx.scale_factor(), 2.0);
+            })
+            .unwrap();
+
+        // When the requested display is gone, the window opens on the primary display.
+        cx.simulate_displays(vec![(primary_bounds, 1.0)]);
+        let window = open_window(display_ids[1], cx);
+        window
+            .update(cx, |_, cx| {
+                assert_eq!(cx.display().unwrap().id(), display_ids[0]);
+                assert_eq!(cx.scale_factor(), 1.0);
+            })
+            .unwrap();
+    }
+}
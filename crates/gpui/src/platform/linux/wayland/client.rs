@@ -592,6 +592,7 @@ impl LinuxClient for WaylandClient {
                     id: id.clone(),
                     name: output.name.clone(),
                     bounds: output.bounds.to_pixels(output.scale as f32),
+                    scale_factor: output.scale as f32,
                 }) as Rc<dyn PlatformDisplay>
             })
             .collect()
@@ -608,6 +609,7 @@ impl LinuxClient for WaylandClient {
                         id: object_id.clone(),
                         name: output.name.clone(),
                         bounds: output.bounds.to_pixels(output.scale as f32),
+                        scale_factor: output.scale as f32,
                     }) as Rc<dyn PlatformDisplay>
                 })
             })
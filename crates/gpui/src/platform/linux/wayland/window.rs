@@ -800,6 +800,7 @@ impl PlatformWindow for WaylandWindow {
                 id: id.clone(),
                 name: display.name.clone(),
                 bounds: display.bounds.to_pixels(state.scale),
+                scale_factor: display.scale as f32,
             }) as Rc<dyn PlatformDisplay>
         })
     }
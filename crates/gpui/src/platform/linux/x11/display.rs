@@ -8,6 +8,7 @@ use crate::{px, Bounds, DisplayId, Pixels, PlatformDisplay, Size};
 pub(crate) struct X11Display {
     x_screen_index: usize,
     bounds: Bounds<Pixels>,
+    scale_factor: f32,
     uuid: Uuid,
 }
 
@@ -27,6 +28,7 @@ impl X11Display {
                     height: px(screen.height_in_pixels as f32 / scale_factor),
                 },
             },
+            scale_factor,
             uuid: Uuid::from_bytes([0; 16]),
         })
     }
@@ -44,4 +46,8 @@ impl PlatformDisplay for X11Display {
     fn bounds(&self) -> Bounds<Pixels> {
         self.bounds
     }
+
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
 }
@@ -6,7 +6,9 @@ use cocoa::{
     foundation::{NSDictionary, NSString},
 };
 use core_foundation::uuid::{CFUUIDGetUUIDBytes, CFUUIDRef};
-use core_graphics::display::{CGDirectDisplayID, CGDisplayBounds, CGGetActiveDisplayList};
+use core_graphics::display::{
+    CGDirectDisplayID, CGDisplay, CGDisplayBounds, CGGetActiveDisplayList,
+};
 use objc::{msg_send, sel, sel_impl};
 use uuid::Uuid;
 
@@ -114,4 +116,12 @@ impl PlatformDisplay for MacDisplay {
             }
         }
     }
+
+    fn scale_factor(&self) -> f32 {
+        // The current display mode reports both its size in points and in backing pixels.
+        CGDisplay::new(self.0)
+            .display_mode()
+            .filter(|mode| mode.width() > 0)
+            .map_or(1., |mode| mode.pixel_width() as f32 / mode.width() as f32)
+    }
 }
@@ -573,9 +573,12 @@ impl Platform for MacPlatform {
 
     fn open_url(&self, url: &str) {
         unsafe {
-            let url = NSURL::alloc(nil)
-                .initWithString_(ns_string(url))
-                .autorelease();
+            let ns_url = NSURL::alloc(nil).initWithString_(ns_string(url));
+            if ns_url == nil {
+                log::error!("not opening invalid url {url:?}");
+                return;
+            }
+            let url = ns_url.autorelease();
             let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
             msg_send![workspace, openURL: url]
         }
@@ -906,18 +909,21 @@ impl Platform for MacPlatform {
                 }
             } else {
                 let mut any_images = false;
+                let mut images = Vec::new();
                 let attributed_string = {
                     let mut buf = NSMutableAttributedString::alloc(nil)
                         // TODO can we skip this? Or at least part of it?
                         .init_attributed_string(NSString::alloc(nil).init_str(""));
 
                     for entry in item.entries {
-                        if let ClipboardEntry::String(ClipboardString { text, metadata: _ }) = entry
-                        {
-                            let to_append = NSAttributedString::alloc(nil)
-                                .init_attributed_string(NSString::alloc(nil).init_str(&text));
+                        match entry {
+                            ClipboardEntry::String(ClipboardString { text, metadata: _ }) => {
+                                let to_append = NSAttributedString::alloc(nil)
+                                    .init_attributed_string(NSString::alloc(nil).init_str(&text));
 
-                            buf.appendAttributedString_(to_append);
+                                buf.appendAttributedString_(to_append);
+                            }
+                            ClipboardEntry::Image(image) => images.push(image),
                         }
                     }
 
@@ -927,6 +933,19 @@ impl Platform for MacPlatform {
                 let state = self.0.lock();
                 state.pasteboard.clearContents();
 
+                // Each image is also written under its own pasteboard type, so that readers
+                // can pick whichever representation they understand best.
+                for image in images {
+                    let bytes = NSData::dataWithBytes_length_(
+                        nil,
+                        image.bytes.as_ptr() as *const c_void,
+                        image.bytes.len() as u64,
+                    );
+                    state
+                        .pasteboard
+                        .setData_forType(bytes, Into::<UTType>::into(image.format).inner_mut());
+                }
+
                 // Only set rich text clipboard types if we actually have 1+ images to include.
                 if any_images {
                     let rtfd_data = attributed_string.RTFDFromRange_documentAttributes_(
@@ -961,8 +980,9 @@ impl Platform for MacPlatform {
     fn read_from_clipboard(&self) -> Option<ClipboardItem> {
         let state = self.0.lock();
         let pasteboard = state.pasteboard;
+        let mut entries = Vec::new();
 
-        // First, see if it's a string.
+        // First, see if there's a string.
         unsafe {
             let types: id = pasteboard.types();
             let string_type: id = ns_string("public.utf8-plain-text");
@@ -974,25 +994,33 @@ impl Platform for MacPlatform {
                 } else if data.bytes().is_null() {
                     // https://developer.apple.com/documentation/foundation/nsdata/1410616-bytes?language=objc
                     // "If the length of the NSData object is 0, this property returns nil."
-                    return Some(self.read_string_from_clipboard(&state, &[]));
+                    entries.extend(self.read_string_from_clipboard(&state, &[]).into_entries());
                 } else {
                     let bytes =
                         slice::from_raw_parts(data.bytes() as *mut u8, data.length() as usize);
 
-                    return Some(self.read_string_from_clipboard(&state, bytes));
+                    entries.extend(
+                        self.read_string_from_clipboard(&state, bytes)
+                            .into_entries(),
+                    );
                 }
             }
 
-            // If it wasn't a string, try the various supported image types.
+            // Then add the most preferred of the supported image types, if any.
             for format in ImageFormat::iter() {
-                if let Some(item) = try_clipboard_image(pasteboard, format) {
-                    return Some(item);
+                if let Some(image) = try_clipboard_image(pasteboard, format) {
+                    entries.push(ClipboardEntry::Image(image));
+                    break;
                 }
             }
         }
 
-        // If it wasn't a string or a supported image type, give up.
-        None
+        // If there was no string or supported image type, give up.
+        if entries.is_empty() {
+            None
+        } else {
+            Some(ClipboardItem { entries })
+        }
     }
 
     fn write_credentials(&self, url: &str, username: &str, password: &[u8]) -> Task<Result<()>> {
@@ -1185,7 +1213,7 @@ impl MacPlatform {
     }
 }
 
-fn try_clipboard_image(pasteboard: id, format: ImageFormat) -> Option<ClipboardItem> {
+fn try_clipboard_image(pasteboard: id, format: ImageFormat) -> Option<Image> {
     let mut ut_type: UTType = format.into();
 
     unsafe {
@@ -1201,9 +1229,7 @@ fn try_clipboard_image(pasteboard: id, format: ImageFormat) -> Option<ClipboardI
                 ));
                 let id = hash(&bytes);
 
-                Some(ClipboardItem {
-                    entries: vec![ClipboardEntry::Image(Image { format, bytes, id })],
-                })
+                Some(Image { format, bytes, id })
             }
         } else {
             None
@@ -1491,6 +1517,19 @@ mod tests {
             platform.read_from_clipboard(),
             Some(ClipboardItem::new_string(text_from_other_app.to_string()))
         );
+
+        let bytes = vec![1, 2, 3];
+        let image = Image {
+            format: ImageFormat::Png,
+            id: hash(&bytes),
+            bytes,
+        };
+        let item = ClipboardItem::new(vec![
+            ClipboardEntry::String(ClipboardString::new("avatar".to_string())),
+            ClipboardEntry::Image(image.clone()),
+        ]);
+        platform.write_to_clipboard(item.clone());
+        assert_eq!(platform.read_from_clipboard(), Some(item));
     }
 
     fn build_platform() -> MacPlatform {
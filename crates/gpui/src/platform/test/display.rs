@@ -6,14 +6,24 @@ pub(crate) struct TestDisplay {
     id: DisplayId,
     uuid: uuid::Uuid,
     bounds: Bounds<Pixels>,
+    scale_factor: f32,
 }
 
 impl TestDisplay {
     pub fn new() -> Self {
+        Self::with_bounds(
+            DisplayId(1),
+            Bounds::from_corners(Point::default(), Point::new(px(1920.), px(1080.))),
+            2.0,
+        )
+    }
+
+    pub fn with_bounds(id: DisplayId, bounds: Bounds<Pixels>, scale_factor: f32) -> Self {
         TestDisplay {
-            id: DisplayId(1),
+            id,
             uuid: uuid::Uuid::new_v4(),
-            bounds: Bounds::from_corners(Point::default(), Point::new(px(1920.), px(1080.))),
+            bounds,
+            scale_factor,
         }
     }
 }
@@ -30,4 +40,8 @@ impl PlatformDisplay for TestDisplay {
     fn bounds(&self) -> crate::Bounds<crate::Pixels> {
         self.bounds
     }
+
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
 }
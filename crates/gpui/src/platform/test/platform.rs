@@ -3,7 +3,7 @@ use crate::{
     Platform, PlatformDisplay, PlatformTextSystem, Task, TestDisplay, TestWindow, WindowAppearance,
     WindowParams,
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use collections::VecDeque;
 use futures::channel::oneshot;
 use parking_lot::Mutex;
@@ -25,7 +25,7 @@ pub(crate) struct TestPlatform {
     foreground_executor: ForegroundExecutor,
 
     pub(crate) active_window: RefCell<Option<TestWindow>>,
-    active_display: Rc<dyn PlatformDisplay>,
+    displays: RefCell<Vec<Rc<dyn PlatformDisplay>>>,
     active_cursor: Mutex<CursorStyle>,
     current_clipboard_item: Mutex<Option<ClipboardItem>>,
     #[cfg(target_os = "linux")]
@@ -73,7 +73,7 @@ impl TestPlatform {
             foreground_executor,
             prompts: Default::default(),
             active_cursor: Default::default(),
-            active_display: Rc::new(TestDisplay::new()),
+            displays: RefCell::new(vec![Rc::new(TestDisplay::new())]),
             active_window: Default::default(),
             current_clipboard_item: Mutex::new(None),
             #[cfg(target_os = "linux")]
@@ -114,6 +114,10 @@ impl TestPlatform {
         !self.prompts.borrow().multiple_choice.is_empty()
     }
 
+    pub(crate) fn simulate_displays(&self, displays: Vec<Rc<dyn PlatformDisplay>>) {
+        *self.displays.borrow_mut() = displays;
+    }
+
     pub(crate) fn prompt(&self, msg: &str, detail: Option<&str>) -> oneshot::Receiver<usize> {
         let (tx, rx) = oneshot::channel();
         self.background_executor()
@@ -189,11 +193,11 @@ impl Platform for TestPlatform {
     }
 
     fn displays(&self) -> Vec<std::rc::Rc<dyn crate::PlatformDisplay>> {
-        vec![self.active_display.clone()]
+        self.displays.borrow().clone()
     }
 
     fn primary_display(&self) -> Option<std::rc::Rc<dyn crate::PlatformDisplay>> {
-        Some(self.active_display.clone())
+        self.displays.borrow().first().cloned()
     }
 
     fn active_window(&self) -> Option<crate::AnyWindowHandle> {
@@ -208,12 +212,18 @@ impl Platform for TestPlatform {
         handle: AnyWindowHandle,
         params: WindowParams,
     ) -> anyhow::Result<Box<dyn crate::PlatformWindow>> {
-        let window = TestWindow::new(
-            handle,
-            params,
-            self.weak.clone(),
-            self.active_display.clone(),
-        );
+        let display = params
+            .display_id
+            .and_then(|id| {
+                self.displays
+                    .borrow()
+                    .iter()
+                    .find(|display| display.id() == id)
+                    .cloned()
+            })
+            .or_else(|| self.primary_display())
+            .ok_or_else(|| anyhow!("no displays available"))?;
+        let window = TestWindow::new(handle, params, self.weak.clone(), display);
         Ok(Box::new(window))
     }
 
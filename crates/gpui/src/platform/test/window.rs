@@ -127,7 +127,7 @@ impl PlatformWindow for TestWindow {
     }
 
     fn scale_factor(&self) -> f32 {
-        2.0
+        self.0.lock().display.scale_factor()
     }
 
     fn appearance(&self) -> WindowAppearance {
@@ -209,6 +209,10 @@ impl PlatformDisplay for WindowsDisplay {
     fn bounds(&self) -> Bounds<Pixels> {
         self.bounds
     }
+
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
 }
 
 fn available_monitors() -> SmallVec<[HMONITOR; 4]> {
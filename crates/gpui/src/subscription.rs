@@ -1,6 +1,16 @@
 use collections::{BTreeMap, BTreeSet};
 use parking_lot::Mutex;
-use std::{cell::Cell, fmt::Debug, mem, rc::Rc, sync::Arc};
+use std::{
+    cell::Cell,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    mem,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicU64, Ordering::SeqCst},
+        Arc,
+    },
+};
 use util::post_inc;
 
 pub(crate) struct SubscriberSet<EmitterKey, Callback>(
@@ -62,29 +72,27 @@ where
             );
         let this = self.0.clone();
 
-        let subscription = Subscription {
-            unsubscribe: Some(Box::new(move || {
-                let mut lock = this.lock();
-                let Some(subscribers) = lock.subscribers.get_mut(&emitter_key) else {
-                    // remove was called with this emitter_key
-                    return;
-                };
-
-                if let Some(subscribers) = subscribers {
-                    subscribers.remove(&subscriber_id);
-                    if subscribers.is_empty() {
-                        lock.subscribers.remove(&emitter_key);
-                    }
-                    return;
+        let subscription = Subscription::new(move || {
+            let mut lock = this.lock();
+            let Some(subscribers) = lock.subscribers.get_mut(&emitter_key) else {
+                // remove was called with this emitter_key
+                return;
+            };
+
+            if let Some(subscribers) = subscribers {
+                subscribers.remove(&subscriber_id);
+                if subscribers.is_empty() {
+                    lock.subscribers.remove(&emitter_key);
                 }
+                return;
+            }
 
-                // We didn't manage to remove the subscription, which means it was dropped
-                // while invoking the callback. Mark it as dropped so that we can remove it
-                // later.
-                lock.dropped_subscribers
-                    .insert((emitter_key, subscriber_id));
-            })),
-        };
+            // We didn't manage to remove the subscription, which means it was dropped
+            // while invoking the callback. Mark it as dropped so that we can remove it
+            // later.
+            lock.dropped_subscribers
+                .insert((emitter_key, subscriber_id));
+        });
         (subscription, move || active.set(true))
     }
 
@@ -150,6 +158,7 @@ where
 /// is cancelled and the callback will no longer be invoked.
 #[must_use]
 pub struct Subscription {
+    id: u64,
     unsubscribe: Option<Box<dyn FnOnce() + 'static>>,
 }
 
@@ -157,11 +166,19 @@ impl Subscription {
     /// Creates a new subscription with a callback that gets invoked when
     /// this subscription is dropped.
     pub fn new(unsubscribe: impl 'static + FnOnce()) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
         Self {
+            id: NEXT_ID.fetch_add(1, SeqCst),
             unsubscribe: Some(Box::new(unsubscribe)),
         }
     }
 
+    /// A process-wide unique identifier for this subscription, which can be used
+    /// to key subscriptions that need to be dropped individually.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
     /// Detaches the subscription from this handle. The callback will
     /// continue to be invoked until the views or models it has been
     /// subscribed to are dropped
@@ -170,6 +187,20 @@ impl Subscription {
     }
 }
 
+impl PartialEq for Subscription {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Subscription {}
+
+impl Hash for Subscription {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
 impl Drop for Subscription {
     fn drop(&mut self) {
         if let Some(unsubscribe) = self.unsubscribe.take() {
@@ -177,3 +208,41 @@ impl Drop for Subscription {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use collections::HashMap;
+
+    use crate::{self as gpui, Context as _, EventEmitter, Subscription, TestAppContext};
+
+    struct Emitter;
+
+    impl EventEmitter<()> for Emitter {}
+
+    #[gpui::test]
+    fn test_dropping_keyed_subscriptions(cx: &mut TestAppContext) {
+        let emitter = cx.new_model(|_| Emitter);
+        let events = Rc::new(RefCell::new(Vec::new()));
+
+        let mut subscriptions = HashMap::<u64, Subscription>::default();
+        let mut ids = Vec::new();
+        for name in ["first", "second"] {
+            let events = events.clone();
+            let subscription = cx
+                .update(|cx| cx.subscribe(&emitter, move |_, _, _| events.borrow_mut().push(name)));
+            ids.push(subscription.id());
+            subscriptions.insert(subscription.id(), subscription);
+        }
+        assert_ne!(ids[0], ids[1]);
+
+        emitter.update(cx, |_, cx| cx.emit(()));
+        assert_eq!(*events.borrow(), ["first", "second"]);
+
+        events.borrow_mut().clear();
+        subscriptions.remove(&ids[0]);
+        emitter.update(cx, |_, cx| cx.emit(()));
+        assert_eq!(*events.borrow(), ["second"]);
+    }
+}
@@ -12,6 +12,7 @@ use std::{
     fmt,
     hash::{Hash, Hasher},
     ops::Range,
+    time::Instant,
 };
 
 /// A view is a piece of state that can be presented on screen by implementing the [Render] trait.
@@ -90,6 +91,20 @@ impl<V: 'static> View<V> {
     }
 }
 
+impl<V: Render> View<V> {
+    /// Renders the view, recording how long it took in the frame's render timings.
+    fn render_element(&self, cx: &mut WindowContext) -> AnyElement {
+        let start = Instant::now();
+        let element = self.update(cx, |view, cx| view.render(cx).into_any_element());
+        *cx.window
+            .next_frame
+            .render_timings
+            .entry(self.entity_id())
+            .or_default() += start.elapsed();
+        element
+    }
+}
+
 impl<V: Render> Element for View<V> {
     type RequestLayoutState = AnyElement;
     type PrepaintState = ();
@@ -103,7 +118,7 @@ impl<V: Render> Element for View<V> {
         _id: Option<&GlobalElementId>,
         cx: &mut WindowContext,
     ) -> (LayoutId, Self::RequestLayoutState) {
-        let mut element = self.update(cx, |view, cx| view.render(cx).into_any_element());
+        let mut element = self.render_element(cx);
         let layout_id = element.request_layout(cx);
         (layout_id, element)
     }
@@ -463,7 +478,7 @@ mod any_view {
         cx: &mut WindowContext,
     ) -> AnyElement {
         let view = view.clone().downcast::<V>().unwrap();
-        view.update(cx, |view, cx| view.render(cx).into_any_element())
+        view.render_element(cx)
     }
 }
 
@@ -475,3 +490,60 @@ impl Render for EmptyView {
         Empty
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as gpui, div, Context as _, TestAppContext};
+
+    use super::*;
+
+    struct Root {
+        child: View<Child>,
+    }
+
+    impl Render for Root {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div().child(self.child.clone())
+        }
+    }
+
+    struct Child;
+
+    impl Render for Child {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+        }
+    }
+
+    #[gpui::test]
+    fn test_render_timings(cx: &mut TestAppContext) {
+        let (root, cx) = cx.add_window_view(|cx| Root {
+            child: cx.new_view(|_| Child),
+        });
+        cx.run_until_parked();
+
+        let child_id = cx.update(|cx| root.read(cx).child.entity_id());
+        let timings = cx.update(|cx| cx.render_timings().collect::<Vec<_>>());
+        assert!(timings
+            .iter()
+            .any(|(view_id, _)| *view_id == root.entity_id()));
+        assert!(timings.iter().any(|(view_id, _)| *view_id == child_id));
+    }
+
+    #[gpui::test]
+    fn test_updating_released_weak_handles(cx: &mut TestAppContext) {
+        let (root, cx) = cx.add_window_view(|cx| Root {
+            child: cx.new_view(|_| Child),
+        });
+        let weak_child = cx.update(|cx| root.read(cx).child.downgrade());
+        let weak_model = cx.new_model(|_| ()).downgrade();
+        assert!(weak_child.update(cx, |_, _| ()).is_ok());
+
+        // Updating a released view or model fails instead of panicking.
+        root.update(cx, |root, cx| root.child = cx.new_view(|_| Child));
+        cx.run_until_parked();
+        weak_child.assert_released();
+        assert!(weak_child.update(cx, |_, _| ()).is_err());
+        assert!(weak_model.update(cx, |_, _| ()).is_err());
+    }
+}
@@ -408,6 +408,7 @@ pub(crate) struct Frame {
     pub(crate) input_handlers: Vec<Option<PlatformInputHandler>>,
     pub(crate) tooltip_requests: Vec<Option<TooltipRequest>>,
     pub(crate) cursor_styles: Vec<CursorStyleRequest>,
+    pub(crate) render_timings: FxHashMap<EntityId, Duration>,
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) debug_bounds: FxHashMap<String, Bounds<Pixels>>,
 }
@@ -447,6 +448,7 @@ impl Frame {
             input_handlers: Vec::new(),
             tooltip_requests: Vec::new(),
             cursor_styles: Vec::new(),
+            render_timings: FxHashMap::default(),
 
             #[cfg(any(test, feature = "test-support"))]
             debug_bounds: FxHashMap::default(),
@@ -464,6 +466,7 @@ impl Frame {
         self.cursor_styles.clear();
         self.hitboxes.clear();
         self.deferred_draws.clear();
+        self.render_timings.clear();
         self.focus = None;
     }
 
@@ -625,6 +628,15 @@ impl Window {
             window_decorations,
         } = options;
 
+        // The requested display may have been disconnected since its id was stored, in which
+        // case the window opens on the primary display instead.
+        let display_id = display_id.filter(|&display_id| {
+            let exists = cx.find_display(display_id).is_some();
+            if !exists {
+                log::warn!("display {display_id:?} not found, falling back to the primary display");
+            }
+            exists
+        });
         let bounds = window_bounds
             .map(|bounds| bounds.get_bounds())
             .unwrap_or_else(|| default_bounds(display_id, cx));
@@ -1045,6 +1057,19 @@ impl<'a> WindowContext<'a> {
             });
     }
 
+    fn dispatch_window_key_handlers(&mut self, event: &KeyDownEvent) -> bool {
+        let mut consumed = false;
+        self.window_key_handlers
+            .clone()
+            .retain(&self.window.handle.window_id(), |handler| {
+                if !consumed {
+                    consumed = handler(event, self);
+                }
+                true
+            });
+        consumed
+    }
+
     /// Schedules the given function to be run at the end of the current effect cycle, allowing entities
     /// that are currently on the stack to be returned to the app.
     pub fn defer(&mut self, f: impl FnOnce(&mut WindowContext) + 'static) {
@@ -1395,6 +1420,17 @@ impl<'a> WindowContext<'a> {
             .is_action_available(action, target)
     }
 
+    /// How long each view took to render in the last frame that was drawn, which is
+    /// useful for finding slow views. Views whose cached output was reused in that
+    /// frame aren't included.
+    pub fn render_timings(&self) -> impl Iterator<Item = (EntityId, Duration)> + '_ {
+        self.window
+            .rendered_frame
+            .render_timings
+            .iter()
+            .map(|(view_id, duration)| (*view_id, *duration))
+    }
+
     /// The position of the mouse relative to the window.
     pub fn mouse_position(&self) -> Point<Pixels> {
         self.window.mouse_position
@@ -1567,7 +1603,7 @@ impl<'a> WindowContext<'a> {
             } else {
                 tooltip_bounds.origin.x = cmp::max(
                     Pixels::ZERO,
-                    tooltip_bounds.origin.x - tooltip_bounds.right() - window_bounds.right(),
+                    tooltip_bounds.origin.x - (tooltip_bounds.right() - window_bounds.right()),
                 );
             }
         }
@@ -1579,7 +1615,7 @@ impl<'a> WindowContext<'a> {
             } else {
                 tooltip_bounds.origin.y = cmp::max(
                     Pixels::ZERO,
-                    tooltip_bounds.origin.y - tooltip_bounds.bottom() - window_bounds.bottom(),
+                    tooltip_bounds.origin.y - (tooltip_bounds.bottom() - window_bounds.bottom()),
                 );
             }
         }
@@ -2070,6 +2106,34 @@ impl<'a> WindowContext<'a> {
         result
     }
 
+    /// Returns the value built by `build` for the element with the given id, reusing the value
+    /// from the previous frame as long as `key` is unchanged and the window isn't being refreshed.
+    /// This method should only be called as part of element drawing.
+    pub fn cached<K, T>(
+        &mut self,
+        element_id: impl Into<ElementId>,
+        key: K,
+        build: impl FnOnce(&mut Self) -> T,
+    ) -> T
+    where
+        K: PartialEq + 'static,
+        T: Clone + 'static,
+    {
+        self.with_element_namespace(element_id, |cx| {
+            let global_id = GlobalElementId(cx.window.element_id_stack.clone());
+            cx.with_element_state::<(K, T), _>(&global_id, |state, cx| {
+                if let Some((cached_key, value)) = state {
+                    if cached_key == key && !cx.window.refreshing {
+                        return (value.clone(), (cached_key, value));
+                    }
+                }
+
+                let value = build(cx);
+                (value.clone(), (key, value))
+            })
+        })
+    }
+
     /// Updates or initializes state for an element with the given id that lives across multiple
     /// frames. If an element with this ID existed in the rendered frame, its state will be passed
     /// to the given closure. The state returned by the closure will be stored so it can be referenced
@@ -3212,6 +3276,13 @@ impl<'a> WindowContext<'a> {
     }
 
     fn dispatch_key_event(&mut self, event: &dyn Any) {
+        if let Some(key_down_event) = event.downcast_ref::<KeyDownEvent>() {
+            if self.dispatch_window_key_handlers(key_down_event) {
+                self.stop_propagation();
+                return;
+            }
+        }
+
         if self.window.dirty.get() {
             self.draw();
         }
@@ -5039,3 +5110,94 @@ pub fn outline(bounds: impl Into<Bounds<Pixels>>, border_color: impl Into<Hsla>)
         border_color: border_color.into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use crate::{
+        self as gpui, canvas, fill, point, px, red, size, Bounds, ContentMask, IntoElement, Render,
+        Styled, TestAppContext, ViewContext,
+    };
+
+    struct CachedRows {
+        key: usize,
+        builds: Rc<Cell<usize>>,
+    }
+
+    impl Render for CachedRows {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let key = self.key;
+            let builds = self.builds.clone();
+            canvas(
+                move |_, cx| cx.cached("rows", key, |_| builds.set(builds.get() + 1)),
+                |_, _, _| {},
+            )
+            .size_full()
+        }
+    }
+
+    #[gpui::test]
+    fn test_cached(cx: &mut TestAppContext) {
+        let builds = Rc::new(Cell::new(0));
+        let (view, cx) = cx.add_window_view(|_| CachedRows {
+            key: 0,
+            builds: builds.clone(),
+        });
+        cx.run_until_parked();
+        assert_eq!(builds.get(), 1);
+
+        // Redrawing with the same key reuses the cached value.
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        assert_eq!(builds.get(), 1);
+
+        view.update(cx, |view, cx| {
+            view.key = 1;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        assert_eq!(builds.get(), 2);
+
+        // Refreshing the window rebuilds the value even though the key is unchanged.
+        cx.update(|cx| cx.refresh());
+        cx.run_until_parked();
+        assert_eq!(builds.get(), 3);
+    }
+
+    #[gpui::test]
+    fn test_primitives_outside_content_mask_are_culled(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let damaged_region = Bounds::new(point(px(0.), px(0.)), size(px(100.), px(20.)));
+        cx.draw(point(px(0.), px(0.)), size(px(100.), px(100.)), |_| {
+            canvas(
+                |_, _| {},
+                move |_, _, cx| {
+                    let mask = ContentMask {
+                        bounds: damaged_region,
+                    };
+                    cx.with_content_mask(Some(mask), |cx| {
+                        for row in 0..10 {
+                            let origin = point(px(0.), px(10. * row as f32));
+                            cx.paint_quad(fill(
+                                Bounds::new(origin, size(px(100.), px(10.))),
+                                red(),
+                            ));
+                        }
+                    });
+                },
+            )
+            .size_full()
+        });
+
+        // Only the rows overlapping the masked region make it into the scene.
+        cx.update(|cx| {
+            let damaged_region = damaged_region.scale(cx.scale_factor());
+            let quads = &cx.window.next_frame.scene.quads;
+            assert_eq!(quads.len(), 2);
+            assert!(quads
+                .iter()
+                .all(|quad| quad.bounds.intersects(&damaged_region)));
+        });
+    }
+}
@@ -2,12 +2,19 @@ use crate::HttpClient;
 use anyhow::{anyhow, bail, Context, Result};
 use futures::AsyncReadExt;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 use url::Url;
 
+/// The environment variable used to download release assets from a mirror of GitHub, such as
+/// `https://mirror.example.com/github/`, rather than from `https://github.com`.
+pub const GITHUB_MIRROR_ENV_VAR: &str = "ZED_GITHUB_MIRROR";
+
 pub struct GitHubLspBinaryVersion {
     pub name: String,
     pub url: String,
+    /// The expected SHA-256 checksum of the asset at `url`, as a hex string.
+    pub sha256: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -24,6 +31,17 @@ pub struct GithubRelease {
 pub struct GithubReleaseAsset {
     pub name: String,
     pub browser_download_url: String,
+    /// The digest of the asset, in the form `sha256:<hex>`. GitHub only reports this for some
+    /// assets.
+    #[serde(default)]
+    pub digest: Option<String>,
+}
+
+impl GithubReleaseAsset {
+    /// Returns the SHA-256 checksum of the asset as a hex string, if GitHub reported one.
+    pub fn sha256(&self) -> Option<&str> {
+        self.digest.as_deref()?.strip_prefix("sha256:")
+    }
 }
 
 pub async fn latest_github_release(
@@ -118,6 +136,86 @@ pub async fn get_release_by_tag_name(
     Ok(release)
 }
 
+/// Returns the mirror configured with [`GITHUB_MIRROR_ENV_VAR`], if any.
+pub fn github_mirror() -> Option<Url> {
+    let mirror = std::env::var(GITHUB_MIRROR_ENV_VAR).ok()?;
+    Url::parse(&mirror)
+        .with_context(|| format!("invalid {GITHUB_MIRROR_ENV_VAR} {mirror:?}"))
+        .map_err(|error| log::error!("{error:?}"))
+        .ok()
+}
+
+/// Rewrites the URL of a GitHub release asset to be served by the given mirror, keeping the
+/// asset's path. For example, with a mirror of `https://mirror.example.com/github/`,
+/// `https://github.com/owner/repo/releases/download/v1/asset.gz` becomes
+/// `https://mirror.example.com/github/owner/repo/releases/download/v1/asset.gz`.
+pub fn mirrored_asset_url(url: &str, mirror: &Url) -> Result<String> {
+    let url = Url::parse(url)?;
+    let mut mirror = mirror.clone();
+    if !mirror.path().ends_with('/') {
+        mirror.set_path(&format!("{}/", mirror.path()));
+    }
+    let mut mirrored = mirror.join(url.path().trim_start_matches('/'))?;
+    mirrored.set_query(url.query());
+    Ok(mirrored.to_string())
+}
+
+/// Downloads a GitHub release asset, from the given mirror if there is one.
+///
+/// When a SHA-256 checksum is given, the downloaded bytes must match it, so that a tampered or
+/// truncated download is never used. Assets without one can't be verified, so they are always
+/// downloaded from GitHub itself rather than from the mirror.
+pub async fn download_release_asset(
+    url: &str,
+    sha256: Option<&str>,
+    mirror: Option<&Url>,
+    http: Arc<dyn HttpClient>,
+) -> Result<Vec<u8>> {
+    let url = match (mirror, sha256) {
+        (Some(mirror), Some(_)) => mirrored_asset_url(url, mirror)?,
+        (Some(mirror), None) => {
+            log::warn!(
+                "not using the {GITHUB_MIRROR_ENV_VAR} mirror {mirror} for {url}, \
+                since no checksum is available to verify it"
+            );
+            url.to_string()
+        }
+        (None, _) => url.to_string(),
+    };
+
+    let mut response = http
+        .get(&url, Default::default(), true)
+        .await
+        .with_context(|| format!("error downloading {url}"))?;
+    if !response.status().is_success() {
+        bail!(
+            "error downloading {url}: status {}",
+            response.status().as_u16()
+        );
+    }
+
+    let mut body = Vec::new();
+    response
+        .body_mut()
+        .read_to_end(&mut body)
+        .await
+        .with_context(|| format!("error reading {url}"))?;
+
+    match sha256 {
+        Some(expected) => {
+            let actual = format!("{:x}", Sha256::digest(&body));
+            if !actual.eq_ignore_ascii_case(expected) {
+                bail!("checksum mismatch for {url}: expected sha256 {expected}, got {actual}");
+            }
+        }
+        None => {
+            log::warn!("no checksum available for {url}, it was downloaded without verification")
+        }
+    }
+
+    Ok(body)
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum AssetKind {
     TarGz,
@@ -145,7 +243,12 @@ pub fn build_asset_url(repo_name_with_owner: &str, tag: &str, kind: AssetKind) -
 
 #[cfg(test)]
 mod tests {
-    use crate::github::{build_asset_url, AssetKind};
+    use futures::executor::block_on;
+    use sha2::{Digest, Sha256};
+    use url::Url;
+
+    use crate::github::{build_asset_url, download_release_asset, mirrored_asset_url, AssetKind};
+    use crate::{FakeHttpClient, Response};
 
     #[test]
     fn test_build_asset_url() {
@@ -164,4 +267,68 @@ mod tests {
             "https://github.com/microsoft/vscode-eslint/archive/refs/tags/release%2F2.3.5.zip"
         );
     }
+
+    #[test]
+    fn test_mirrored_asset_url() {
+        let url = "https://github.com/rust-lang/rust-analyzer/releases/download/2024-10-07/rust-analyzer-x86_64-unknown-linux-gnu.gz";
+        let expected = "https://mirror.example.com/github/rust-lang/rust-analyzer/releases/download/2024-10-07/rust-analyzer-x86_64-unknown-linux-gnu.gz";
+
+        let mirror = Url::parse("https://mirror.example.com/github/").unwrap();
+        assert_eq!(mirrored_asset_url(url, &mirror).unwrap(), expected);
+
+        // The mirror's path doesn't need a trailing slash.
+        let mirror = Url::parse("https://mirror.example.com/github").unwrap();
+        assert_eq!(mirrored_asset_url(url, &mirror).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_download_release_asset_verifies_checksum() {
+        const BINARY: &[u8] = b"language server binary";
+        let http = FakeHttpClient::create(|request| async move {
+            assert_eq!(
+                request.uri().to_string(),
+                "https://mirror.example.com/owner/repo/releases/download/v1/server"
+            );
+            Ok(Response::builder().status(200).body(BINARY.into()).unwrap())
+        });
+        let url = "https://github.com/owner/repo/releases/download/v1/server";
+        let mirror = Url::parse("https://mirror.example.com").unwrap();
+        let checksum = format!("{:x}", Sha256::digest(BINARY));
+
+        let downloaded = block_on(download_release_asset(
+            url,
+            Some(&checksum),
+            Some(&mirror),
+            http.clone(),
+        ))
+        .unwrap();
+        assert_eq!(downloaded, BINARY);
+
+        let wrong_checksum = format!("{:x}", Sha256::digest(b"something else"));
+        let error = block_on(download_release_asset(
+            url,
+            Some(&wrong_checksum),
+            Some(&mirror),
+            http,
+        ))
+        .unwrap_err();
+        assert!(error.to_string().contains("checksum mismatch"), "{error}");
+    }
+
+    #[test]
+    fn test_download_release_asset_without_checksum_skips_mirror() {
+        const BINARY: &[u8] = b"language server binary";
+        let http = FakeHttpClient::create(|request| async move {
+            assert_eq!(
+                request.uri().to_string(),
+                "https://github.com/owner/repo/releases/download/v1/server"
+            );
+            Ok(Response::builder().status(200).body(BINARY.into()).unwrap())
+        });
+        let url = "https://github.com/owner/repo/releases/download/v1/server";
+        let mirror = Url::parse("https://mirror.example.com").unwrap();
+
+        let downloaded = block_on(download_release_asset(url, None, Some(&mirror), http)).unwrap();
+        assert_eq!(downloaded, BINARY);
+    }
 }
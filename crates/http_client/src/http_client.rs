@@ -8,7 +8,7 @@ pub use http::{self, Method, Request, Response, StatusCode, Uri};
 
 use futures::future::BoxFuture;
 use http::request::Builder;
-#[cfg(feature = "test-support")]
+#[cfg(any(test, feature = "test-support"))]
 use std::fmt;
 use std::{
     any::type_name,
@@ -346,7 +346,7 @@ impl HttpClient for BlockedHttpClient {
     }
 }
 
-#[cfg(feature = "test-support")]
+#[cfg(any(test, feature = "test-support"))]
 type FakeHttpHandler = Box<
     dyn Fn(Request<AsyncBody>) -> BoxFuture<'static, Result<Response<AsyncBody>, anyhow::Error>>
         + Send
@@ -354,12 +354,12 @@ type FakeHttpHandler = Box<
         + 'static,
 >;
 
-#[cfg(feature = "test-support")]
+#[cfg(any(test, feature = "test-support"))]
 pub struct FakeHttpClient {
     handler: FakeHttpHandler,
 }
 
-#[cfg(feature = "test-support")]
+#[cfg(any(test, feature = "test-support"))]
 impl FakeHttpClient {
     pub fn create<Fut, F>(handler: F) -> Arc<HttpClientWithUrl>
     where
@@ -396,14 +396,14 @@ impl FakeHttpClient {
     }
 }
 
-#[cfg(feature = "test-support")]
+#[cfg(any(test, feature = "test-support"))]
 impl fmt::Debug for FakeHttpClient {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("FakeHttpClient").finish()
     }
 }
 
-#[cfg(feature = "test-support")]
+#[cfg(any(test, feature = "test-support"))]
 impl HttpClient for FakeHttpClient {
     fn send(
         &self,
@@ -1,5 +1,8 @@
-use anyhow::Result;
-use chrono::{Datelike, Local, NaiveTime, Timelike};
+use anyhow::{anyhow, Result};
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, Local, NaiveTime, TimeZone, Timelike, Utc,
+};
 use editor::scroll::Autoscroll;
 use editor::Editor;
 use gpui::{actions, AppContext, ViewContext, WindowContext};
@@ -8,7 +11,7 @@ use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
 use std::{
     fs::OpenOptions,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
     sync::Arc,
 };
 use workspace::{AppState, OpenVisible, Workspace};
@@ -26,13 +29,26 @@ pub struct JournalSettings {
     ///
     /// Default: hour12
     pub hour_format: Option<HourFormat>,
+    /// The path of each entry relative to the journal directory, using `strftime`-style
+    /// date specifiers.
+    ///
+    /// Default: `%Y/%m/%d.md`
+    pub entry_path_template: Option<String>,
+    /// Which time zone to use when dating entries.
+    ///
+    /// Default: local
+    pub time_zone: Option<JournalTimeZone>,
 }
 
+const DEFAULT_ENTRY_PATH_TEMPLATE: &str = "%Y/%m/%d.md";
+
 impl Default for JournalSettings {
     fn default() -> Self {
         Self {
             path: Some("~".into()),
             hour_format: Some(Default::default()),
+            entry_path_template: Some(DEFAULT_ENTRY_PATH_TEMPLATE.into()),
+            time_zone: Some(Default::default()),
         }
     }
 }
@@ -45,6 +61,14 @@ pub enum HourFormat {
     Hour24,
 }
 
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalTimeZone {
+    #[default]
+    Local,
+    Utc,
+}
+
 impl settings::Settings for JournalSettings {
     const KEY: Option<&'static str> = Some("journal");
 
@@ -79,16 +103,33 @@ pub fn new_journal_entry(workspace: &Workspace, cx: &mut WindowContext) {
     };
     let journal_dir_clone = journal_dir.clone();
 
-    let now = Local::now();
-    let month_dir = journal_dir
-        .join(format!("{:02}", now.year()))
-        .join(format!("{:02}", now.month()));
-    let entry_path = month_dir.join(format!("{:02}.md", now.day()));
-    let now = now.time();
+    let template = settings
+        .entry_path_template
+        .as_deref()
+        .unwrap_or(DEFAULT_ENTRY_PATH_TEMPLATE);
+    let (entry_path, now) = match settings.time_zone.unwrap_or_default() {
+        JournalTimeZone::Local => {
+            let now = Local::now();
+            (journal_entry_path(&now, template), now.time())
+        }
+        JournalTimeZone::Utc => {
+            let now = Utc::now();
+            (journal_entry_path(&now, template), now.time())
+        }
+    };
+    let entry_path = match entry_path {
+        Ok(entry_path) => journal_dir.join(entry_path),
+        Err(error) => {
+            log::error!("Can't determine journal entry path: {error}");
+            return;
+        }
+    };
     let entry_heading = heading_entry(now, &settings.hour_format);
 
     let create_entry = cx.background_executor().spawn(async move {
-        std::fs::create_dir_all(month_dir)?;
+        if let Some(entry_dir) = entry_path.parent() {
+            std::fs::create_dir_all(entry_dir)?;
+        }
         OpenOptions::new()
             .create(true)
             .truncate(false)
@@ -172,6 +213,26 @@ fn journal_dir(path: &str) -> Option<PathBuf> {
     expanded_journal_dir
 }
 
+/// Returns the path of the entry for the given time, relative to the journal directory.
+pub fn journal_entry_path<Tz: TimeZone>(now: &DateTime<Tz>, template: &str) -> Result<PathBuf>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let items = StrftimeItems::new(template).collect::<Vec<_>>();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return Err(anyhow!("invalid journal entry path template {template:?}"));
+    }
+    let path = PathBuf::from(now.format_with_items(items.into_iter()).to_string());
+    // Entries must stay inside the journal directory.
+    let stays_in_journal_dir = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if path.as_os_str().is_empty() || !stays_in_journal_dir {
+        return Err(anyhow!("invalid journal entry path template {template:?}"));
+    }
+    Ok(path)
+}
+
 fn heading_entry(now: NaiveTime, hour_format: &Option<HourFormat>) -> String {
     match hour_format {
         Some(HourFormat::Hour24) => {
@@ -188,6 +249,59 @@ fn heading_entry(now: NaiveTime, hour_format: &Option<HourFormat>) -> String {
 
 #[cfg(test)]
 mod tests {
+    mod journal_entry_path_tests {
+        use super::super::*;
+        use chrono::FixedOffset;
+
+        #[test]
+        fn test_default_template() {
+            let now = Utc.with_ymd_and_hms(2024, 3, 7, 12, 0, 0).unwrap();
+            assert_eq!(
+                journal_entry_path(&now, DEFAULT_ENTRY_PATH_TEMPLATE).unwrap(),
+                Path::new("2024/03/07.md")
+            );
+        }
+
+        #[test]
+        fn test_custom_templates() {
+            let now = Utc.with_ymd_and_hms(2024, 11, 28, 12, 0, 0).unwrap();
+            assert_eq!(
+                journal_entry_path(&now, "%Y-%m-%d.md").unwrap(),
+                Path::new("2024-11-28.md")
+            );
+            assert_eq!(
+                journal_entry_path(&now, "%Y/week-%V/%a.txt").unwrap(),
+                Path::new("2024/week-48/Thu.txt")
+            );
+        }
+
+        #[test]
+        fn test_invalid_templates() {
+            let now = Utc.with_ymd_and_hms(2024, 11, 28, 12, 0, 0).unwrap();
+            assert!(journal_entry_path(&now, "%Y/%Q.md").is_err());
+            assert!(journal_entry_path(&now, "").is_err());
+            assert!(journal_entry_path(&now, "/%Y/%m.md").is_err());
+            assert!(journal_entry_path(&now, "../%Y/%m.md").is_err());
+            assert!(journal_entry_path(&now, "%Y/../../%m.md").is_err());
+        }
+
+        #[test]
+        fn test_entry_date_depends_on_time_zone() {
+            // Shortly before midnight in UTC-5 is already the next day (and year) in UTC.
+            let offset = FixedOffset::west_opt(5 * 3600).unwrap();
+            let local = offset.with_ymd_and_hms(2023, 12, 31, 23, 30, 0).unwrap();
+            let utc = local.with_timezone(&Utc);
+            assert_eq!(
+                journal_entry_path(&local, DEFAULT_ENTRY_PATH_TEMPLATE).unwrap(),
+                Path::new("2023/12/31.md")
+            );
+            assert_eq!(
+                journal_entry_path(&utc, DEFAULT_ENTRY_PATH_TEMPLATE).unwrap(),
+                Path::new("2024/01/01.md")
+            );
+        }
+    }
+
     mod heading_entry_tests {
         use super::super::*;
 
@@ -6,7 +6,9 @@ pub use crate::{
 };
 use crate::{
     diagnostic_set::{DiagnosticEntry, DiagnosticGroup},
-    language_settings::{language_settings, IndentGuideSettings, LanguageSettings},
+    language_settings::{
+        language_settings, IndentGuideSettings, LanguageSettings, WhitespaceDiagnosticsSettings,
+    },
     markdown::parse_markdown,
     outline::OutlineItem,
     syntax_map::{
@@ -4508,3 +4510,50 @@ pub fn trailing_whitespace_ranges(rope: &Rope) -> Vec<Range<usize>> {
 
     ranges
 }
+
+/// Produces diagnostics for trailing whitespace and a missing final newline
+/// in the given rope, depending on which checks are enabled in `settings`.
+///
+/// Each reported problem forms its own diagnostic group.
+pub fn whitespace_diagnostics(
+    rope: &Rope,
+    settings: WhitespaceDiagnosticsSettings,
+) -> Vec<DiagnosticEntry<PointUtf16>> {
+    let mut entries = Vec::new();
+    let mut push_entry = |range: Range<PointUtf16>, message: &str| {
+        let group_id = entries.len();
+        entries.push(DiagnosticEntry {
+            range,
+            diagnostic: Diagnostic {
+                source: Some(WHITESPACE_DIAGNOSTICS_SOURCE.to_string()),
+                severity: DiagnosticSeverity::WARNING,
+                message: message.to_string(),
+                group_id,
+                is_primary: true,
+                ..Default::default()
+            },
+        });
+    };
+
+    if settings.trailing_whitespace {
+        for range in trailing_whitespace_ranges(rope) {
+            push_entry(
+                rope.offset_to_point_utf16(range.start)..rope.offset_to_point_utf16(range.end),
+                "trailing whitespace",
+            );
+        }
+    }
+
+    if settings.final_newline
+        && !rope.is_empty()
+        && rope.reversed_chars_at(rope.len()).next() != Some('\n')
+    {
+        let end = rope.max_point_utf16();
+        push_entry(end..end, "missing newline at end of file");
+    }
+
+    entries
+}
+
+/// The `source` reported by diagnostics from [`whitespace_diagnostics`].
+pub const WHITESPACE_DIAGNOSTICS_SOURCE: &str = "whitespace";
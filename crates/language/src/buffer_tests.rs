@@ -172,6 +172,43 @@ async fn test_first_line_pattern(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+fn test_language_for_path(cx: &mut AppContext) {
+    let registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
+    for (name, suffix, first_line_pattern) in [
+        ("Rust", "rs", None),
+        ("Python", "py", Some(r"^#!.*\bpython[0-9.]*\b")),
+    ] {
+        registry.register_test_language(LanguageConfig {
+            name: name.into(),
+            matcher: LanguageMatcher {
+                path_suffixes: vec![suffix.into()],
+                first_line_pattern: first_line_pattern.map(|pattern| Regex::new(pattern).unwrap()),
+            },
+            ..Default::default()
+        });
+    }
+
+    let language_for_path = |path: &str, first_line: Option<&str>| {
+        registry
+            .language_for_path(Path::new(path), first_line)
+            .map(|language| language.name())
+    };
+    assert_eq!(language_for_path("foo.rs", None), Some("Rust".into()));
+    assert_eq!(
+        language_for_path("script", Some("#!/usr/bin/env python")),
+        Some("Python".into())
+    );
+    assert_eq!(language_for_path("script", Some("#!/bin/sh")), None);
+    assert_eq!(language_for_path("script", None), None);
+
+    // The suffix takes precedence over the first line.
+    assert_eq!(
+        language_for_path("foo.rs", Some("#!/usr/bin/env python3")),
+        Some("Rust".into())
+    );
+}
+
 #[gpui::test]
 async fn test_language_for_file_with_custom_file_types(cx: &mut TestAppContext) {
     cx.update(|cx| {
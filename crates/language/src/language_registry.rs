@@ -610,14 +610,31 @@ impl LanguageRegistry {
         cx: &AppContext,
     ) -> Option<AvailableLanguage> {
         let user_file_types = all_language_settings(Some(file), cx);
+        let first_line = content.map(|content| {
+            let end = content.clip_point(Point::new(0, 256), Bias::Left);
+            let end = content.point_to_offset(end);
+            content.chunks_in_range(0..end).collect::<String>()
+        });
 
         self.language_for_file_internal(
             &file.full_path(cx),
-            content,
+            first_line.as_deref(),
             Some(&user_file_types.file_types),
         )
     }
 
+    /// Returns the language for the given path, without taking user settings into account.
+    ///
+    /// When no language claims the path's suffix, the file's first line is matched against
+    /// each language's first line pattern, so that scripts can be detected by their shebang.
+    pub fn language_for_path(
+        self: &Arc<Self>,
+        path: &Path,
+        first_line: Option<&str>,
+    ) -> Option<AvailableLanguage> {
+        self.language_for_file_internal(path, first_line, None)
+    }
+
     pub fn language_for_file_path<'a>(
         self: &Arc<Self>,
         path: &'a Path,
@@ -637,7 +654,7 @@ impl LanguageRegistry {
     fn language_for_file_internal(
         self: &Arc<Self>,
         path: &Path,
-        content: Option<&Rope>,
+        first_line: Option<&str>,
         user_file_types: Option<&HashMap<Arc<str>, GlobSet>>,
     ) -> Option<AvailableLanguage> {
         let filename = path.file_name().and_then(|name| name.to_str());
@@ -657,18 +674,15 @@ impl LanguageRegistry {
                 .iter()
                 .map(|suffix| suffix.unwrap_or(""))
                 .any(|suffix| custom_suffixes.is_match(suffix));
-            let content_matches = content.zip(config.first_line_pattern.as_ref()).map_or(
-                false,
-                |(content, pattern)| {
-                    let end = content.clip_point(Point::new(0, 256), Bias::Left);
-                    let end = content.point_to_offset(end);
-                    let text = content.chunks_in_range(0..end).collect::<String>();
-                    pattern.is_match(&text)
-                },
-            );
+            let content_matches = first_line
+                .zip(config.first_line_pattern.as_ref())
+                .map_or(false, |(first_line, pattern)| pattern.is_match(first_line));
+            // A file's first line is only used when no language claims its suffix.
             if path_matches_custom_suffix {
+                3
+            } else if path_matches_default_suffix {
                 2
-            } else if path_matches_default_suffix || content_matches {
+            } else if content_matches {
                 1
             } else {
                 0
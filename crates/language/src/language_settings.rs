@@ -96,6 +96,9 @@ pub struct LanguageSettings {
     /// Whether or not to ensure there's a single newline at the end of a buffer
     /// when saving it.
     pub ensure_final_newline_on_save: bool,
+    /// Whether to report trailing whitespace and missing final newlines
+    /// as diagnostics.
+    pub whitespace_diagnostics: WhitespaceDiagnosticsSettings,
     /// How to perform a buffer format.
     pub formatter: SelectedFormatter,
     /// Zed's Prettier integration settings.
@@ -283,6 +286,10 @@ pub struct LanguageSettingsContent {
     /// Default: true
     #[serde(default)]
     pub ensure_final_newline_on_save: Option<bool>,
+    /// Whether to report trailing whitespace and missing final newlines
+    /// as diagnostics.
+    #[serde(default)]
+    pub whitespace_diagnostics: Option<WhitespaceDiagnosticsSettingsContent>,
     /// How to perform a buffer format.
     ///
     /// Default: auto
@@ -706,6 +713,37 @@ pub struct IndentGuideSettings {
     pub background_coloring: IndentGuideBackgroundColoring,
 }
 
+/// The settings for the built-in whitespace linter.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+pub struct WhitespaceDiagnosticsSettings {
+    /// Whether to report whitespace at the end of lines.
+    pub trailing_whitespace: bool,
+    /// Whether to report a buffer that doesn't end with a newline.
+    pub final_newline: bool,
+}
+
+/// The contents of the built-in whitespace linter settings.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WhitespaceDiagnosticsSettingsContent {
+    /// Whether to report whitespace at the end of lines.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub trailing_whitespace: Option<bool>,
+    /// Whether to report a buffer that doesn't end with a newline.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub final_newline: Option<bool>,
+}
+
+impl WhitespaceDiagnosticsSettings {
+    /// Returns whether any of the whitespace checks are enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.trailing_whitespace || self.final_newline
+    }
+}
+
 fn line_width() -> u32 {
     1
 }
@@ -1161,6 +1199,16 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         &mut settings.ensure_final_newline_on_save,
         src.ensure_final_newline_on_save,
     );
+    if let Some(whitespace_diagnostics) = src.whitespace_diagnostics {
+        merge(
+            &mut settings.whitespace_diagnostics.trailing_whitespace,
+            whitespace_diagnostics.trailing_whitespace,
+        );
+        merge(
+            &mut settings.whitespace_diagnostics.final_newline,
+            whitespace_diagnostics.final_newline,
+        );
+    }
     merge(
         &mut settings.enable_language_server,
         src.enable_language_server,
@@ -1206,6 +1254,56 @@ pub struct PrettierSettings {
 mod tests {
     use super::*;
 
+    #[gpui::test]
+    fn test_language_settings_precedence(cx: &mut AppContext) {
+        let mut store = SettingsStore::test(cx);
+        store.register_setting::<AllLanguageSettings>(cx);
+        let markdown = |store: &SettingsStore, cx: &AppContext| {
+            store
+                .get::<AllLanguageSettings>(None)
+                .language(None, Some(&LanguageName::new("Markdown")), cx)
+                .into_owned()
+        };
+
+        // The built-in settings disable formatting on save for Markdown.
+        let settings = markdown(&store, cx);
+        assert_eq!(settings.format_on_save, FormatOnSave::Off);
+        assert!(!settings.use_on_type_format);
+
+        // User settings override the built-in language settings field by field. A user's
+        // global settings override built-in language settings, but not the user's own
+        // language settings.
+        store
+            .set_user_settings(
+                r#"{
+                    "tab_size": 2,
+                    "use_on_type_format": true,
+                    "languages": {
+                        "Markdown": {
+                            "tab_size": 8,
+                            "format_on_save": "on"
+                        }
+                    }
+                }"#,
+                cx,
+            )
+            .unwrap();
+        let settings = markdown(&store, cx);
+        assert_eq!(settings.format_on_save, FormatOnSave::On);
+        assert_eq!(settings.tab_size.get(), 8);
+        assert!(settings.use_on_type_format);
+        assert!(settings.prettier.allowed);
+
+        // Invalid values are reported rather than ignored, and the previous values are kept.
+        assert!(store
+            .set_user_settings(
+                r#"{ "languages": { "Markdown": { "format_on_save": "sometimes" } } }"#,
+                cx,
+            )
+            .is_err());
+        assert_eq!(markdown(&store, cx).format_on_save, FormatOnSave::On);
+    }
+
     #[test]
     fn test_formatter_deserialization() {
         let raw_auto = "{\"formatter\": \"auto\"}";
@@ -2,10 +2,12 @@ use anyhow::{anyhow, bail, Context, Result};
 use async_trait::async_trait;
 use futures::StreamExt;
 use gpui::AsyncAppContext;
-use http_client::github::{latest_github_release, GitHubLspBinaryVersion};
+use http_client::github::{
+    download_release_asset, github_mirror, latest_github_release, GitHubLspBinaryVersion,
+};
 pub use language::*;
 use lsp::LanguageServerBinary;
-use smol::fs::{self, File};
+use smol::fs;
 use std::{any::Any, env::consts, path::PathBuf, sync::Arc};
 use util::{fs::remove_matching, maybe, ResultExt};
 
@@ -55,6 +57,7 @@ impl super::LspAdapter for CLspAdapter {
         let version = GitHubLspBinaryVersion {
             name: release.tag_name,
             url: asset.browser_download_url.clone(),
+            sha256: asset.sha256().map(Into::into),
         };
         Ok(Box::new(version) as Box<_>)
     }
@@ -71,19 +74,15 @@ impl super::LspAdapter for CLspAdapter {
         let binary_path = version_dir.join("bin/clangd");
 
         if fs::metadata(&binary_path).await.is_err() {
-            let mut response = delegate
-                .http_client()
-                .get(&version.url, Default::default(), true)
-                .await
-                .context("error downloading release")?;
-            let mut file = File::create(&zip_path).await?;
-            if !response.status().is_success() {
-                Err(anyhow!(
-                    "download failed with status {}",
-                    response.status().to_string()
-                ))?;
-            }
-            futures::io::copy(response.body_mut(), &mut file).await?;
+            let bytes = download_release_asset(
+                &version.url,
+                version.sha256.as_deref(),
+                github_mirror().as_ref(),
+                delegate.http_client(),
+            )
+            .await
+            .context("error downloading release")?;
+            fs::write(&zip_path, bytes).await?;
 
             let unzip_status = smol::process::Command::new("unzip")
                 .current_dir(&container_dir)
@@ -6,7 +6,9 @@ use collections::HashMap;
 use feature_flags::FeatureFlagAppExt;
 use futures::StreamExt;
 use gpui::{AppContext, AsyncAppContext};
-use http_client::github::{latest_github_release, GitHubLspBinaryVersion};
+use http_client::github::{
+    download_release_asset, github_mirror, latest_github_release, GitHubLspBinaryVersion,
+};
 use language::{
     LanguageRegistry, LanguageServerName, LanguageToolchainStore, LspAdapter, LspAdapterDelegate,
 };
@@ -301,6 +303,7 @@ impl LspAdapter for NodeVersionAdapter {
         Ok(Box::new(GitHubLspBinaryVersion {
             name: release.tag_name,
             url: asset.browser_download_url.clone(),
+            sha256: asset.sha256().map(Into::into),
         }))
     }
 
@@ -319,19 +322,22 @@ impl LspAdapter for NodeVersionAdapter {
         let destination_container_path =
             container_dir.join(format!("package-version-server-{}-tmp", version.name));
         if fs::metadata(&destination_path).await.is_err() {
-            let mut response = delegate
-                .http_client()
-                .get(&version.url, Default::default(), true)
-                .await
-                .map_err(|err| anyhow!("error downloading release: {}", err))?;
+            let bytes = download_release_asset(
+                &version.url,
+                version.sha256.as_deref(),
+                github_mirror().as_ref(),
+                delegate.http_client(),
+            )
+            .await
+            .context("error downloading release")?;
             if version.url.ends_with(".zip") {
                 node_runtime::extract_zip(
                     &destination_container_path,
-                    BufReader::new(response.body_mut()),
+                    BufReader::new(bytes.as_slice()),
                 )
                 .await?;
             } else if version.url.ends_with(".tar.gz") {
-                let decompressed_bytes = GzipDecoder::new(BufReader::new(response.body_mut()));
+                let decompressed_bytes = GzipDecoder::new(BufReader::new(bytes.as_slice()));
                 let archive = Archive::new(decompressed_bytes);
                 archive.unpack(&destination_container_path).await?;
             }
@@ -4,7 +4,9 @@ use async_trait::async_trait;
 use collections::HashMap;
 use futures::{io::BufReader, StreamExt};
 use gpui::{AppContext, AsyncAppContext};
-use http_client::github::{latest_github_release, GitHubLspBinaryVersion};
+use http_client::github::{
+    download_release_asset, github_mirror, latest_github_release, GitHubLspBinaryVersion,
+};
 pub use language::*;
 use lsp::LanguageServerBinary;
 use regex::Regex;
@@ -94,6 +96,7 @@ impl LspAdapter for RustLspAdapter {
         Ok(Box::new(GitHubLspBinaryVersion {
             name: release.tag_name,
             url: asset.browser_download_url.clone(),
+            sha256: asset.sha256().map(Into::into),
         }))
     }
 
@@ -107,12 +110,15 @@ impl LspAdapter for RustLspAdapter {
         let destination_path = container_dir.join(format!("rust-analyzer-{}", version.name));
 
         if fs::metadata(&destination_path).await.is_err() {
-            let mut response = delegate
-                .http_client()
-                .get(&version.url, Default::default(), true)
-                .await
-                .map_err(|err| anyhow!("error downloading release: {}", err))?;
-            let decompressed_bytes = GzipDecoder::new(BufReader::new(response.body_mut()));
+            let bytes = download_release_asset(
+                &version.url,
+                version.sha256.as_deref(),
+                github_mirror().as_ref(),
+                delegate.http_client(),
+            )
+            .await
+            .context("error downloading release")?;
+            let decompressed_bytes = GzipDecoder::new(BufReader::new(bytes.as_slice()));
             let mut file = File::create(&destination_path).await?;
             futures::io::copy(decompressed_bytes, &mut file).await?;
             // todo("windows")
@@ -1,10 +1,15 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context as _, Result};
 use async_compression::futures::bufread::GzipDecoder;
 use async_tar::Archive;
 use async_trait::async_trait;
 use collections::HashMap;
 use gpui::AsyncAppContext;
-use http_client::github::{build_asset_url, AssetKind, GitHubLspBinaryVersion};
+use http_client::{
+    github::{
+        build_asset_url, download_release_asset, github_mirror, AssetKind, GitHubLspBinaryVersion,
+    },
+    HttpClient, Url,
+};
 use language::{LanguageServerName, LanguageToolchainStore, LspAdapter, LspAdapterDelegate};
 use lsp::{CodeActionKind, LanguageServerBinary};
 use node_runtime::NodeRuntime;
@@ -312,6 +317,49 @@ impl EsLintLspAdapter {
     fn build_destination_path(container_dir: &Path) -> PathBuf {
         container_dir.join(format!("vscode-eslint-{}", Self::CURRENT_VERSION))
     }
+
+    fn release_version() -> Result<GitHubLspBinaryVersion> {
+        let url = build_asset_url(
+            "microsoft/vscode-eslint",
+            Self::CURRENT_VERSION_TAG_NAME,
+            Self::GITHUB_ASSET_KIND,
+        )?;
+
+        Ok(GitHubLspBinaryVersion {
+            name: Self::CURRENT_VERSION.into(),
+            url,
+            sha256: None,
+        })
+    }
+
+    /// Downloads the release's source archive and unpacks it into a `vscode-eslint` directory
+    /// inside `destination_path`, returning that directory.
+    async fn download_release(
+        version: &GitHubLspBinaryVersion,
+        mirror: Option<&Url>,
+        http: Arc<dyn HttpClient>,
+        destination_path: &Path,
+    ) -> Result<PathBuf> {
+        let bytes = download_release_asset(&version.url, version.sha256.as_deref(), mirror, http)
+            .await
+            .context("error downloading release")?;
+        match Self::GITHUB_ASSET_KIND {
+            AssetKind::TarGz => {
+                let decompressed_bytes = GzipDecoder::new(BufReader::new(bytes.as_slice()));
+                let archive = Archive::new(decompressed_bytes);
+                archive.unpack(destination_path).await?;
+            }
+            AssetKind::Zip => {
+                node_runtime::extract_zip(destination_path, bytes.as_slice()).await?;
+            }
+        }
+
+        let mut dir = fs::read_dir(destination_path).await?;
+        let first = dir.next().await.ok_or(anyhow!("missing first file"))??;
+        let repo_root = destination_path.join("vscode-eslint");
+        fs::rename(first.path(), &repo_root).await?;
+        Ok(repo_root)
+    }
 }
 
 #[async_trait(?Send)]
@@ -411,16 +459,7 @@ impl LspAdapter for EsLintLspAdapter {
         &self,
         _delegate: &dyn LspAdapterDelegate,
     ) -> Result<Box<dyn 'static + Send + Any>> {
-        let url = build_asset_url(
-            "microsoft/vscode-eslint",
-            Self::CURRENT_VERSION_TAG_NAME,
-            Self::GITHUB_ASSET_KIND,
-        )?;
-
-        Ok(Box::new(GitHubLspBinaryVersion {
-            name: Self::CURRENT_VERSION.into(),
-            url,
-        }))
+        Ok(Box::new(Self::release_version()?))
     }
 
     async fn fetch_server_binary(
@@ -436,30 +475,13 @@ impl LspAdapter for EsLintLspAdapter {
         if fs::metadata(&server_path).await.is_err() {
             remove_matching(&container_dir, |entry| entry != destination_path).await;
 
-            let mut response = delegate
-                .http_client()
-                .get(&version.url, Default::default(), true)
-                .await
-                .map_err(|err| anyhow!("error downloading release: {}", err))?;
-            match Self::GITHUB_ASSET_KIND {
-                AssetKind::TarGz => {
-                    let decompressed_bytes = GzipDecoder::new(BufReader::new(response.body_mut()));
-                    let archive = Archive::new(decompressed_bytes);
-                    archive.unpack(&destination_path).await?;
-                }
-                AssetKind::Zip => {
-                    node_runtime::extract_zip(
-                        &destination_path,
-                        BufReader::new(response.body_mut()),
-                    )
-                    .await?;
-                }
-            }
-
-            let mut dir = fs::read_dir(&destination_path).await?;
-            let first = dir.next().await.ok_or(anyhow!("missing first file"))??;
-            let repo_root = destination_path.join("vscode-eslint");
-            fs::rename(first.path(), &repo_root).await?;
+            let repo_root = Self::download_release(
+                &version,
+                github_mirror().as_ref(),
+                delegate.http_client(),
+                &destination_path,
+            )
+            .await?;
 
             #[cfg(target_os = "windows")]
             {
@@ -530,6 +552,64 @@ mod tests {
     use gpui::{Context, TestAppContext};
     use unindent::Unindent;
 
+    #[cfg(not(windows))]
+    #[test]
+    fn test_eslint_download_with_github_mirror() {
+        use super::EsLintLspAdapter;
+        use async_compression::futures::bufread::GzipEncoder;
+        use http_client::{FakeHttpClient, Response, Url};
+        use smol::io::{AsyncReadExt, BufReader};
+
+        smol::block_on(async {
+            let mut archive = async_tar::Builder::new(Vec::new());
+            let package_json = br#"{"name": "vscode-eslint"}"#;
+            let mut header = async_tar::Header::new_gnu();
+            header.set_size(package_json.len() as u64);
+            header.set_mode(0o644);
+            archive
+                .append_data(
+                    &mut header,
+                    "vscode-eslint-release-2.4.4/package.json",
+                    package_json.as_slice(),
+                )
+                .await
+                .unwrap();
+            let archive = archive.into_inner().await.unwrap();
+            let mut gzipped_archive = Vec::new();
+            GzipEncoder::new(BufReader::new(archive.as_slice()))
+                .read_to_end(&mut gzipped_archive)
+                .await
+                .unwrap();
+
+            // ESLint's source archive has no checksum, so it's downloaded from GitHub even
+            // when a mirror is configured.
+            let version = EsLintLspAdapter::release_version().unwrap();
+            assert_eq!(version.sha256, None);
+            let http = FakeHttpClient::create({
+                let url = version.url.clone();
+                move |request| {
+                    assert_eq!(request.uri().to_string(), url);
+                    let body = gzipped_archive.clone();
+                    async move { Ok(Response::new(body.into())) }
+                }
+            });
+            let mirror = Url::parse("https://mirror.example.com/github/").unwrap();
+
+            let container_dir = util::test::temp_tree(serde_json::json!({}));
+            let destination_path = EsLintLspAdapter::build_destination_path(container_dir.path());
+            let repo_root = EsLintLspAdapter::download_release(
+                &version,
+                Some(&mirror),
+                http,
+                &destination_path,
+            )
+            .await
+            .unwrap();
+            assert_eq!(repo_root, destination_path.join("vscode-eslint"));
+            assert!(repo_root.join("package.json").is_file());
+        });
+    }
+
     #[gpui::test]
     async fn test_outline(cx: &mut TestAppContext) {
         let language = crate::language(
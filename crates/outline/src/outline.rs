@@ -1,10 +1,13 @@
+use collections::HashSet;
 use editor::{
-    actions::ToggleOutline, scroll::Autoscroll, Anchor, AnchorRangeExt, Editor, EditorMode,
+    actions::ToggleOutline, scroll::Autoscroll, Anchor, AnchorRangeExt, Editor, EditorEvent,
+    EditorMode,
 };
 use fuzzy::StringMatch;
 use gpui::{
-    div, rems, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, ParentElement,
-    Point, Render, Styled, Task, View, ViewContext, VisualContext, WeakView, WindowContext,
+    actions, div, rems, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView,
+    ParentElement, Point, Render, Styled, Subscription, Task, View, ViewContext, VisualContext,
+    WeakView, WindowContext,
 };
 use language::Outline;
 use ordered_float::OrderedFloat;
@@ -15,21 +18,21 @@ use std::{
 };
 
 use theme::ActiveTheme;
-use ui::{prelude::*, ListItem, ListItemSpacing};
+use ui::{prelude::*, Disclosure, ListItem, ListItemSpacing};
 use util::ResultExt;
 use workspace::{DismissDecision, ModalView};
 
+actions!(
+    outline,
+    [CollapseAllEntries, ExpandAllEntries, ToggleSelectedEntry]
+);
+
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(OutlineView::register).detach();
 }
 
 pub fn toggle(editor: View<Editor>, _: &ToggleOutline, cx: &mut WindowContext) {
-    let outline = editor
-        .read(cx)
-        .buffer()
-        .read(cx)
-        .snapshot(cx)
-        .outline(Some(cx.theme().syntax()));
+    let outline = buffer_outline(&editor, cx);
 
     if let Some((workspace, outline)) = editor.read(cx).workspace().zip(outline) {
         workspace.update(cx, |workspace, cx| {
@@ -38,8 +41,35 @@ pub fn toggle(editor: View<Editor>, _: &ToggleOutline, cx: &mut WindowContext) {
     }
 }
 
+fn buffer_outline(editor: &View<Editor>, cx: &AppContext) -> Option<Outline<Anchor>> {
+    editor
+        .read(cx)
+        .buffer()
+        .read(cx)
+        .snapshot(cx)
+        .outline(Some(cx.theme().syntax()))
+}
+
+/// Identifies an outline item by its own text and the texts of its ancestors,
+/// which stays the same when the outline is rebuilt after unrelated edits.
+type OutlineItemPath = Vec<String>;
+
+fn outline_item_paths(outline: &Outline<Anchor>) -> Vec<OutlineItemPath> {
+    let mut ancestors = Vec::<&str>::new();
+    outline
+        .items
+        .iter()
+        .map(|item| {
+            ancestors.truncate(item.depth);
+            ancestors.push(&item.text);
+            ancestors.iter().map(|text| text.to_string()).collect()
+        })
+        .collect()
+}
+
 pub struct OutlineView {
     picker: View<Picker<OutlineViewDelegate>>,
+    _editor_subscription: Subscription,
 }
 
 impl FocusableView for OutlineView {
@@ -58,8 +88,14 @@ impl ModalView for OutlineView {
 }
 
 impl Render for OutlineView {
-    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
-        v_flex().w(rems(34.)).child(self.picker.clone())
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("OutlineView")
+            .w(rems(34.))
+            .on_action(cx.listener(Self::toggle_selected_entry))
+            .on_action(cx.listener(Self::expand_all_entries))
+            .on_action(cx.listener(Self::collapse_all_entries))
+            .child(self.picker.clone())
     }
 }
 
@@ -82,10 +118,63 @@ impl OutlineView {
         editor: View<Editor>,
         cx: &mut ViewContext<Self>,
     ) -> OutlineView {
+        let editor_subscription = cx.subscribe(&editor, |this, editor, event, cx| {
+            if let EditorEvent::Reparsed(_) = event {
+                this.rebuild_outline(&editor, cx);
+            }
+        });
         let delegate = OutlineViewDelegate::new(cx.view().downgrade(), outline, editor, cx);
         let picker =
             cx.new_view(|cx| Picker::uniform_list(delegate, cx).max_height(Some(vh(0.75, cx))));
-        OutlineView { picker }
+        OutlineView {
+            picker,
+            _editor_subscription: editor_subscription,
+        }
+    }
+
+    fn rebuild_outline(&mut self, editor: &View<Editor>, cx: &mut ViewContext<Self>) {
+        let Some(outline) = buffer_outline(editor, cx) else {
+            return;
+        };
+        self.picker.update(cx, |picker, cx| {
+            picker.delegate.set_outline(outline);
+            if picker.delegate.last_query.is_empty() {
+                picker.delegate.update_visible_items(cx);
+            } else {
+                picker.refresh(cx);
+            }
+        });
+    }
+
+    fn toggle_selected_entry(&mut self, _: &ToggleSelectedEntry, cx: &mut ViewContext<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            let delegate = &mut picker.delegate;
+            if let Some(item_ix) = delegate
+                .matches
+                .get(delegate.selected_match_index)
+                .map(|mat| mat.candidate_id)
+            {
+                delegate.toggle_item(item_ix, cx);
+            }
+        });
+    }
+
+    fn expand_all_entries(&mut self, _: &ExpandAllEntries, cx: &mut ViewContext<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker.delegate.folded_items.clear();
+            picker.delegate.update_visible_items(cx);
+        });
+    }
+
+    fn collapse_all_entries(&mut self, _: &CollapseAllEntries, cx: &mut ViewContext<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            let delegate = &mut picker.delegate;
+            delegate.folded_items = (0..delegate.outline.items.len())
+                .filter(|&ix| delegate.has_children(ix))
+                .map(|ix| delegate.item_paths[ix].clone())
+                .collect();
+            delegate.update_visible_items(cx);
+        });
     }
 }
 
@@ -93,6 +182,8 @@ struct OutlineViewDelegate {
     outline_view: WeakView<OutlineView>,
     active_editor: View<Editor>,
     outline: Outline<Anchor>,
+    item_paths: Vec<OutlineItemPath>,
+    folded_items: HashSet<OutlineItemPath>,
     selected_match_index: usize,
     prev_scroll_position: Option<Point<f32>>,
     matches: Vec<StringMatch>,
@@ -115,10 +206,89 @@ impl OutlineViewDelegate {
             selected_match_index: 0,
             prev_scroll_position: Some(editor.update(cx, |editor, cx| editor.scroll_position(cx))),
             active_editor: editor,
+            item_paths: outline_item_paths(&outline),
+            folded_items: HashSet::default(),
             outline,
         }
     }
 
+    /// Replaces the outline, keeping the fold state of items that are still present.
+    fn set_outline(&mut self, outline: Outline<Anchor>) {
+        self.item_paths = outline_item_paths(&outline);
+        self.folded_items
+            .retain(|path| self.item_paths.contains(path));
+        self.outline = outline;
+    }
+
+    fn has_children(&self, item_ix: usize) -> bool {
+        let depth = self.outline.items[item_ix].depth;
+        self.outline
+            .items
+            .get(item_ix + 1)
+            .map_or(false, |next_item| next_item.depth > depth)
+    }
+
+    fn is_folded(&self, item_ix: usize) -> bool {
+        self.folded_items.contains(&self.item_paths[item_ix])
+    }
+
+    fn toggle_item(&mut self, item_ix: usize, cx: &mut ViewContext<Picker<OutlineViewDelegate>>) {
+        if !self.last_query.is_empty() || !self.has_children(item_ix) {
+            return;
+        }
+        let path = &self.item_paths[item_ix];
+        if !self.folded_items.remove(path) {
+            self.folded_items.insert(path.clone());
+        }
+        self.update_visible_items(cx);
+    }
+
+    /// Matches every item that is not nested inside a folded item. Folding only
+    /// applies to the unfiltered outline, searching still finds nested items.
+    fn visible_matches(&self) -> Vec<StringMatch> {
+        let mut matches = Vec::new();
+        let mut folded_depth = None;
+        for (ix, item) in self.outline.items.iter().enumerate() {
+            if let Some(depth) = folded_depth {
+                if item.depth > depth {
+                    continue;
+                }
+                folded_depth = None;
+            }
+            if self.is_folded(ix) {
+                folded_depth = Some(item.depth);
+            }
+            matches.push(StringMatch {
+                candidate_id: ix,
+                score: Default::default(),
+                positions: Default::default(),
+                string: Default::default(),
+            });
+        }
+        matches
+    }
+
+    /// Recomputes the unfiltered matches after a fold state change, keeping the selection
+    /// on the same item, or on its folded ancestor if the item became hidden.
+    fn update_visible_items(&mut self, cx: &mut ViewContext<Picker<OutlineViewDelegate>>) {
+        if !self.last_query.is_empty() {
+            return;
+        }
+        let selected_item_ix = self
+            .matches
+            .get(self.selected_match_index)
+            .map(|mat| mat.candidate_id);
+        self.matches = self.visible_matches();
+        self.selected_match_index = selected_item_ix
+            .and_then(|item_ix| {
+                self.matches
+                    .iter()
+                    .rposition(|mat| mat.candidate_id <= item_ix)
+            })
+            .unwrap_or(0);
+        cx.notify();
+    }
+
     fn restore_active_editor(&mut self, cx: &mut WindowContext) {
         self.active_editor.update(cx, |editor, cx| {
             editor.clear_row_highlights::<OutlineRowHighlights>();
@@ -181,18 +351,7 @@ impl PickerDelegate for OutlineViewDelegate {
         let selected_index;
         if query.is_empty() {
             self.restore_active_editor(cx);
-            self.matches = self
-                .outline
-                .items
-                .iter()
-                .enumerate()
-                .map(|(index, _)| StringMatch {
-                    candidate_id: index,
-                    score: Default::default(),
-                    positions: Default::default(),
-                    string: Default::default(),
-                })
-                .collect();
+            self.matches = self.visible_matches();
 
             let (buffer, cursor_offset) = self.active_editor.update(cx, |editor, cx| {
                 let buffer = editor.buffer().read(cx).snapshot(cx);
@@ -200,11 +359,11 @@ impl PickerDelegate for OutlineViewDelegate {
                 (buffer, cursor_offset)
             });
             selected_index = self
-                .outline
-                .items
+                .matches
                 .iter()
                 .enumerate()
-                .map(|(ix, item)| {
+                .map(|(ix, mat)| {
+                    let item = &self.outline.items[mat.candidate_id];
                     let range = item.range.to_offset(&buffer);
                     let distance_to_closest_endpoint = cmp::min(
                         (range.start as isize - cursor_offset as isize).abs(),
@@ -271,7 +430,13 @@ impl PickerDelegate for OutlineViewDelegate {
         cx: &mut ViewContext<Picker<Self>>,
     ) -> Option<Self::ListItem> {
         let mat = self.matches.get(ix)?;
-        let outline_item = self.outline.items.get(mat.candidate_id)?;
+        let item_ix = mat.candidate_id;
+        let outline_item = self.outline.items.get(item_ix)?;
+        let disclosure = (self.last_query.is_empty() && self.has_children(item_ix)).then(|| {
+            Disclosure::new(("outline-disclosure", item_ix), !self.is_folded(item_ix)).on_click(
+                cx.listener(move |picker, _, cx| picker.delegate.toggle_item(item_ix, cx)),
+            )
+        });
 
         Some(
             ListItem::new(ix)
@@ -279,9 +444,13 @@ impl PickerDelegate for OutlineViewDelegate {
                 .spacing(ListItemSpacing::Sparse)
                 .selected(selected)
                 .child(
-                    div()
+                    h_flex()
                         .text_ui(cx)
+                        .gap_1()
                         .pl(rems(outline_item.depth as f32))
+                        .when(self.last_query.is_empty(), |this| {
+                            this.child(div().w_4().children(disclosure))
+                        })
                         .child(language::render_item(outline_item, mat.ranges(), cx)),
                 ),
         )
@@ -416,6 +585,112 @@ mod tests {
         assert_single_caret_at_row(&editor, expected_first_highlighted_row, cx);
     }
 
+    #[gpui::test]
+    async fn test_outline_view_fold_state(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/dir",
+            json!({
+                "a.rs": indoc!{"
+                    struct SingleLine;
+                    struct MultiLine {
+                        field_1: i32,
+                        field_2: i32,
+                    }
+                "}
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+        project.read_with(cx, |project, _| project.languages().add(rust_lang()));
+
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let worktree_id = workspace.update(cx, |workspace, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees(cx).next().unwrap().read(cx).id()
+            })
+        });
+        let buffer = project
+            .update(cx, |project, cx| project.open_local_buffer("/dir/a.rs", cx))
+            .await
+            .unwrap();
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_path((worktree_id, "a.rs"), None, true, cx)
+            })
+            .await
+            .unwrap();
+
+        let outline_view = open_outline_view(&workspace, cx);
+        assert_eq!(
+            outline_names(&outline_view, cx),
+            vec![
+                "struct SingleLine",
+                "struct MultiLine",
+                "field_1",
+                "field_2"
+            ],
+        );
+
+        cx.dispatch_action(menu::SelectNext);
+        cx.dispatch_action(ToggleSelectedEntry);
+        assert_eq!(
+            outline_names(&outline_view, cx),
+            vec!["struct SingleLine", "struct MultiLine"],
+            "Children of a folded item should not be rendered"
+        );
+        assert_eq!(selected_name(&outline_view, cx), "struct MultiLine");
+
+        buffer.update(cx, |buffer, cx| {
+            let end = buffer.len();
+            buffer.edit([(end..end, "struct Added;\n")], None, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            outline_names(&outline_view, cx),
+            vec!["struct SingleLine", "struct MultiLine", "struct Added"],
+            "Fold state should be preserved when the outline is rebuilt"
+        );
+
+        cx.dispatch_action(ToggleSelectedEntry);
+        assert_eq!(
+            outline_names(&outline_view, cx),
+            vec![
+                "struct SingleLine",
+                "struct MultiLine",
+                "field_1",
+                "field_2",
+                "struct Added"
+            ],
+        );
+
+        cx.dispatch_action(menu::SelectNext);
+        cx.dispatch_action(CollapseAllEntries);
+        assert_eq!(
+            outline_names(&outline_view, cx),
+            vec!["struct SingleLine", "struct MultiLine", "struct Added"],
+        );
+        assert_eq!(
+            selected_name(&outline_view, cx),
+            "struct MultiLine",
+            "Selection should move to the folded parent of a hidden item"
+        );
+
+        cx.dispatch_action(ExpandAllEntries);
+        assert_eq!(
+            outline_names(&outline_view, cx),
+            vec![
+                "struct SingleLine",
+                "struct MultiLine",
+                "field_1",
+                "field_2",
+                "struct Added"
+            ],
+        );
+    }
+
     fn open_outline_view(
         workspace: &View<Workspace>,
         cx: &mut VisualTestContext,
@@ -453,6 +728,17 @@ mod tests {
         })
     }
 
+    fn selected_name(
+        outline_view: &View<Picker<OutlineViewDelegate>>,
+        cx: &mut VisualTestContext,
+    ) -> String {
+        outline_view.update(cx, |outline_view, _| {
+            let delegate = &outline_view.delegate;
+            let mat = &delegate.matches[delegate.selected_match_index];
+            delegate.outline.items[mat.candidate_id].text.clone()
+        })
+    }
+
     fn highlighted_display_rows(editor: &View<Editor>, cx: &mut VisualTestContext) -> Vec<u32> {
         editor.update(cx, |editor, cx| {
             editor
@@ -242,6 +242,14 @@ pub fn embeddings_dir() -> &'static PathBuf {
     })
 }
 
+/// Returns the path to the chat outbox directory.
+///
+/// This is where channel messages that haven't been delivered yet are stored.
+pub fn chat_outbox_dir() -> &'static PathBuf {
+    static CHAT_OUTBOX_DIR: OnceLock<PathBuf> = OnceLock::new();
+    CHAT_OUTBOX_DIR.get_or_init(|| support_dir().join("chat_outbox"))
+}
+
 /// Returns the path to the languages directory.
 ///
 /// This is where language servers are downloaded to for languages built-in to Zed.
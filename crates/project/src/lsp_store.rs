@@ -18,10 +18,11 @@ use async_trait::async_trait;
 use client::{proto, TypedEnvelope};
 use collections::{btree_map, BTreeMap, HashMap, HashSet};
 use futures::{
+    channel::mpsc,
     future::{join_all, Shared},
     select,
     stream::FuturesUnordered,
-    AsyncWriteExt, Future, FutureExt, StreamExt,
+    AsyncWriteExt, Future, FutureExt, Stream, StreamExt,
 };
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use gpui::{
@@ -36,11 +37,12 @@ use language::{
     },
     markdown, point_to_lsp, prepare_completion_documentation,
     proto::{deserialize_anchor, deserialize_version, serialize_anchor, serialize_version},
-    range_from_lsp, Bias, Buffer, BufferSnapshot, CachedLspAdapter, CodeLabel, Diagnostic,
-    DiagnosticEntry, DiagnosticSet, Diff, Documentation, File as _, Language, LanguageName,
-    LanguageRegistry, LanguageServerBinaryStatus, LanguageServerName, LanguageToolchainStore,
-    LocalFile, LspAdapter, LspAdapterDelegate, Patch, PointUtf16, TextBufferSnapshot, ToOffset,
-    ToPointUtf16, Transaction, Unclipped,
+    range_from_lsp, whitespace_diagnostics, Bias, Buffer, BufferSnapshot, CachedLspAdapter,
+    CodeLabel, Diagnostic, DiagnosticEntry, DiagnosticSet, Diff, Documentation, File as _,
+    Language, LanguageName, LanguageRegistry, LanguageServerBinaryStatus, LanguageServerName,
+    LanguageToolchainStore, LocalFile, LspAdapter, LspAdapterDelegate, Patch, PointUtf16,
+    TextBufferSnapshot, ToOffset, ToPointUtf16, Transaction, Unclipped,
+    WHITESPACE_DIAGNOSTICS_SOURCE,
 };
 use lsp::{
     CodeActionKind, CompletionContext, DiagnosticSeverity, DiagnosticTag,
@@ -69,6 +71,7 @@ use std::{
     iter, mem,
     ops::{ControlFlow, Range},
     path::{self, Path, PathBuf},
+    pin::Pin,
     str,
     sync::Arc,
     time::{Duration, Instant},
@@ -91,6 +94,9 @@ pub use worktree::{
 const SERVER_LAUNCHING_BEFORE_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 pub const SERVER_PROGRESS_THROTTLE_TIMEOUT: Duration = Duration::from_millis(100);
 
+/// How long to wait after a buffer is edited before re-running the built-in whitespace linter.
+pub const WHITESPACE_DIAGNOSTICS_DEBOUNCE: Duration = Duration::from_millis(100);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FormatTrigger {
     Save,
@@ -145,6 +151,10 @@ pub struct LocalLspStore {
         HashMap<LanguageServerId, (LanguageServerName, Arc<LanguageServer>)>,
     prettier_store: Model<PrettierStore>,
     current_lsp_settings: HashMap<LanguageServerName, LspSettings>,
+    /// The id under which the built-in whitespace linter publishes its diagnostics,
+    /// allocated the first time the linter runs.
+    whitespace_diagnostics_server_id: Option<LanguageServerId>,
+    whitespace_diagnostics_tasks: HashMap<BufferId, Task<()>>,
     _subscription: gpui::Subscription,
 }
 
@@ -731,6 +741,24 @@ pub struct LspStore {
     >,
 }
 
+/// The symbol batches returned by [`LspStore::stream_symbols`]. Dropping it cancels any
+/// requests that are still in flight.
+pub struct SymbolBatches {
+    batches: mpsc::UnboundedReceiver<Result<Vec<Symbol>>>,
+    _task: Task<()>,
+}
+
+impl Stream for SymbolBatches {
+    type Item = Result<Vec<Symbol>>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.batches.poll_next_unpin(cx)
+    }
+}
+
 pub enum LspStoreEvent {
     LanguageServerAdded(LanguageServerId, LanguageServerName, Option<WorktreeId>),
     LanguageServerRemoved(LanguageServerId),
@@ -906,6 +934,8 @@ impl LspStore {
                 language_server_watcher_registrations: Default::default(),
                 current_lsp_settings: ProjectSettings::get_global(cx).lsp.clone(),
                 buffers_being_formatted: Default::default(),
+                whitespace_diagnostics_server_id: None,
+                whitespace_diagnostics_tasks: Default::default(),
                 prettier_store,
                 environment,
                 http_client,
@@ -1109,6 +1139,7 @@ impl LspStore {
     ) {
         match event {
             language::BufferEvent::Edited { .. } => {
+                self.refresh_whitespace_diagnostics(&buffer, WHITESPACE_DIAGNOSTICS_DEBOUNCE, cx);
                 self.on_buffer_edited(buffer, cx);
             }
 
@@ -1135,7 +1166,13 @@ impl LspStore {
         .detach();
 
         self.register_buffer_with_language_servers(buffer, cx);
+        self.refresh_whitespace_diagnostics(buffer, Duration::ZERO, cx);
         cx.observe_release(buffer, |this, buffer, cx| {
+            if let Some(local) = this.as_local_mut() {
+                local
+                    .whitespace_diagnostics_tasks
+                    .remove(&buffer.remote_id());
+            }
             if let Some(file) = File::from_dyn(buffer.file()) {
                 if file.is_local() {
                     let uri = lsp::Url::from_file_path(file.abs_path(cx)).unwrap();
@@ -1429,6 +1466,10 @@ impl LspStore {
     }
 
     fn on_settings_changed(&mut self, cx: &mut ModelContext<Self>) {
+        for buffer in self.buffer_store.read(cx).buffers().collect::<Vec<_>>() {
+            self.refresh_whitespace_diagnostics(&buffer, Duration::ZERO, cx);
+        }
+
         let mut language_servers_to_start = Vec::new();
         let mut language_formatters_to_check = Vec::new();
         for buffer in self.buffer_store.read(cx).buffers() {
@@ -2719,30 +2760,50 @@ impl LspStore {
     }
 
     pub fn symbols(&self, query: &str, cx: &mut ModelContext<Self>) -> Task<Result<Vec<Symbol>>> {
+        let mut batches = self.stream_symbols(query, cx);
+        cx.spawn(|_, _| async move {
+            let mut symbols = Vec::new();
+            while let Some(batch) = batches.next().await {
+                symbols.extend(batch?);
+            }
+            Ok(symbols)
+        })
+    }
+
+    /// Searches the workspace for symbols matching the given query, yielding a batch
+    /// of symbols as soon as each language server responds.
+    ///
+    /// Dropping the returned receiver cancels any requests that are still in flight.
+    pub fn stream_symbols(&self, query: &str, cx: &mut ModelContext<Self>) -> SymbolBatches {
         let language_registry = self.languages.clone();
+        let (tx, rx) = mpsc::unbounded();
 
-        if let Some((upstream_client, project_id)) = self.upstream_client().as_ref() {
+        let task = if let Some((upstream_client, project_id)) = self.upstream_client().as_ref() {
             let request = upstream_client.request(proto::GetProjectSymbols {
                 project_id: *project_id,
                 query: query.to_string(),
             });
             cx.foreground_executor().spawn(async move {
-                let response = request.await?;
-                let mut symbols = Vec::new();
-                let core_symbols = response
-                    .symbols
-                    .into_iter()
-                    .filter_map(|symbol| Self::deserialize_symbol(symbol).log_err())
-                    .collect::<Vec<_>>();
-                populate_labels_for_symbols(
-                    core_symbols,
-                    &language_registry,
-                    None,
-                    None,
-                    &mut symbols,
-                )
+                let symbols = async {
+                    let response = request.await?;
+                    let mut symbols = Vec::new();
+                    let core_symbols = response
+                        .symbols
+                        .into_iter()
+                        .filter_map(|symbol| Self::deserialize_symbol(symbol).log_err())
+                        .collect::<Vec<_>>();
+                    populate_labels_for_symbols(
+                        core_symbols,
+                        &language_registry,
+                        None,
+                        None,
+                        &mut symbols,
+                    )
+                    .await;
+                    anyhow::Ok(symbols)
+                }
                 .await;
-                Ok(symbols)
+                tx.unbounded_send(symbols).ok();
             })
         } else {
             struct WorkspaceSymbolsResult {
@@ -2753,7 +2814,7 @@ impl LspStore {
                 lsp_symbols: Vec<(String, SymbolKind, lsp::Location)>,
             }
 
-            let mut requests = Vec::new();
+            let mut requests = FuturesUnordered::new();
             for ((worktree_id, _), server_id) in self.language_server_ids.iter() {
                 let Some(worktree_handle) = self
                     .worktree_store
@@ -2822,15 +2883,13 @@ impl LspStore {
             }
 
             cx.spawn(move |this, mut cx| async move {
-                let responses = futures::future::join_all(requests).await;
-                let this = match this.upgrade() {
-                    Some(this) => this,
-                    None => return Ok(Vec::new()),
-                };
+                while let Some(result) = requests.next().await {
+                    let Some(this) = this.upgrade() else {
+                        break;
+                    };
 
-                let mut symbols = Vec::new();
-                for result in responses {
-                    let core_symbols = this.update(&mut cx, |this, cx| {
+                    let mut symbols = Vec::new();
+                    let Ok(core_symbols) = this.update(&mut cx, |this, cx| {
                         result
                             .lsp_symbols
                             .into_iter()
@@ -2868,7 +2927,9 @@ impl LspStore {
                                 })
                             })
                             .collect()
-                    })?;
+                    }) else {
+                        break;
+                    };
 
                     populate_labels_for_symbols(
                         core_symbols,
@@ -2878,10 +2939,17 @@ impl LspStore {
                         &mut symbols,
                     )
                     .await;
-                }
 
-                Ok(symbols)
+                    if tx.unbounded_send(Ok(symbols)).is_err() {
+                        break;
+                    }
+                }
             })
+        };
+
+        SymbolBatches {
+            batches: rx,
+            _task: task,
         }
     }
 
@@ -3025,6 +3093,99 @@ impl LspStore {
         None
     }
 
+    /// Re-runs the built-in whitespace linter on the given buffer after `delay`, clearing
+    /// its diagnostics if the linter has been disabled for the buffer's language.
+    ///
+    /// A pending run for the same buffer is replaced, so bursts of edits are linted once.
+    fn refresh_whitespace_diagnostics(
+        &mut self,
+        buffer_handle: &Model<Buffer>,
+        delay: Duration,
+        cx: &mut ModelContext<Self>,
+    ) -> Option<()> {
+        let buffer = buffer_handle.read(cx);
+        let buffer_id = buffer.remote_id();
+        let file = File::from_dyn(buffer.file())?;
+        let abs_path = file.as_local()?.abs_path(cx);
+        let settings = language_settings(buffer.language().map(|l| l.name()), buffer.file(), cx)
+            .whitespace_diagnostics;
+
+        let server_id = if settings.is_enabled() {
+            self.whitespace_diagnostics_server_id()?
+        } else {
+            let server_id = self.as_local()?.whitespace_diagnostics_server_id?;
+            let has_whitespace_diagnostics = self
+                .diagnostic_summaries
+                .get(&file.worktree_id(cx))
+                .and_then(|summaries| summaries.get(&file.path))
+                .map_or(false, |summaries| summaries.contains_key(&server_id));
+            if !has_whitespace_diagnostics {
+                self.as_local_mut()?
+                    .whitespace_diagnostics_tasks
+                    .remove(&buffer_id);
+                return None;
+            }
+            server_id
+        };
+
+        let buffer_handle = buffer_handle.downgrade();
+        let task = cx.spawn(move |this, mut cx| async move {
+            if !delay.is_zero() {
+                cx.background_executor().timer(delay).await;
+            }
+            this.update(&mut cx, |this, cx| {
+                let buffer = buffer_handle.upgrade()?;
+                let diagnostics = whitespace_diagnostics(buffer.read(cx).as_rope(), settings)
+                    .into_iter()
+                    .map(|entry| DiagnosticEntry {
+                        range: Unclipped(entry.range.start)..Unclipped(entry.range.end),
+                        diagnostic: entry.diagnostic,
+                    })
+                    .collect();
+                this.update_diagnostic_entries(server_id, abs_path, None, diagnostics, cx)
+                    .log_err()
+            })
+            .ok();
+        });
+        self.as_local_mut()?
+            .whitespace_diagnostics_tasks
+            .insert(buffer_id, task);
+        None
+    }
+
+    /// Returns the id under which the built-in whitespace linter publishes diagnostics,
+    /// registering it with collaborators the first time it is needed.
+    fn whitespace_diagnostics_server_id(&mut self) -> Option<LanguageServerId> {
+        if let Some(server_id) = self.as_local()?.whitespace_diagnostics_server_id {
+            return Some(server_id);
+        }
+
+        let server_id = self.languages.next_language_server_id();
+        self.as_local_mut()?.whitespace_diagnostics_server_id = Some(server_id);
+        self.language_server_statuses.insert(
+            server_id,
+            LanguageServerStatus {
+                name: WHITESPACE_DIAGNOSTICS_SOURCE.to_string(),
+                pending_work: Default::default(),
+                has_pending_diagnostic_updates: false,
+                progress_tokens: Default::default(),
+            },
+        );
+        if let Some((downstream_client, project_id)) = self.downstream_client.as_ref() {
+            downstream_client
+                .send(proto::StartLanguageServer {
+                    project_id: *project_id,
+                    server: Some(proto::LanguageServer {
+                        id: server_id.0 as u64,
+                        name: WHITESPACE_DIAGNOSTICS_SOURCE.to_string(),
+                        worktree_id: None,
+                    }),
+                })
+                .log_err();
+        }
+        Some(server_id)
+    }
+
     pub fn on_buffer_saved(
         &mut self,
         buffer: Model<Buffer>,
@@ -6456,6 +6617,8 @@ impl LspStore {
         let mut primary_diagnostic_group_ids = HashMap::default();
         let mut sources_by_group_id = HashMap::default();
         let mut supporting_diagnostics = HashMap::default();
+        let mut previous_group_ids =
+            self.previous_diagnostic_group_ids(language_server_id, &abs_path, cx);
 
         // Ensure that primary diagnostics are always the most severe
         params.diagnostics.sort_by_key(|item| item.severity);
@@ -6490,7 +6653,13 @@ impl LspStore {
                     (diagnostic.severity, is_unnecessary),
                 );
             } else {
-                let group_id = post_inc(&mut self.next_diagnostic_group_id);
+                let group_id = previous_group_ids
+                    .remove(&(
+                        diagnostic.source.clone(),
+                        diagnostic.message.trim().to_string(),
+                        range.clone(),
+                    ))
+                    .unwrap_or_else(|| post_inc(&mut self.next_diagnostic_group_id));
                 let is_disk_based =
                     source.map_or(false, |source| disk_based_sources.contains(source));
 
@@ -6563,6 +6732,45 @@ impl LspStore {
         Ok(())
     }
 
+    /// Returns the group ids of the primary diagnostics that were last published by
+    /// the given server for the given path, keyed by their source, message and range,
+    /// so that diagnostics which survive a re-publish keep their group.
+    fn previous_diagnostic_group_ids(
+        &self,
+        server_id: LanguageServerId,
+        abs_path: &Path,
+        cx: &AppContext,
+    ) -> HashMap<(Option<String>, String, Range<Unclipped<PointUtf16>>), usize> {
+        let mut group_ids = HashMap::default();
+        let Some((worktree, relative_path)) =
+            self.worktree_store.read(cx).find_worktree(abs_path, cx)
+        else {
+            return group_ids;
+        };
+        let Some(diagnostics_by_server_id) = self
+            .diagnostics
+            .get(&worktree.read(cx).id())
+            .and_then(|diagnostics| diagnostics.get(relative_path.as_path()))
+        else {
+            return group_ids;
+        };
+        if let Ok(ix) = diagnostics_by_server_id.binary_search_by_key(&server_id, |e| e.0) {
+            for entry in &diagnostics_by_server_id[ix].1 {
+                if entry.diagnostic.is_primary {
+                    group_ids.insert(
+                        (
+                            entry.diagnostic.source.clone(),
+                            entry.diagnostic.message.clone(),
+                            entry.range.clone(),
+                        ),
+                        entry.diagnostic.group_id,
+                    );
+                }
+            }
+        }
+        group_ids
+    }
+
     fn insert_newly_running_language_server(
         &mut self,
         language: LanguageName,
@@ -99,7 +99,7 @@ pub use worktree::{
 pub use buffer_store::ProjectTransaction;
 pub use lsp_store::{
     DiagnosticSummary, LanguageServerLogType, LanguageServerProgress, LanguageServerPromptRequest,
-    LanguageServerStatus, LanguageServerToQuery, LspStore, LspStoreEvent,
+    LanguageServerStatus, LanguageServerToQuery, LspStore, LspStoreEvent, SymbolBatches,
     SERVER_PROGRESS_THROTTLE_TIMEOUT,
 };
 pub use toolchain_store::ToolchainStore;
@@ -1620,6 +1620,25 @@ impl Project {
         }
     }
 
+    /// Removes the given guest from this shared project. Only the host can remove guests.
+    pub fn remove_guest(
+        &self,
+        peer_id: proto::PeerId,
+        cx: &ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let ProjectClientState::Shared { remote_id, .. } = self.client_state else {
+            return Task::ready(Err(anyhow!("can only remove guests from a shared project")));
+        };
+        let request = self.client.request(proto::RemoveProjectGuest {
+            project_id: remote_id,
+            peer_id: Some(peer_id),
+        });
+        cx.spawn(|_, _| async move {
+            request.await?;
+            Ok(())
+        })
+    }
+
     pub fn disconnected_from_host(&mut self, cx: &mut ModelContext<Self>) {
         if self.is_disconnected(cx) {
             return;
@@ -2599,6 +2618,11 @@ impl Project {
             .update(cx, |lsp_store, cx| lsp_store.symbols(query, cx))
     }
 
+    pub fn stream_symbols(&self, query: &str, cx: &mut ModelContext<Self>) -> SymbolBatches {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.stream_symbols(query, cx))
+    }
+
     pub fn open_buffer_for_symbol(
         &mut self,
         symbol: &Symbol,
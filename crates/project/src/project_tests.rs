@@ -6,6 +6,7 @@ use http_client::Url;
 use language::{
     language_settings::{
         language_settings, AllLanguageSettings, LanguageSettingsContent, SoftWrap,
+        WhitespaceDiagnosticsSettingsContent,
     },
     tree_sitter_rust, tree_sitter_typescript, Diagnostic, DiagnosticSet, FakeLspAdapter,
     LanguageConfig, LanguageMatcher, LanguageName, LineEnding, OffsetRangeExt, Point, ToPoint,
@@ -771,6 +772,75 @@ async fn test_managing_language_servers(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_project_specific_initialization_options(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/the-root",
+        json!({
+            ".zed": {
+                "settings.json": r#"{
+                    "lsp": {
+                        "the-language-server": {
+                            "initialization_options": { "checkOnSave": false }
+                        }
+                    }
+                }"#
+            },
+            "a.rs": "",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/the-root".as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+
+    let initialization_options = Arc::new(Mutex::new(None));
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            name: "the-language-server",
+            initialization_options: Some(json!({
+                "cargo": { "features": "all" },
+                "checkOnSave": true,
+            })),
+            initializer: Some(Box::new({
+                let initialization_options = initialization_options.clone();
+                move |server| {
+                    let initialization_options = initialization_options.clone();
+                    server.handle_request::<lsp::request::Initialize, _, _>(move |params, _| {
+                        *initialization_options.lock() = params.initialization_options;
+                        async move { Ok(lsp::InitializeResult::default()) }
+                    });
+                }
+            })),
+            ..Default::default()
+        },
+    );
+    cx.executor().run_until_parked();
+
+    let _buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer("/the-root/a.rs", cx)
+        })
+        .await
+        .unwrap();
+    let _fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    // The project's options are merged into the adapter's, taking precedence over them.
+    assert_eq!(
+        initialization_options.lock().clone(),
+        Some(json!({
+            "cargo": { "features": "all" },
+            "checkOnSave": false,
+        }))
+    );
+}
+
 #[gpui::test]
 async fn test_reporting_fs_changes_to_language_servers(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -1494,6 +1564,172 @@ async fn test_restarting_server_with_diagnostics_published(cx: &mut gpui::TestAp
     });
 }
 
+#[gpui::test]
+async fn test_whitespace_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree("/dir", json!({ "a.txt": "one  \ntwo\nthree" }))
+        .await;
+
+    let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer("/dir/a.txt", cx)
+        })
+        .await
+        .unwrap();
+
+    let whitespace_diagnostics = |cx: &mut gpui::TestAppContext| {
+        cx.executor().run_until_parked();
+        buffer.update(cx, |buffer, _| {
+            buffer
+                .snapshot()
+                .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+                .map(|entry| (entry.range, entry.diagnostic.message.clone()))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // The linter is disabled by default.
+    assert_eq!(
+        whitespace_diagnostics(cx),
+        Vec::<(Range<Point>, String)>::new()
+    );
+
+    let set_whitespace_settings =
+        |settings: WhitespaceDiagnosticsSettingsContent, cx: &mut gpui::TestAppContext| {
+            cx.update(|cx| {
+                SettingsStore::update_global(cx, |store, cx| {
+                    store.update_user_settings::<AllLanguageSettings>(cx, |settings_content| {
+                        settings_content.defaults.whitespace_diagnostics = Some(settings);
+                    });
+                })
+            });
+        };
+
+    set_whitespace_settings(
+        WhitespaceDiagnosticsSettingsContent {
+            trailing_whitespace: Some(true),
+            final_newline: Some(true),
+        },
+        cx,
+    );
+    assert_eq!(
+        whitespace_diagnostics(cx),
+        [
+            (
+                Point::new(0, 3)..Point::new(0, 5),
+                "trailing whitespace".to_string()
+            ),
+            (
+                Point::new(2, 4)..Point::new(2, 5),
+                "missing newline at end of file".to_string()
+            ),
+        ]
+    );
+    project.update(cx, |project, cx| {
+        assert_eq!(
+            project.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 0,
+                warning_count: 2,
+            }
+        );
+    });
+
+    // Diagnostics are recomputed once the buffer stops being edited.
+    buffer.update(cx, |buffer, cx| {
+        let len = buffer.len();
+        buffer.edit([(len..len, "\n")], None, cx);
+    });
+    assert_eq!(whitespace_diagnostics(cx).len(), 2);
+    cx.executor()
+        .advance_clock(lsp_store::WHITESPACE_DIAGNOSTICS_DEBOUNCE);
+    assert_eq!(
+        whitespace_diagnostics(cx),
+        [(
+            Point::new(0, 3)..Point::new(0, 5),
+            "trailing whitespace".to_string()
+        )]
+    );
+
+    // Disabling the linter clears its diagnostics.
+    set_whitespace_settings(
+        WhitespaceDiagnosticsSettingsContent {
+            trailing_whitespace: Some(false),
+            final_newline: Some(false),
+        },
+        cx,
+    );
+    assert_eq!(
+        whitespace_diagnostics(cx),
+        Vec::<(Range<Point>, String)>::new()
+    );
+    project.update(cx, |project, cx| {
+        assert_eq!(
+            project.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 0,
+                warning_count: 0,
+            }
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_partially_overridden_whitespace_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            ".zed": {
+                "settings.json": r#"{ "whitespace_diagnostics": { "final_newline": false } }"#
+            },
+            "a.txt": "one  \ntwo\nthree",
+        }),
+    )
+    .await;
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings::<AllLanguageSettings>(cx, |settings_content| {
+                settings_content.defaults.whitespace_diagnostics =
+                    Some(WhitespaceDiagnosticsSettingsContent {
+                        trailing_whitespace: Some(true),
+                        final_newline: Some(true),
+                    });
+            });
+        })
+    });
+
+    let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+    cx.executor().run_until_parked();
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer("/dir/a.txt", cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+
+    // The project only turns off the final newline check, so trailing whitespace is still reported.
+    buffer.update(cx, |buffer, _| {
+        assert_eq!(
+            buffer
+                .snapshot()
+                .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+                .map(|entry| (entry.range, entry.diagnostic.message.clone()))
+                .collect::<Vec<_>>(),
+            [(
+                Point::new(0, 3)..Point::new(0, 5),
+                "trailing whitespace".to_string()
+            )]
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_restarted_server_reporting_invalid_buffer_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -1875,7 +2111,7 @@ async fn test_transforming_diagnostics(cx: &mut gpui::TestAppContext) {
                         severity: DiagnosticSeverity::WARNING,
                         message: "unreachable statement".to_string(),
                         is_disk_based: true,
-                        group_id: 4,
+                        group_id: 3,
                         is_primary: true,
                         ..Default::default()
                     }
@@ -1887,7 +2123,7 @@ async fn test_transforming_diagnostics(cx: &mut gpui::TestAppContext) {
                         severity: DiagnosticSeverity::ERROR,
                         message: "undefined variable 'A'".to_string(),
                         is_disk_based: true,
-                        group_id: 3,
+                        group_id: 0,
                         is_primary: true,
                         ..Default::default()
                     },
@@ -1967,7 +2203,7 @@ async fn test_transforming_diagnostics(cx: &mut gpui::TestAppContext) {
                         severity: DiagnosticSeverity::WARNING,
                         message: "undefined variable 'A'".to_string(),
                         is_disk_based: true,
-                        group_id: 6,
+                        group_id: 0,
                         is_primary: true,
                         ..Default::default()
                     }
@@ -1979,7 +2215,7 @@ async fn test_transforming_diagnostics(cx: &mut gpui::TestAppContext) {
                         severity: DiagnosticSeverity::ERROR,
                         message: "undefined variable 'BB'".to_string(),
                         is_disk_based: true,
-                        group_id: 5,
+                        group_id: 4,
                         is_primary: true,
                         ..Default::default()
                     },
@@ -3894,6 +4130,126 @@ async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_diagnostic_group_ids_are_stable_across_publishes(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            "a.rs": "fn a() { A }\nfn b() { BB }\nfn c() { CCC }\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |p, cx| p.open_local_buffer("/dir/a.rs", cx))
+        .await
+        .unwrap();
+
+    let uri = Url::from_file_path("/dir/a.rs").unwrap();
+    let diagnostic = |row, start, end, severity, message: &str| lsp::Diagnostic {
+        range: lsp::Range::new(lsp::Position::new(row, start), lsp::Position::new(row, end)),
+        severity: Some(severity),
+        message: message.to_string(),
+        source: Some("rustc".to_string()),
+        ..Default::default()
+    };
+    let group_ids = |cx: &mut gpui::TestAppContext| {
+        buffer.update(cx, |buffer, _| {
+            buffer
+                .snapshot()
+                .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+                .map(|entry| (entry.diagnostic.message, entry.diagnostic.group_id))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    project
+        .update(cx, |p, cx| {
+            p.update_diagnostics(
+                LanguageServerId(0),
+                lsp::PublishDiagnosticsParams {
+                    uri: uri.clone(),
+                    diagnostics: vec![
+                        diagnostic(0, 9, 10, DiagnosticSeverity::WARNING, "unused 'A'"),
+                        diagnostic(1, 9, 11, DiagnosticSeverity::WARNING, "unused 'BB'"),
+                    ],
+                    version: None,
+                },
+                &[],
+                cx,
+            )
+        })
+        .unwrap();
+    assert_eq!(
+        group_ids(cx),
+        [
+            ("unused 'A'".to_string(), 0),
+            ("unused 'BB'".to_string(), 1)
+        ]
+    );
+
+    // Re-publish the same diagnostics along with a new, more severe one, which
+    // gets processed first. The existing diagnostics keep their groups.
+    project
+        .update(cx, |p, cx| {
+            p.update_diagnostics(
+                LanguageServerId(0),
+                lsp::PublishDiagnosticsParams {
+                    uri: uri.clone(),
+                    diagnostics: vec![
+                        diagnostic(0, 9, 10, DiagnosticSeverity::WARNING, "unused 'A'"),
+                        diagnostic(1, 9, 11, DiagnosticSeverity::WARNING, "unused 'BB'"),
+                        diagnostic(2, 9, 12, DiagnosticSeverity::ERROR, "undefined 'CCC'"),
+                    ],
+                    version: None,
+                },
+                &[],
+                cx,
+            )
+        })
+        .unwrap();
+    assert_eq!(
+        group_ids(cx),
+        [
+            ("unused 'A'".to_string(), 0),
+            ("unused 'BB'".to_string(), 1),
+            ("undefined 'CCC'".to_string(), 2),
+        ]
+    );
+
+    // A diagnostic whose message changes is treated as a new diagnostic.
+    project
+        .update(cx, |p, cx| {
+            p.update_diagnostics(
+                LanguageServerId(0),
+                lsp::PublishDiagnosticsParams {
+                    uri,
+                    diagnostics: vec![
+                        diagnostic(0, 9, 10, DiagnosticSeverity::WARNING, "unused 'A'"),
+                        diagnostic(1, 9, 11, DiagnosticSeverity::WARNING, "unknown 'BB'"),
+                        diagnostic(2, 9, 12, DiagnosticSeverity::ERROR, "undefined 'CCC'"),
+                    ],
+                    version: None,
+                },
+                &[],
+                cx,
+            )
+        })
+        .unwrap();
+    assert_eq!(
+        group_ids(cx),
+        [
+            ("unused 'A'".to_string(), 0),
+            ("unknown 'BB'".to_string(), 3),
+            ("undefined 'CCC'".to_string(), 2),
+        ]
+    );
+}
+
 #[gpui::test]
 async fn test_rename(cx: &mut gpui::TestAppContext) {
     // hi
@@ -118,11 +118,8 @@ impl SearchQuery {
         let mut query = query.to_string();
         let initial_query = Arc::from(query.as_str());
         if whole_word {
-            let mut word_query = String::new();
-            word_query.push_str("\\b");
-            word_query.push_str(&query);
-            word_query.push_str("\\b");
-            query = word_query
+            // Group the query so that word boundaries apply to every alternation branch.
+            query = format!("\\b(?:{query})\\b");
         }
 
         let multiline = query.contains('\n') || query.contains("\\n") || query.contains("\\s");
@@ -455,6 +452,7 @@ pub fn deserialize_path_matches(glob_set: &str) -> anyhow::Result<PathMatcher> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use gpui::{Context as _, TestAppContext};
 
     #[test]
     fn path_matcher_creation_for_valid_paths() {
@@ -499,4 +497,66 @@ mod tests {
             }
         }
     }
+
+    #[gpui::test]
+    async fn test_search_options(cx: &mut TestAppContext) {
+        let buffer = cx.new_model(|cx| Buffer::local("a.b axb A.B a.bc\nab abc cd", cx));
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+
+        let search = |query: SearchQuery| {
+            let snapshot = snapshot.clone();
+            async move { query.search(&snapshot, None).await }
+        };
+        let text = |query: &str, whole_word, case_sensitive| {
+            SearchQuery::text(
+                query,
+                whole_word,
+                case_sensitive,
+                false,
+                Default::default(),
+                Default::default(),
+                None,
+            )
+            .unwrap()
+        };
+        let regex = |query: &str, whole_word, case_sensitive| {
+            SearchQuery::regex(
+                query,
+                whole_word,
+                case_sensitive,
+                false,
+                Default::default(),
+                Default::default(),
+                None,
+            )
+            .unwrap()
+        };
+
+        // Plain text queries never interpret regex metacharacters.
+        assert_eq!(
+            search(text("a.b", false, false)).await,
+            [0..3, 8..11, 12..15]
+        );
+        assert_eq!(search(text("a.b", false, true)).await, [0..3, 12..15]);
+        assert_eq!(search(text("a.b", true, false)).await, [0..3, 8..11]);
+        assert_eq!(search(text("a.b", true, true)).await, [0..3]);
+
+        assert_eq!(
+            search(regex("a.b", false, false)).await,
+            [0..3, 4..7, 8..11, 12..15]
+        );
+        assert_eq!(
+            search(regex("a.b", false, true)).await,
+            [0..3, 4..7, 12..15]
+        );
+        assert_eq!(search(regex("a.b", true, false)).await, [0..3, 4..7, 8..11]);
+        assert_eq!(search(regex("a.b", true, true)).await, [0..3, 4..7]);
+
+        // Word boundaries apply to every branch of an alternation.
+        assert_eq!(
+            search(regex("ab|cd", false, true)).await,
+            [17..19, 20..22, 24..26]
+        );
+        assert_eq!(search(regex("ab|cd", true, true)).await, [17..19, 24..26]);
+    }
 }
@@ -1,13 +1,14 @@
 use editor::{scroll::Autoscroll, styled_runs_for_code_label, Bias, Editor};
+use futures::StreamExt;
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
-    rems, AppContext, DismissEvent, FontWeight, Model, ParentElement, StyledText, Task, View,
-    ViewContext, WeakView, WindowContext,
+    rems, AppContext, DismissEvent, EventEmitter, FontWeight, Model, ParentElement, StyledText,
+    Task, View, ViewContext, WeakView, WindowContext,
 };
 use ordered_float::OrderedFloat;
 use picker::{Picker, PickerDelegate};
 use project::{Project, Symbol};
-use std::{borrow::Cow, cmp::Reverse, sync::Arc};
+use std::{borrow::Cow, cmp::Reverse, sync::Arc, time::Duration};
 use theme::ActiveTheme;
 use util::ResultExt;
 use workspace::{
@@ -33,6 +34,9 @@ pub fn init(cx: &mut AppContext) {
 
 pub type ProjectSymbols = View<Picker<ProjectSymbolsDelegate>>;
 
+/// How long the query has to stay unchanged before symbols are requested from the language servers.
+pub const SYMBOL_QUERY_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(75);
+
 pub struct ProjectSymbolsDelegate {
     workspace: WeakView<Workspace>,
     project: Model<Project>,
@@ -42,6 +46,7 @@ pub struct ProjectSymbolsDelegate {
     external_match_candidates: Vec<StringMatchCandidate>,
     show_worktree_root_name: bool,
     matches: Vec<StringMatch>,
+    debounce_timeout: Duration,
 }
 
 impl ProjectSymbolsDelegate {
@@ -55,9 +60,16 @@ impl ProjectSymbolsDelegate {
             external_match_candidates: Default::default(),
             matches: Default::default(),
             show_worktree_root_name: false,
+            debounce_timeout: SYMBOL_QUERY_DEBOUNCE_TIMEOUT,
         }
     }
 
+    /// Sets how long the query has to stay unchanged before symbols are requested.
+    pub fn with_debounce_timeout(mut self, debounce_timeout: Duration) -> Self {
+        self.debounce_timeout = debounce_timeout;
+        self
+    }
+
     fn filter(&mut self, query: &str, cx: &mut ViewContext<Picker<Self>>) {
         const MAX_MATCHES: usize = 100;
         let mut visible_matches = cx.background_executor().block(fuzzy::match_strings(
@@ -100,8 +112,42 @@ impl ProjectSymbolsDelegate {
         self.matches = matches;
         self.set_selected_index(0, cx);
     }
+
+    fn set_symbols(
+        &mut self,
+        symbols: Vec<Symbol>,
+        query: &str,
+        cx: &mut ViewContext<Picker<Self>>,
+    ) {
+        let project = self.project.read(cx);
+        let (visible_match_candidates, external_match_candidates) = symbols
+            .iter()
+            .enumerate()
+            .map(|(id, symbol)| {
+                StringMatchCandidate::new(
+                    id,
+                    symbol.label.text[symbol.label.filter_range.clone()].to_string(),
+                )
+            })
+            .partition(|candidate| {
+                project
+                    .entry_for_path(&symbols[candidate.id].path, cx)
+                    .map_or(false, |e| !e.is_ignored)
+            });
+
+        self.visible_match_candidates = visible_match_candidates;
+        self.external_match_candidates = external_match_candidates;
+        self.symbols = symbols;
+        self.filter(query, cx);
+    }
 }
 
+/// Emitted by the project symbols picker each time a language server's
+/// results are added to the list of symbols.
+pub struct SymbolsUpdated;
+
+impl EventEmitter<SymbolsUpdated> for Picker<ProjectSymbolsDelegate> {}
+
 impl PickerDelegate for ProjectSymbolsDelegate {
     type ListItem = ListItem;
     fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
@@ -164,34 +210,40 @@ impl PickerDelegate for ProjectSymbolsDelegate {
     fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()> {
         self.filter(&query, cx);
         self.show_worktree_root_name = self.project.read(cx).visible_worktrees(cx).count() > 1;
-        let symbols = self
-            .project
-            .update(cx, |project, cx| project.symbols(&query, cx));
+        let debounce_timeout = self.debounce_timeout;
         cx.spawn(|this, mut cx| async move {
-            let symbols = symbols.await.log_err();
-            if let Some(symbols) = symbols {
-                this.update(&mut cx, |this, cx| {
-                    let delegate = &mut this.delegate;
-                    let project = delegate.project.read(cx);
-                    let (visible_match_candidates, external_match_candidates) = symbols
-                        .iter()
-                        .enumerate()
-                        .map(|(id, symbol)| {
-                            StringMatchCandidate::new(
-                                id,
-                                symbol.label.text[symbol.label.filter_range.clone()].to_string(),
-                            )
-                        })
-                        .partition(|candidate| {
-                            project
-                                .entry_for_path(&symbols[candidate.id].path, cx)
-                                .map_or(false, |e| !e.is_ignored)
-                        });
+            // The picker drops this task when the query changes, so a burst of keystrokes only
+            // requests symbols for the last query, and responses to earlier ones are discarded.
+            cx.background_executor().timer(debounce_timeout).await;
+            let Ok(mut batches) = this.update(&mut cx, |this, cx| {
+                this.delegate
+                    .project
+                    .update(cx, |project, cx| project.stream_symbols(&query, cx))
+            }) else {
+                return;
+            };
+
+            let mut symbols = Vec::new();
+            let mut received_any = false;
+            while let Some(batch) = batches.next().await {
+                let Some(batch) = batch.log_err() else {
+                    continue;
+                };
+                received_any = true;
+                symbols.extend(batch);
+                let updated = this.update(&mut cx, |this, cx| {
+                    this.delegate.set_symbols(symbols.clone(), &query, cx);
+                    cx.emit(SymbolsUpdated);
+                    cx.notify();
+                });
+                if updated.is_err() {
+                    return;
+                }
+            }
 
-                    delegate.visible_match_candidates = visible_match_candidates;
-                    delegate.external_match_candidates = external_match_candidates;
-                    delegate.symbols = symbols;
-                    delegate.filter(&query, cx);
+            if !received_any {
+                this.update(&mut cx, |this, cx| {
+                    this.delegate.set_symbols(Vec::new(), &query, cx);
                 })
                 .log_err();
             }
@@ -258,13 +310,18 @@ impl PickerDelegate for ProjectSymbolsDelegate {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use futures::StreamExt;
     use gpui::{SemanticVersion, TestAppContext, VisualContext};
     use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher};
     use project::FakeFs;
     use serde_json::json;
     use settings::SettingsStore;
-    use std::{path::Path, sync::Arc};
+    use std::{
+        cell::Cell,
+        path::Path,
+        rc::Rc,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
 
     #[gpui::test]
     async fn test_project_symbols(cx: &mut TestAppContext) {
@@ -358,6 +415,7 @@ mod tests {
             p.update_matches("onex".to_string(), cx);
         });
 
+        cx.executor().advance_clock(SYMBOL_QUERY_DEBOUNCE_TIMEOUT);
         cx.run_until_parked();
         symbols.update(cx, |symbols, _| {
             assert_eq!(symbols.delegate.matches.len(), 0);
@@ -369,6 +427,7 @@ mod tests {
             p.update_matches("on".to_string(), cx);
         });
 
+        cx.executor().advance_clock(SYMBOL_QUERY_DEBOUNCE_TIMEOUT);
         cx.run_until_parked();
         symbols.update(cx, |symbols, _| {
             let delegate = &symbols.delegate;
@@ -383,12 +442,234 @@ mod tests {
             p.update_matches("".to_string(), cx);
         });
 
+        cx.executor().advance_clock(SYMBOL_QUERY_DEBOUNCE_TIMEOUT);
         cx.run_until_parked();
         symbols.update(cx, |symbols, _| {
             assert_eq!(symbols.delegate.matches.len(), 0);
         });
     }
 
+    #[gpui::test]
+    async fn test_project_symbols_are_streamed(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/dir", json!({ "test.rs": "" })).await;
+
+        let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fast_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                name: "fast-server",
+                ..Default::default()
+            },
+        );
+        let mut slow_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                name: "slow-server",
+                ..Default::default()
+            },
+        );
+
+        let _buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer("/dir/test.rs", cx)
+            })
+            .await
+            .unwrap();
+
+        // One server responds right away, while the other takes a second.
+        let fast_server = fast_servers.next().await.unwrap();
+        fast_server.handle_request::<lsp::WorkspaceSymbolRequest, _, _>(|_, _| async move {
+            Ok(Some(lsp::WorkspaceSymbolResponse::Flat(vec![symbol(
+                "one",
+                "/dir/test.rs",
+            )])))
+        });
+        let slow_server = slow_servers.next().await.unwrap();
+        slow_server.handle_request::<lsp::WorkspaceSymbolRequest, _, _>(|_, cx| {
+            let executor = cx.background_executor().clone();
+            async move {
+                executor.timer(Duration::from_secs(1)).await;
+                Ok(Some(lsp::WorkspaceSymbolResponse::Flat(vec![symbol(
+                    "only",
+                    "/dir/test.rs",
+                )])))
+            }
+        });
+
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let symbols = cx.new_view(|cx| {
+            Picker::uniform_list(
+                ProjectSymbolsDelegate::new(workspace.downgrade(), project.clone()),
+                cx,
+            )
+        });
+        let updates = Rc::new(Cell::new(0));
+        cx.update(|cx| {
+            let updates = updates.clone();
+            cx.subscribe(&symbols, move |_, _: &SymbolsUpdated, _| {
+                updates.set(updates.get() + 1)
+            })
+            .detach();
+        });
+
+        symbols.update(cx, |p, cx| p.update_matches("o".to_string(), cx));
+        cx.executor().advance_clock(SYMBOL_QUERY_DEBOUNCE_TIMEOUT);
+        cx.run_until_parked();
+        assert_eq!(updates.get(), 1);
+        symbols.update(cx, |symbols, _| {
+            assert_eq!(symbols.delegate.matches.len(), 1);
+            assert_eq!(symbols.delegate.matches[0].string, "one");
+        });
+
+        cx.executor().advance_clock(Duration::from_secs(1));
+        cx.run_until_parked();
+        assert_eq!(updates.get(), 2);
+        symbols.update(cx, |symbols, _| {
+            assert_eq!(symbols.delegate.matches.len(), 2);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_project_symbol_queries_are_debounced(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/dir", json!({ "test.rs": "" })).await;
+
+        let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_servers =
+            language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
+
+        let _buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer("/dir/test.rs", cx)
+            })
+            .await
+            .unwrap();
+
+        let fake_server = fake_servers.next().await.unwrap();
+        let requested_queries = Arc::new(Mutex::new(Vec::new()));
+        fake_server.handle_request::<lsp::WorkspaceSymbolRequest, _, _>({
+            let requested_queries = requested_queries.clone();
+            move |params, _| {
+                requested_queries.lock().unwrap().push(params.query);
+                async move {
+                    Ok(Some(lsp::WorkspaceSymbolResponse::Flat(vec![symbol(
+                        "one",
+                        "/dir/test.rs",
+                    )])))
+                }
+            }
+        });
+
+        let debounce_timeout = Duration::from_millis(200);
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let symbols = cx.new_view(|cx| {
+            Picker::uniform_list(
+                ProjectSymbolsDelegate::new(workspace.downgrade(), project.clone())
+                    .with_debounce_timeout(debounce_timeout),
+                cx,
+            )
+        });
+
+        // A burst of keystrokes, each arriving before the debounce timeout elapses.
+        for query in ["o", "on", "one"] {
+            symbols.update(cx, |p, cx| p.update_matches(query.to_string(), cx));
+            cx.executor().advance_clock(debounce_timeout / 2);
+            cx.run_until_parked();
+        }
+        assert!(requested_queries.lock().unwrap().is_empty());
+
+        cx.executor().advance_clock(debounce_timeout);
+        cx.run_until_parked();
+        assert_eq!(*requested_queries.lock().unwrap(), ["one"]);
+        symbols.update(cx, |symbols, _| {
+            assert_eq!(symbols.delegate.matches.len(), 1);
+            assert_eq!(symbols.delegate.matches[0].string, "one");
+        });
+    }
+
+    #[gpui::test]
+    async fn test_dropping_symbol_batches_cancels_requests(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/dir", json!({ "test.rs": "" })).await;
+
+        let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_servers =
+            language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
+
+        let _buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer("/dir/test.rs", cx)
+            })
+            .await
+            .unwrap();
+
+        let mut fake_server = fake_servers.next().await.unwrap();
+        fake_server.handle_request::<lsp::WorkspaceSymbolRequest, _, _>(|_, cx| {
+            let executor = cx.background_executor().clone();
+            async move {
+                executor.timer(Duration::from_secs(1)).await;
+                Ok(Some(lsp::WorkspaceSymbolResponse::Flat(vec![symbol(
+                    "one",
+                    "/dir/test.rs",
+                )])))
+            }
+        });
+
+        let batches = project.update(cx, |project, cx| project.stream_symbols("o", cx));
+        cx.run_until_parked();
+        drop(batches);
+        cx.run_until_parked();
+        fake_server
+            .receive_notification::<lsp::notification::Cancel>()
+            .await;
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let store = SettingsStore::test(cx);
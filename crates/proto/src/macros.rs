@@ -50,9 +50,15 @@ macro_rules! messages {
 
 #[macro_export]
 macro_rules! request_messages {
-    ($(($request_name:ident, $response_name:ident)),* $(,)?) => {
+    ($(($request_name:ident, $response_name:ident $(, forward_timeout: $forward_timeout:expr)?)),* $(,)?) => {
         $(impl RequestMessage for $request_name {
             type Response = $response_name;
+
+            $(
+                fn forward_timeout() -> Option<std::time::Duration> {
+                    $forward_timeout
+                }
+            )?
         })*
     };
 }
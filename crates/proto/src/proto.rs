@@ -23,6 +23,10 @@ include!(concat!(env!("OUT_DIR"), "/zed.messages.rs"));
 pub const SSH_PEER_ID: PeerId = PeerId { owner_id: 0, id: 0 };
 pub const SSH_PROJECT_ID: u64 = 0;
 
+/// How long to wait for a response to a forwarded request, unless the request's type
+/// overrides [`RequestMessage::forward_timeout`].
+pub const DEFAULT_FORWARD_TIMEOUT: Duration = Duration::from_secs(60);
+
 pub trait EnvelopedMessage: Clone + Debug + Serialize + Sized + Send + Sync + 'static {
     const NAME: &'static str;
     const PRIORITY: MessagePriority;
@@ -42,6 +46,12 @@ pub trait EntityMessage: EnvelopedMessage {
 
 pub trait RequestMessage: EnvelopedMessage {
     type Response: EnvelopedMessage;
+
+    /// How long to wait for a response when this request is forwarded to another peer,
+    /// or `None` if handling it can legitimately take arbitrarily long.
+    fn forward_timeout() -> Option<Duration> {
+        Some(DEFAULT_FORWARD_TIMEOUT)
+    }
 }
 
 pub trait AnyTypedEnvelope: 'static + Send + Sync {
@@ -156,6 +166,8 @@ messages!(
     (CancelCall, Foreground),
     (ChannelMessageSent, Foreground),
     (ChannelMessageUpdate, Foreground),
+    (ChannelTyping, Foreground),
+    (ChannelTypingUpdate, Foreground),
     (ComputeEmbeddings, Background),
     (ComputeEmbeddingsResponse, Background),
     (CopyProjectEntry, Foreground),
@@ -249,6 +261,7 @@ messages!(
     (Ping, Foreground),
     (PrepareRename, Background),
     (PrepareRenameResponse, Background),
+    (PresenceChanged, Foreground),
     (ProjectEntryResponse, Foreground),
     (CountLanguageModelTokens, Background),
     (CountLanguageModelTokensResponse, Background),
@@ -265,6 +278,7 @@ messages!(
     (UpdateChannelMessage, Foreground),
     (RemoveContact, Foreground),
     (RemoveProjectCollaborator, Foreground),
+    (RemoveProjectGuest, Foreground),
     (RenameChannel, Foreground),
     (RenameChannelResponse, Foreground),
     (RenameProjectEntry, Foreground),
@@ -281,6 +295,7 @@ messages!(
     (SetChannelVisibility, Foreground),
     (SendChannelMessage, Background),
     (SendChannelMessageResponse, Background),
+    (ServerShutdown, Foreground),
     (ShareProject, Foreground),
     (ShareProjectResponse, Foreground),
     (ShowContacts, Foreground),
@@ -306,6 +321,7 @@ messages!(
     (UpdateInviteInfo, Foreground),
     (UpdateLanguageServer, Foreground),
     (UpdateParticipantLocation, Foreground),
+    (UpdatePresence, Foreground),
     (UpdateProject, Foreground),
     (UpdateProjectCollaborator, Foreground),
     (UpdateUserPlan, Foreground),
@@ -370,7 +386,7 @@ messages!(
 
 request_messages!(
     (AcceptTermsOfService, AcceptTermsOfServiceResponse),
-    (ApplyCodeAction, ApplyCodeActionResponse),
+    (ApplyCodeAction, ApplyCodeActionResponse, forward_timeout: None),
     (
         ApplyCompletionAdditionalEdits,
         ApplyCompletionAdditionalEditsResponse
@@ -387,7 +403,7 @@ request_messages!(
     (DeleteProjectEntry, ProjectEntryResponse),
     (ExpandProjectEntry, ExpandProjectEntryResponse),
     (Follow, FollowResponse),
-    (FormatBuffers, FormatBuffersResponse),
+    (FormatBuffers, FormatBuffersResponse, forward_timeout: None),
     (FuzzySearchUsers, UsersResponse),
     (GetCachedEmbeddings, GetCachedEmbeddingsResponse),
     (GetChannelMembers, GetChannelMembersResponse),
@@ -428,7 +444,7 @@ request_messages!(
     (OpenBufferByPath, OpenBufferResponse),
     (OpenBufferForSymbol, OpenBufferForSymbolResponse),
     (OpenNewBuffer, OpenBufferResponse),
-    (PerformRename, PerformRenameResponse),
+    (PerformRename, PerformRenameResponse, forward_timeout: None),
     (Ping, Ack),
     (PrepareRename, PrepareRenameResponse),
     (CountLanguageModelTokens, CountLanguageModelTokensResponse),
@@ -440,6 +456,7 @@ request_messages!(
     (RemoveChannelMessage, Ack),
     (UpdateChannelMessage, Ack),
     (RemoveContact, Ack),
+    (RemoveProjectGuest, Ack),
     (RenameChannel, RenameChannelResponse),
     (RenameProjectEntry, ProjectEntryResponse),
     (RequestContact, Ack),
@@ -450,7 +467,7 @@ request_messages!(
     (ResolveInlayHint, ResolveInlayHintResponse),
     (RespondToChannelInvite, Ack),
     (RespondToContactRequest, Ack),
-    (SaveBuffer, BufferSaved),
+    (SaveBuffer, BufferSaved, forward_timeout: None),
     (FindSearchCandidates, FindSearchCandidatesResponse),
     (SendChannelMessage, SendChannelMessageResponse),
     (SetChannelMemberRole, Ack),
@@ -465,7 +482,7 @@ request_messages!(
     (UpdateWorktree, Ack),
     (LspExtExpandMacro, LspExtExpandMacroResponse),
     (SetRoomParticipantRole, Ack),
-    (BlameBuffer, BlameBufferResponse),
+    (BlameBuffer, BlameBufferResponse, forward_timeout: None),
     (RejoinRemoteProjects, RejoinRemoteProjectsResponse),
     (MultiLspQuery, MultiLspQueryResponse),
     (RestartLanguageServers, Ack),
@@ -534,6 +551,7 @@ entity_messages!(
     RefreshInlayHints,
     ReloadBuffers,
     RemoveProjectCollaborator,
+    RemoveProjectGuest,
     RenameProjectEntry,
     ResolveCompletionDocumentation,
     ResolveInlayHint,
@@ -36,14 +36,27 @@ impl Connection {
     pub fn in_memory(
         executor: gpui::BackgroundExecutor,
     ) -> (Self, Self, std::sync::Arc<std::sync::atomic::AtomicBool>) {
-        use std::sync::{
-            atomic::{AtomicBool, Ordering::SeqCst},
-            Arc,
+        Self::in_memory_with_latency(executor, std::time::Duration::ZERO)
+    }
+
+    /// Like [`Connection::in_memory`], but every message is delivered no earlier than
+    /// `latency` after it was sent, as measured by the executor's clock.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn in_memory_with_latency(
+        executor: gpui::BackgroundExecutor,
+        latency: std::time::Duration,
+    ) -> (Self, Self, std::sync::Arc<std::sync::atomic::AtomicBool>) {
+        use std::{
+            sync::{
+                atomic::{AtomicBool, Ordering::SeqCst},
+                Arc,
+            },
+            time::{Duration, Instant},
         };
 
         let killed = Arc::new(AtomicBool::new(false));
-        let (a_tx, a_rx) = channel(killed.clone(), executor.clone());
-        let (b_tx, b_rx) = channel(killed.clone(), executor);
+        let (a_tx, a_rx) = channel(killed.clone(), executor.clone(), latency);
+        let (b_tx, b_rx) = channel(killed.clone(), executor, latency);
         return (
             Self { tx: a_tx, rx: b_rx },
             Self { tx: b_tx, rx: a_rx },
@@ -54,6 +67,7 @@ impl Connection {
         fn channel(
             killed: Arc<AtomicBool>,
             executor: gpui::BackgroundExecutor,
+            latency: Duration,
         ) -> (
             Box<dyn Send + Unpin + futures::Sink<WebSocketMessage, Error = anyhow::Error>>,
             Box<dyn Send + Unpin + futures::Stream<Item = Result<WebSocketMessage, anyhow::Error>>>,
@@ -62,7 +76,7 @@ impl Connection {
             use futures::channel::mpsc;
             use std::io::{Error, ErrorKind};
 
-            let (tx, rx) = mpsc::unbounded::<WebSocketMessage>();
+            let (tx, rx) = mpsc::unbounded::<(Instant, WebSocketMessage)>();
 
             let tx = tx.sink_map_err(|error| anyhow!(error)).with({
                 let killed = killed.clone();
@@ -78,17 +92,24 @@ impl Connection {
                             std::io::Result::Err(Error::new(ErrorKind::Other, "connection lost"))?;
                         }
 
-                        Ok(msg)
+                        Ok((executor.now() + latency, msg))
                     })
                 }
             });
 
             let rx = rx.then({
                 let executor = executor.clone();
-                move |msg| {
+                move |(deliver_at, msg)| {
                     let killed = killed.clone();
                     let executor = executor.clone();
                     Box::pin(async move {
+                        // Messages are delivered in order, so waiting here delays any
+                        // later messages until this one has arrived.
+                        let now = executor.now();
+                        if deliver_at > now {
+                            executor.timer(deliver_at - now).await;
+                        }
+
                         executor.simulate_random_delay().await;
 
                         // Reads from a half-open TCP connection will hang.
@@ -18,6 +18,7 @@ use serde::{ser::SerializeStruct, Serialize};
 use std::{
     fmt, future,
     future::Future,
+    mem,
     sync::atomic::Ordering::SeqCst,
     sync::{
         atomic::{self, AtomicU32},
@@ -62,6 +63,8 @@ pub struct Peer {
     epoch: AtomicU32,
     pub connections: RwLock<HashMap<ConnectionId, ConnectionState>>,
     next_connection_id: AtomicU32,
+    keepalive_interval: Duration,
+    receive_timeout: Duration,
 }
 
 #[derive(Clone, Serialize)]
@@ -69,39 +72,69 @@ pub struct ConnectionState {
     #[serde(skip)]
     outgoing_tx: mpsc::UnboundedSender<proto::Message>,
     next_message_id: Arc<AtomicU32>,
-    #[allow(clippy::type_complexity)]
     #[serde(skip)]
-    response_channels: Arc<
-        Mutex<
-            Option<
-                HashMap<
-                    u32,
-                    oneshot::Sender<(proto::Envelope, std::time::Instant, oneshot::Sender<()>)>,
-                >,
-            >,
-        >,
-    >,
-    #[allow(clippy::type_complexity)]
+    response_channels: ResponseChannels,
     #[serde(skip)]
-    stream_response_channels: Arc<
-        Mutex<
-            Option<
-                HashMap<u32, mpsc::UnboundedSender<(Result<proto::Envelope>, oneshot::Sender<()>)>>,
+    stream_response_channels: StreamResponseChannels,
+}
+
+type ResponseChannels = Arc<
+    Mutex<
+        Option<
+            HashMap<
+                u32,
+                oneshot::Sender<(proto::Envelope, std::time::Instant, oneshot::Sender<()>)>,
             >,
         >,
     >,
+>;
+
+type StreamResponseChannels = Arc<
+    Mutex<
+        Option<HashMap<u32, mpsc::UnboundedSender<(Result<proto::Envelope>, oneshot::Sender<()>)>>>,
+    >,
+>;
+
+/// Fails all requests that are still awaiting a response on a connection, and
+/// prevents new requests from being sent on it.
+fn close_response_channels(
+    response_channels: &ResponseChannels,
+    stream_response_channels: &StreamResponseChannels,
+) {
+    response_channels.lock().take();
+    if let Some(channels) = stream_response_channels.lock().take() {
+        for channel in channels.values() {
+            let _ =
+                channel.unbounded_send((Err(anyhow!("connection closed")), oneshot::channel().0));
+        }
+    }
 }
 
-const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(1);
+pub const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(1);
 const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
 pub const RECEIVE_TIMEOUT: Duration = Duration::from_secs(10);
 
 impl Peer {
     pub fn new(epoch: u32) -> Arc<Self> {
+        Self::with_keepalive(epoch, KEEPALIVE_INTERVAL, RECEIVE_TIMEOUT)
+    }
+
+    /// Creates a peer that pings each connection after `keepalive_interval` without
+    /// sending anything else, and drops connections that are silent for `receive_timeout`.
+    ///
+    /// The other side of each connection drops it after [`RECEIVE_TIMEOUT`] of silence,
+    /// so `keepalive_interval` should be comfortably shorter than that.
+    pub fn with_keepalive(
+        epoch: u32,
+        keepalive_interval: Duration,
+        receive_timeout: Duration,
+    ) -> Arc<Self> {
         Arc::new(Self {
             epoch: AtomicU32::new(epoch),
             connections: Default::default(),
             next_connection_id: Default::default(),
+            keepalive_interval,
+            receive_timeout,
         })
     }
 
@@ -151,30 +184,24 @@ impl Peer {
         let this = self.clone();
         let response_channels = connection_state.response_channels.clone();
         let stream_response_channels = connection_state.stream_response_channels.clone();
+        let keepalive_interval = self.keepalive_interval;
+        let receive_timeout_duration = self.receive_timeout;
 
         let handle_io = async move {
             tracing::trace!(%connection_id, "handle io future: start");
 
             let _end_connection = util::defer(|| {
-                response_channels.lock().take();
-                if let Some(channels) = stream_response_channels.lock().take() {
-                    for channel in channels.values() {
-                        let _ = channel.unbounded_send((
-                            Err(anyhow!("connection closed")),
-                            oneshot::channel().0,
-                        ));
-                    }
-                }
+                close_response_channels(&response_channels, &stream_response_channels);
                 this.connections.write().remove(&connection_id);
                 tracing::trace!(%connection_id, "handle io future: end");
             });
 
             // Send messages on this frequency so the connection isn't closed.
-            let keepalive_timer = create_timer(KEEPALIVE_INTERVAL).fuse();
+            let keepalive_timer = create_timer(keepalive_interval).fuse();
             futures::pin_mut!(keepalive_timer);
 
             // Disconnect if we don't receive messages at least this frequently.
-            let receive_timeout = create_timer(RECEIVE_TIMEOUT).fuse();
+            let receive_timeout = create_timer(receive_timeout_duration).fuse();
             futures::pin_mut!(receive_timeout);
 
             loop {
@@ -193,7 +220,7 @@ impl Peer {
                                         tracing::trace!(%connection_id, "outgoing rpc message: done writing");
                                         result.context("failed to write RPC message")?;
                                         tracing::trace!(%connection_id, "keepalive interval: resetting after sending message");
-                                        keepalive_timer.set(create_timer(KEEPALIVE_INTERVAL).fuse());
+                                        keepalive_timer.set(create_timer(keepalive_interval).fuse());
                                     }
                                     _ = create_timer(WRITE_TIMEOUT).fuse() => {
                                         tracing::trace!(%connection_id, "outgoing rpc message: writing timed out");
@@ -213,7 +240,7 @@ impl Peer {
                                     tracing::trace!(%connection_id, "keepalive interval: done pinging");
                                     result.context("failed to send keepalive")?;
                                     tracing::trace!(%connection_id, "keepalive interval: resetting after pinging");
-                                    keepalive_timer.set(create_timer(KEEPALIVE_INTERVAL).fuse());
+                                    keepalive_timer.set(create_timer(keepalive_interval).fuse());
                                 }
                                 _ = create_timer(WRITE_TIMEOUT).fuse() => {
                                     tracing::trace!(%connection_id, "keepalive interval: pinging timed out");
@@ -225,7 +252,7 @@ impl Peer {
                             let incoming = incoming.context("error reading rpc message from socket")?;
                             tracing::trace!(%connection_id, "incoming rpc message: received");
                             tracing::trace!(%connection_id, "receive timeout: resetting");
-                            receive_timeout.set(create_timer(RECEIVE_TIMEOUT).fuse());
+                            receive_timeout.set(create_timer(receive_timeout_duration).fuse());
                             if let (proto::Message::Envelope(incoming), received_at) = incoming {
                                 tracing::trace!(%connection_id, "incoming rpc message: processing");
                                 futures::select_biased! {
@@ -384,8 +411,20 @@ impl Peer {
         self.add_connection(connection, move |duration| executor.timer(duration))
     }
 
+    /// Closes a single connection, failing any requests that are still awaiting
+    /// a response on it. Other connections are unaffected.
     pub fn disconnect(&self, connection_id: ConnectionId) {
-        self.connections.write().remove(&connection_id);
+        let connection = self.connections.write().remove(&connection_id);
+        if let Some(connection) = connection {
+            close_response_channels(
+                &connection.response_channels,
+                &connection.stream_response_channels,
+            );
+        }
+    }
+
+    pub fn connection_count(&self) -> usize {
+        self.connections.read().len()
     }
 
     #[cfg(any(test, feature = "test-support"))]
@@ -395,7 +434,13 @@ impl Peer {
     }
 
     pub fn teardown(&self) {
-        self.connections.write().clear();
+        let connections = mem::take(&mut *self.connections.write());
+        for connection in connections.values() {
+            close_response_channels(
+                &connection.response_channels,
+                &connection.stream_response_channels,
+            );
+        }
     }
 
     /// Make a request and wait for a response.
@@ -426,6 +471,29 @@ impl Peer {
             .map_ok(|envelope| envelope.payload)
     }
 
+    /// Forward a request like [`Peer::forward_request`], but give up with an
+    /// [`ErrorCode::Timeout`] error if `timeout` completes before the response arrives.
+    pub fn forward_request_with_timeout<T: RequestMessage>(
+        &self,
+        sender_id: ConnectionId,
+        receiver_id: ConnectionId,
+        request: T,
+        timeout: impl Future<Output = ()>,
+    ) -> impl Future<Output = Result<T::Response>> {
+        let response = self.forward_request(sender_id, receiver_id, request);
+        async move {
+            let response = response.fuse();
+            let timeout = timeout.fuse();
+            futures::pin_mut!(response, timeout);
+            futures::select_biased! {
+                response = response => response,
+                _ = timeout => Err(ErrorCode::Timeout
+                    .message(format!("timed out waiting for a response to {}", T::NAME))
+                    .anyhow()),
+            }
+        }
+    }
+
     fn request_internal<T: RequestMessage>(
         &self,
         original_sender_id: Option<ConnectionId>,
@@ -1036,6 +1104,75 @@ mod tests {
             .is_err());
     }
 
+    #[gpui::test(iterations = 50)]
+    async fn test_disconnect_single_connection(cx: &mut TestAppContext) {
+        let executor = cx.executor();
+        let server = Peer::new(0);
+        let client = Peer::new(0);
+
+        let mut client_conn_ids = Vec::new();
+        let mut server_conn_ids = Vec::new();
+        for _ in 0..2 {
+            let (client_to_server_conn, server_to_client_conn, _kill) =
+                Connection::in_memory(executor.clone());
+            let (client_conn_id, client_io, mut client_incoming) =
+                client.add_test_connection(client_to_server_conn, executor.clone());
+            let (server_conn_id, server_io, mut server_incoming) =
+                server.add_test_connection(server_to_client_conn, executor.clone());
+            executor.spawn(client_io).detach();
+            executor.spawn(server_io).detach();
+
+            // The client never responds to requests from the server.
+            executor
+                .spawn(async move { while client_incoming.next().await.is_some() {} })
+                .detach();
+            executor
+                .spawn({
+                    let server = server.clone();
+                    async move {
+                        while let Some(envelope) = server_incoming.next().await {
+                            let envelope = envelope.into_any();
+                            if let Some(envelope) =
+                                envelope.downcast_ref::<TypedEnvelope<proto::Ping>>()
+                            {
+                                server.respond(envelope.receipt(), proto::Ack {}).ok();
+                            }
+                        }
+                    }
+                })
+                .detach();
+
+            client_conn_ids.push(client_conn_id);
+            server_conn_ids.push(server_conn_id);
+        }
+        assert_eq!(server.connection_count(), 2);
+
+        let pending_request = executor.spawn(server.request(server_conn_ids[0], proto::Ping {}));
+        executor.run_until_parked();
+
+        // Requests awaiting a response on the disconnected connection fail immediately.
+        server.disconnect(server_conn_ids[0]);
+        assert_eq!(server.connection_count(), 1);
+        assert_eq!(
+            pending_request.await.unwrap_err().to_string(),
+            "connection was closed"
+        );
+        assert!(server
+            .request(server_conn_ids[0], proto::Ping {})
+            .await
+            .is_err());
+
+        // The other connection is unaffected.
+        assert_eq!(
+            client
+                .request(client_conn_ids[1], proto::Ping {})
+                .await
+                .unwrap(),
+            proto::Ack {}
+        );
+        assert_eq!(server.connection_count(), 1);
+    }
+
     #[gpui::test(iterations = 50)]
     async fn test_io_error(cx: &mut TestAppContext) {
         let executor = cx.executor();
@@ -1058,4 +1195,93 @@ mod tests {
             "connection was closed"
         );
     }
+
+    #[gpui::test]
+    async fn test_forward_request_timeout(cx: &mut TestAppContext) {
+        let executor = cx.executor();
+        let server = Peer::new(0);
+        let client = Peer::new(0);
+
+        let (client_to_server_conn, server_to_client_conn, _kill) =
+            Connection::in_memory(executor.clone());
+        let (_, io_task1, mut client_incoming) =
+            client.add_test_connection(client_to_server_conn, executor.clone());
+        let (server_to_client_conn_id, io_task2, _server_incoming) =
+            server.add_test_connection(server_to_client_conn, executor.clone());
+        executor.spawn(io_task1).detach();
+        executor.spawn(io_task2).detach();
+
+        let timeout = Duration::from_secs(5);
+        let forward_ping = || {
+            executor.spawn(server.forward_request_with_timeout(
+                server_to_client_conn_id,
+                server_to_client_conn_id,
+                proto::Ping {},
+                executor.timer(timeout),
+            ))
+        };
+
+        // A request that is never answered fails once the timeout elapses.
+        let request = forward_ping();
+        let _unanswered = client_incoming.next().await.unwrap();
+        executor.advance_clock(timeout);
+        let error = request.await.unwrap_err();
+        assert_eq!(error.error_code(), ErrorCode::Timeout);
+
+        // A response that arrives before the timeout cancels it.
+        let request = forward_ping();
+        let ping = client_incoming
+            .next()
+            .await
+            .unwrap()
+            .into_any()
+            .downcast::<TypedEnvelope<proto::Ping>>()
+            .unwrap();
+        client.respond(ping.receipt(), proto::Ack {}).unwrap();
+        request.await.unwrap();
+        executor.advance_clock(timeout);
+    }
+
+    #[gpui::test(iterations = 50)]
+    async fn test_in_memory_latency(cx: &mut TestAppContext) {
+        let executor = cx.executor();
+        let latency = Duration::from_millis(100);
+        let (mut client_conn, server_conn, _kill) =
+            Connection::in_memory_with_latency(executor.clone(), latency);
+
+        let (received_tx, mut received_rx) = mpsc::unbounded();
+        executor
+            .spawn(async move {
+                let mut rx = server_conn.rx;
+                while let Some(message) = rx.next().await {
+                    received_tx.unbounded_send(message.unwrap()).unwrap();
+                }
+            })
+            .detach();
+
+        client_conn
+            .send(WebSocketMessage::Binary(vec![1]))
+            .await
+            .unwrap();
+        client_conn
+            .send(WebSocketMessage::Binary(vec![2]))
+            .await
+            .unwrap();
+
+        executor.run_until_parked();
+        assert!(received_rx.try_next().is_err());
+
+        executor.advance_clock(latency - Duration::from_millis(1));
+        assert!(received_rx.try_next().is_err());
+
+        executor.advance_clock(Duration::from_millis(1));
+        assert_eq!(
+            received_rx.try_next().unwrap(),
+            Some(WebSocketMessage::Binary(vec![1]))
+        );
+        assert_eq!(
+            received_rx.try_next().unwrap(),
+            Some(WebSocketMessage::Binary(vec![2]))
+        );
+    }
 }
@@ -56,8 +56,7 @@ where
                     .encode(&mut self.encoding_buffer)
                     .map_err(io::Error::from)?;
                 let buffer =
-                    zstd::stream::encode_all(self.encoding_buffer.as_slice(), COMPRESSION_LEVEL)
-                        .unwrap();
+                    zstd::stream::encode_all(self.encoding_buffer.as_slice(), COMPRESSION_LEVEL)?;
 
                 self.encoding_buffer.clear();
                 self.encoding_buffer.shrink_to(MAX_BUFFER_LEN);
@@ -88,13 +87,16 @@ where
             let received_at = Instant::now();
             match bytes? {
                 WebSocketMessage::Binary(bytes) => {
-                    zstd::stream::copy_decode(bytes.as_slice(), &mut self.encoding_buffer).unwrap();
-                    let envelope = Envelope::decode(self.encoding_buffer.as_slice())
-                        .map_err(io::Error::from)?;
+                    let envelope =
+                        zstd::stream::copy_decode(bytes.as_slice(), &mut self.encoding_buffer)
+                            .and_then(|_| {
+                                Envelope::decode(self.encoding_buffer.as_slice())
+                                    .map_err(io::Error::from)
+                            });
 
                     self.encoding_buffer.clear();
                     self.encoding_buffer.shrink_to(MAX_BUFFER_LEN);
-                    return Ok((Message::Envelope(envelope), received_at));
+                    return Ok((Message::Envelope(envelope?), received_at));
                 }
                 WebSocketMessage::Ping(_) => return Ok((Message::Ping, received_at)),
                 WebSocketMessage::Pong(_) => return Ok((Message::Pong, received_at)),
@@ -141,4 +143,49 @@ mod tests {
         stream.read().await.unwrap();
         assert!(stream.encoding_buffer.capacity() <= MAX_BUFFER_LEN);
     }
+
+    #[gpui::test]
+    async fn test_large_messages_are_compressed() {
+        let envelope = Envelope {
+            payload: Some(envelope::Payload::UpdateWorktree(UpdateWorktree {
+                updated_entries: (0..1000)
+                    .map(|id| Entry {
+                        id,
+                        path: format!("src/module_{id}/mod.rs"),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        let (tx, mut rx) = futures::channel::mpsc::unbounded();
+        let mut sink = MessageStream::new(tx.sink_map_err(|_| anyhow!("")));
+        sink.write(Message::Envelope(envelope.clone()))
+            .await
+            .unwrap();
+
+        let Some(WebSocketMessage::Binary(bytes)) = rx.next().await else {
+            panic!("expected a binary message");
+        };
+        assert!(bytes.len() < envelope.encoded_len() / 2);
+
+        let mut stream = MessageStream::new(futures::stream::iter([anyhow::Ok(
+            WebSocketMessage::Binary(bytes),
+        )]));
+        let (Message::Envelope(received), _) = stream.read().await.unwrap() else {
+            panic!("expected an envelope");
+        };
+        assert_eq!(received, envelope);
+    }
+
+    #[gpui::test]
+    async fn test_corrupt_messages_are_errors() {
+        let mut stream = MessageStream::new(futures::stream::iter([anyhow::Ok(
+            WebSocketMessage::Binary(b"not zstd".to_vec()),
+        )]));
+        assert!(stream.read().await.is_err());
+        assert!(stream.encoding_buffer.is_empty());
+    }
 }
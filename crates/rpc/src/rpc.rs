@@ -19,4 +19,11 @@ mod proto_client;
 #[cfg(feature = "gpui")]
 pub use proto_client::*;
 
-pub const PROTOCOL_VERSION: u32 = 68;
+pub const PROTOCOL_VERSION: u32 = 69;
+
+/// The oldest protocol version that the server still accepts connections from. Clients
+/// one version behind can keep connecting while the server is being upgraded.
+///
+/// Only bump [`PROTOCOL_VERSION`] for changes that older clients can ignore, such as
+/// new messages or fields. Bump this as well when older clients can no longer be served.
+pub const MIN_SUPPORTED_PROTOCOL_VERSION: u32 = PROTOCOL_VERSION - 1;
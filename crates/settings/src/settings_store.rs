@@ -1685,6 +1685,53 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_user_settings_file_changes(cx: &mut gpui::TestAppContext) {
+        use crate::{handle_settings_file_changes, watch_config_file};
+        use fs::{FakeFs, Fs as _};
+        use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+        let fs = FakeFs::new(cx.executor());
+        let path = PathBuf::from("/settings.json");
+        fs.insert_file(&path, b"{}".to_vec()).await;
+
+        let results = Rc::new(RefCell::new(Vec::new()));
+        cx.update(|cx| {
+            let mut store = SettingsStore::new(cx);
+            store.register_setting::<TurboSetting>(cx);
+            store
+                .set_default_settings(r#"{ "turbo": false }"#, cx)
+                .unwrap();
+            cx.set_global(store);
+
+            let settings_rx = watch_config_file(cx.background_executor(), fs.clone(), path.clone());
+            handle_settings_file_changes(settings_rx, cx, {
+                let results = results.clone();
+                move |error, _| results.borrow_mut().push(error.is_none())
+            });
+        });
+        let turbo = |cx: &mut gpui::TestAppContext| {
+            cx.read(|cx| cx.global::<SettingsStore>().get::<TurboSetting>(None).0)
+        };
+        assert!(!turbo(cx));
+
+        // Editing the file updates the settings.
+        fs.atomic_write(path.clone(), r#"{ "turbo": true }"#.into())
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert!(turbo(cx));
+        assert_eq!(results.take(), [true]);
+
+        // Malformed settings are reported, and the last good settings are kept.
+        fs.atomic_write(path.clone(), r#"{ "turbo": "#.into())
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert!(turbo(cx));
+        assert_eq!(results.take(), [false]);
+    }
+
     #[derive(Debug, Deserialize, PartialEq)]
     struct TurboSetting(bool);
 
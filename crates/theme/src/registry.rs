@@ -1,16 +1,22 @@
 use std::sync::Arc;
-use std::{fmt::Debug, path::Path};
+use std::time::Duration;
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{anyhow, Context, Result};
 use collections::HashMap;
 use derive_more::{Deref, DerefMut};
 use fs::Fs;
 use futures::StreamExt;
-use gpui::{AppContext, AssetSource, Global, SharedString};
+use gpui::{AppContext, AssetSource, Global, SharedString, Task};
 use parking_lot::RwLock;
 use util::ResultExt;
 
-use crate::{refine_theme_family, Appearance, Theme, ThemeFamily, ThemeFamilyContent};
+use crate::{
+    refine_theme_family, Appearance, Theme, ThemeFamily, ThemeFamilyContent, ThemeSettings,
+};
 
 /// The metadata for a theme.
 #[derive(Debug, Clone)]
@@ -141,6 +147,23 @@ impl ThemeRegistry {
             .cloned()
     }
 
+    /// Returns the theme with the given name, or the default theme for the given [`Appearance`]
+    /// if no such theme exists.
+    ///
+    /// Falls back to the built-in Zed theme when the default theme isn't loaded either, so this
+    /// always returns a theme.
+    pub fn get_or_default(&self, name: &str, appearance: Appearance) -> Arc<Theme> {
+        if let Ok(theme) = self.get(name) {
+            return theme;
+        }
+
+        let default_name = ThemeSettings::default_theme(appearance);
+        log::warn!("theme not found: {name}, falling back to {default_name}");
+        self.get(default_name)
+            .or_else(|_| self.get(&crate::fallback_themes::zed_default_dark().name))
+            .unwrap_or_else(|_| Arc::new(crate::fallback_themes::zed_default_dark()))
+    }
+
     /// Loads the themes bundled with the Zed binary and adds them to the registry.
     pub fn load_bundled_themes(&self) {
         let theme_paths = self
@@ -216,6 +239,33 @@ impl ThemeRegistry {
 
         Ok(())
     }
+
+    /// Watches the given directory for changes to user themes, reloading each theme that
+    /// changes and then the current theme, so that edits to the active theme are applied
+    /// right away. A theme that fails to parse keeps its previous version.
+    pub fn watch_user_themes(themes_path: PathBuf, fs: Arc<dyn Fs>, cx: &AppContext) -> Task<()> {
+        cx.spawn(|cx| async move {
+            let (mut events, _) = fs.watch(&themes_path, Duration::from_millis(100)).await;
+
+            while let Some(paths) = events.next().await {
+                for event in paths {
+                    if fs.metadata(&event.path).await.ok().flatten().is_some() {
+                        if let Some(theme_registry) =
+                            cx.update(|cx| ThemeRegistry::global(cx).clone()).log_err()
+                        {
+                            if let Some(()) = theme_registry
+                                .load_user_theme(&event.path, fs.clone())
+                                .await
+                                .log_err()
+                            {
+                                cx.update(ThemeSettings::reload_current_theme).log_err();
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
 }
 
 impl Default for ThemeRegistry {
@@ -223,3 +273,166 @@ impl Default for ThemeRegistry {
         Self::new(Box::new(()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fs::FakeFs;
+    use gpui::{rgba, Hsla, TestAppContext};
+    use settings::SettingsStore;
+
+    use crate::{ActiveTheme, LoadThemes, SystemAppearance};
+
+    use super::*;
+
+    fn theme_family_json(background: &str) -> String {
+        format!(
+            r#"{{
+                "name": "Test",
+                "author": "Zed",
+                "themes": [
+                    {{
+                        "name": "Test Theme",
+                        "appearance": "dark",
+                        "style": {{ "background": "{background}" }}
+                    }}
+                ]
+            }}"#
+        )
+    }
+
+    #[gpui::test]
+    async fn test_reloading_the_active_theme_when_it_changes(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.create_dir(Path::new("/themes")).await.unwrap();
+
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            SystemAppearance::init(cx);
+            crate::init(LoadThemes::JustBase, cx);
+            ThemeRegistry::watch_user_themes("/themes".into(), fs.clone(), cx).detach();
+        });
+        cx.run_until_parked();
+
+        fs.insert_file(
+            "/themes/test.json",
+            theme_family_json("#ff0000ff").into_bytes(),
+        )
+        .await;
+        cx.run_until_parked();
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store
+                    .set_user_settings(r#"{ "theme": "Test Theme" }"#, cx)
+                    .unwrap();
+            });
+            assert_eq!(cx.theme().name, "Test Theme");
+            assert_eq!(cx.theme().colors().background, Hsla::from(rgba(0xff0000ff)));
+        });
+
+        // Editing the active theme's file applies the change right away.
+        fs.insert_file(
+            "/themes/test.json",
+            theme_family_json("#00ff00ff").into_bytes(),
+        )
+        .await;
+        cx.run_until_parked();
+        cx.update(|cx| {
+            assert_eq!(cx.theme().name, "Test Theme");
+            assert_eq!(cx.theme().colors().background, Hsla::from(rgba(0x00ff00ff)));
+        });
+
+        // A file that fails to parse keeps the previously loaded theme.
+        fs.insert_file("/themes/test.json", b"{ \"name\": ".to_vec())
+            .await;
+        cx.run_until_parked();
+        cx.update(|cx| {
+            assert_eq!(cx.theme().name, "Test Theme");
+            assert_eq!(cx.theme().colors().background, Hsla::from(rgba(0x00ff00ff)));
+        });
+    }
+
+    #[test]
+    fn test_get_or_default() {
+        let registry = ThemeRegistry::new(Box::new(()));
+        assert_eq!(
+            registry.get_or_default("One Dark", Appearance::Light).name,
+            "One Dark"
+        );
+        assert_eq!(
+            registry.get_or_default("One Drak", Appearance::Dark).name,
+            "One Dark"
+        );
+
+        let mut light = crate::fallback_themes::zed_default_dark();
+        light.name = "One Light".into();
+        light.appearance = Appearance::Light;
+        registry.insert_themes([light]);
+        assert_eq!(
+            registry.get_or_default("One Lihgt", Appearance::Light).name,
+            "One Light"
+        );
+
+        // Even with no themes loaded, a theme is always returned.
+        registry.clear();
+        assert_eq!(
+            registry.get_or_default("One Drak", Appearance::Dark).name,
+            "One Dark"
+        );
+    }
+
+    #[gpui::test]
+    fn test_missing_theme_in_settings(cx: &mut AppContext) {
+        let settings_store = SettingsStore::test(cx);
+        cx.set_global(settings_store);
+        crate::init(LoadThemes::JustBase, cx);
+
+        SettingsStore::update_global(cx, |store, cx| {
+            store
+                .set_user_settings(r#"{ "theme": "Nonexistent Theme" }"#, cx)
+                .unwrap();
+        });
+        assert_eq!(cx.theme().name, "One Dark");
+    }
+
+    struct BundledThemes(String);
+
+    impl AssetSource for BundledThemes {
+        fn load(&self, path: &str) -> Result<Option<std::borrow::Cow<'static, [u8]>>> {
+            Ok((path == "themes/test.json").then(|| self.0.clone().into_bytes().into()))
+        }
+
+        fn list(&self, path: &str) -> Result<Vec<SharedString>> {
+            Ok(if path == "themes/" {
+                vec!["themes/test.json".into()]
+            } else {
+                Vec::new()
+            })
+        }
+    }
+
+    #[gpui::test]
+    async fn test_user_themes_take_precedence_over_bundled_ones(cx: &mut TestAppContext) {
+        let registry = ThemeRegistry::new(Box::new(BundledThemes(theme_family_json("#ff0000ff"))));
+        registry.load_bundled_themes();
+        assert_eq!(
+            registry.get("Test Theme").unwrap().colors().background,
+            Hsla::from(rgba(0xff0000ff))
+        );
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_file(
+            "/themes/test.json",
+            theme_family_json("#00ff00ff").into_bytes(),
+        )
+        .await;
+        registry
+            .load_user_themes(Path::new("/themes"), fs)
+            .await
+            .unwrap();
+        assert_eq!(
+            registry.get("Test Theme").unwrap().colors().background,
+            Hsla::from(rgba(0x00ff00ff))
+        );
+    }
+}
@@ -1,4 +1,3 @@
-use crate::fallback_themes::zed_default_dark;
 use crate::{Appearance, SyntaxTheme, Theme, ThemeRegistry, ThemeStyleContent};
 use anyhow::Result;
 use derive_more::{Deref, DerefMut};
@@ -441,6 +440,26 @@ impl ThemeSettings {
         f32::max(self.buffer_line_height.value(), MIN_LINE_HEIGHT)
     }
 
+    /// Returns a copy of the settings with the UI and buffer font sizes multiplied by the given
+    /// scale, never going below the minimum font size.
+    ///
+    /// Font sizes are part of the theme settings rather than the [`Theme`] itself, so this is
+    /// where they are scaled.
+    pub fn with_font_scale(&self, scale: f32) -> ThemeSettings {
+        let mut settings = self.clone();
+        settings.ui_font_size = (settings.ui_font_size * scale).max(MIN_FONT_SIZE);
+        settings.buffer_font_size = (settings.buffer_font_size * scale).max(MIN_FONT_SIZE);
+        settings
+    }
+
+    /// Returns a copy of the settings whose active theme has its contrast boosted by the given
+    /// amount. See [`Theme::with_contrast_boost`].
+    pub fn with_contrast_boost(&self, amount: f32) -> ThemeSettings {
+        let mut settings = self.clone();
+        settings.active_theme = Arc::new(self.active_theme.with_contrast_boost(amount));
+        settings
+    }
+
     /// Switches to the theme with the given name, if it exists.
     ///
     /// Returns a `Some` containing the new theme if it was successful.
@@ -627,10 +646,10 @@ impl settings::Settings for ThemeSettings {
             buffer_font_size: defaults.buffer_font_size.unwrap().into(),
             buffer_line_height: defaults.buffer_line_height.unwrap(),
             theme_selection: defaults.theme.clone(),
-            active_theme: themes
-                .get(defaults.theme.as_ref().unwrap().theme(*system_appearance))
-                .or(themes.get(&zed_default_dark().name))
-                .unwrap(),
+            active_theme: themes.get_or_default(
+                defaults.theme.as_ref().unwrap().theme(*system_appearance),
+                *system_appearance,
+            ),
             theme_overrides: None,
             ui_density: defaults.ui_density.unwrap_or(UiDensity::Default),
             unnecessary_code_fade: defaults.unnecessary_code_fade.unwrap_or(0.0),
@@ -676,10 +695,7 @@ impl settings::Settings for ThemeSettings {
                 this.theme_selection = Some(value.clone());
 
                 let theme_name = value.theme(*system_appearance);
-
-                if let Some(theme) = themes.get(theme_name).log_err() {
-                    this.active_theme = theme;
-                }
+                this.active_theme = themes.get_or_default(theme_name, *system_appearance);
             }
 
             this.theme_overrides.clone_from(&value.theme_overrides);
@@ -312,6 +312,40 @@ impl Theme {
     pub fn window_background_appearance(&self) -> WindowBackgroundAppearance {
         self.styles.window_background_appearance
     }
+
+    /// Returns a copy of the theme with the contrast between text and the surfaces behind it
+    /// increased by the given amount of lightness, between 0.0 and 1.0.
+    ///
+    /// Foreground colors are moved away from the background, and backgrounds are moved further
+    /// towards black for dark themes, or towards white for light themes.
+    pub fn with_contrast_boost(&self, amount: f32) -> Theme {
+        let amount = amount.clamp(0., 1.);
+        // Positive amounts lighten, so foregrounds get lighter and backgrounds darker.
+        let amount = match self.appearance {
+            Appearance::Dark => amount,
+            Appearance::Light => -amount,
+        };
+
+        let mut theme = self.clone();
+        let colors = &mut theme.styles.colors;
+        for color in [
+            &mut colors.text,
+            &mut colors.text_muted,
+            &mut colors.text_placeholder,
+            &mut colors.editor_foreground,
+        ] {
+            *color = color.lighten(amount);
+        }
+        for color in [
+            &mut colors.background,
+            &mut colors.surface_background,
+            &mut colors.elevated_surface_background,
+            &mut colors.editor_background,
+        ] {
+            *color = color.darken(amount);
+        }
+        theme
+    }
 }
 
 /// Compounds a color with an alpha value.
@@ -321,3 +355,53 @@ pub fn color_alpha(color: Hsla, alpha: f32) -> Hsla {
     color.a = alpha;
     color
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::{px, AppContext};
+
+    use crate::fallback_themes::zed_default_dark;
+
+    use super::*;
+
+    #[test]
+    fn test_contrast_boost() {
+        let dark = zed_default_dark();
+        let boosted = dark.with_contrast_boost(0.1);
+        assert!(boosted.colors().text.l > dark.colors().text.l);
+        assert!(boosted.colors().editor_foreground.l > dark.colors().editor_foreground.l);
+        assert!(boosted.colors().background.l < dark.colors().background.l);
+        assert!(boosted.colors().editor_background.l < dark.colors().editor_background.l);
+        assert_eq!(boosted.colors().border, dark.colors().border);
+
+        let mut light = zed_default_dark();
+        light.appearance = Appearance::Light;
+        light.styles.colors = ThemeColors::light();
+        let boosted = light.with_contrast_boost(0.1);
+        assert!(boosted.colors().text.l < light.colors().text.l);
+        assert!(boosted.colors().background.l > light.colors().background.l);
+
+        // Lightness stays within bounds however large the boost.
+        let boosted = dark.with_contrast_boost(10.);
+        assert_eq!(boosted.colors().text.l, 1.);
+        assert_eq!(boosted.colors().background.l, 0.);
+    }
+
+    #[gpui::test]
+    fn test_font_scale(cx: &mut AppContext) {
+        let settings_store = SettingsStore::test(cx);
+        cx.set_global(settings_store);
+        init(LoadThemes::JustBase, cx);
+
+        let settings = ThemeSettings::get_global(cx).clone();
+        ThemeSettings::override_global(settings.with_font_scale(1.5), cx);
+        let scaled = ThemeSettings::get_global(cx);
+        assert_eq!(scaled.ui_font_size, settings.ui_font_size * 1.5);
+        assert_eq!(scaled.buffer_font_size, settings.buffer_font_size * 1.5);
+        assert!(Arc::ptr_eq(&scaled.active_theme, &settings.active_theme));
+
+        let shrunk = settings.with_font_scale(0.01);
+        assert_eq!(shrunk.ui_font_size, px(6.));
+        assert_eq!(shrunk.buffer_font_size, px(6.));
+    }
+}
@@ -82,6 +82,7 @@ pub struct ThemeSelectorDelegate {
     original_theme: Arc<Theme>,
     selection_completed: bool,
     selected_index: usize,
+    hovered_index: Option<usize>,
     telemetry: Arc<Telemetry>,
     view: WeakView<ThemeSelector>,
 }
@@ -131,6 +132,7 @@ impl ThemeSelectorDelegate {
             matches,
             original_theme: original_theme.clone(),
             selected_index: 0,
+            hovered_index: None,
             selection_completed: false,
             telemetry,
             view: weak_view,
@@ -141,7 +143,37 @@ impl ThemeSelectorDelegate {
     }
 
     fn show_selected_theme(&mut self, cx: &mut ViewContext<Picker<ThemeSelectorDelegate>>) {
-        if let Some(mat) = self.matches.get(self.selected_index) {
+        self.show_theme_at(self.selected_index, cx);
+    }
+
+    /// Previews the theme at the given index while it's hovered, going back to the selected
+    /// theme once the hover ends. The previewed theme is reverted on dismiss, like the selected
+    /// one, unless it's confirmed.
+    ///
+    /// When the mouse moves between rows, the newly hovered row may be notified before the
+    /// previous one, so a hover ending only reverts the preview if that row is still the one
+    /// being previewed.
+    fn preview_hovered_theme(
+        &mut self,
+        ix: usize,
+        hovered: bool,
+        cx: &mut ViewContext<Picker<ThemeSelectorDelegate>>,
+    ) {
+        if self.selection_completed {
+            return;
+        }
+
+        if hovered {
+            self.hovered_index = Some(ix);
+            self.show_theme_at(ix, cx);
+        } else if self.hovered_index == Some(ix) {
+            self.hovered_index = None;
+            self.show_selected_theme(cx);
+        }
+    }
+
+    fn show_theme_at(&mut self, ix: usize, cx: &mut ViewContext<Picker<ThemeSelectorDelegate>>) {
+        if let Some(mat) = self.matches.get(ix) {
             let registry = ThemeRegistry::global(cx);
             match registry.get(&mat.string) {
                 Ok(theme) => {
@@ -185,6 +217,8 @@ impl PickerDelegate for ThemeSelectorDelegate {
     }
 
     fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<ThemeSelectorDelegate>>) {
+        // Confirm the selected theme rather than one that's only being previewed on hover.
+        self.show_selected_theme(cx);
         self.selection_completed = true;
 
         let theme_name = cx.theme().name.clone();
@@ -286,7 +320,7 @@ impl PickerDelegate for ThemeSelectorDelegate {
         &self,
         ix: usize,
         selected: bool,
-        _cx: &mut ViewContext<Picker<Self>>,
+        cx: &mut ViewContext<Picker<Self>>,
     ) -> Option<Self::ListItem> {
         let theme_match = &self.matches[ix];
 
@@ -295,10 +329,175 @@ impl PickerDelegate for ThemeSelectorDelegate {
                 .inset(true)
                 .spacing(ListItemSpacing::Sparse)
                 .selected(selected)
-                .child(HighlightedLabel::new(
-                    theme_match.string.clone(),
-                    theme_match.positions.clone(),
-                )),
+                .on_hover(cx.listener(move |picker, hovered: &bool, cx| {
+                    picker.delegate.preview_hovered_theme(ix, *hovered, cx);
+                }))
+                .child(
+                    div()
+                        .debug_selector(|| format!("THEME_ITEM-{}", theme_match.string))
+                        .child(HighlightedLabel::new(
+                            theme_match.string.clone(),
+                            theme_match.positions.clone(),
+                        )),
+                ),
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use gpui::{point, px, Modifiers, TestAppContext, VisualTestContext};
+    use project::Project;
+    use settings::Settings;
+    use workspace::AppState;
+
+    use super::*;
+
+    #[gpui::test]
+    async fn test_hover_preview_is_reverted_on_dismiss(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_file(
+                "/themes/test.json",
+                r#"{
+                    "name": "Test",
+                    "author": "Zed",
+                    "themes": [
+                        { "name": "Test Theme", "appearance": "dark", "style": {} }
+                    ]
+                }"#
+                .into(),
+            )
+            .await;
+        let registry = cx.update(|cx| ThemeRegistry::global(cx));
+        registry
+            .load_user_theme(Path::new("/themes/test.json"), app_state.fs.clone())
+            .await
+            .unwrap();
+
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let original_theme = cx.update(|cx| cx.theme().clone());
+        assert_eq!(original_theme.name, "One Dark");
+
+        let picker = open_theme_selector(&workspace, cx);
+        picker.update(cx, |picker, cx| {
+            assert_eq!(
+                picker.delegate.matches[picker.delegate.selected_index].string,
+                "One Dark"
+            );
+
+            picker.delegate.preview_hovered_theme(1, true, cx);
+            assert_eq!(cx.theme().name, "Test Theme");
+            picker.delegate.preview_hovered_theme(1, false, cx);
+            assert_eq!(cx.theme().name, "One Dark");
+        });
+
+        // Other settings changing while a theme is previewed are kept when the preview is reverted.
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store
+                    .set_user_settings(r#"{ "buffer_font_size": 20 }"#, cx)
+                    .unwrap();
+            });
+        });
+        picker.update(cx, |picker, cx| {
+            picker.delegate.preview_hovered_theme(1, true, cx);
+            assert_eq!(cx.theme().name, "Test Theme");
+        });
+
+        cx.dispatch_action(menu::Cancel);
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<ThemeSelector>(cx).is_none());
+        });
+        cx.update(|cx| {
+            assert!(Arc::ptr_eq(cx.theme(), &original_theme));
+            assert_eq!(ThemeSettings::get_global(cx).buffer_font_size, px(20.));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_hover_preview_follows_the_mouse(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_file(
+                "/themes/test.json",
+                r#"{
+                    "name": "Test",
+                    "author": "Zed",
+                    "themes": [
+                        { "name": "Test Theme A", "appearance": "dark", "style": {} },
+                        { "name": "Test Theme B", "appearance": "dark", "style": {} }
+                    ]
+                }"#
+                .into(),
+            )
+            .await;
+        let registry = cx.update(|cx| ThemeRegistry::global(cx));
+        registry
+            .load_user_theme(Path::new("/themes/test.json"), app_state.fs.clone())
+            .await
+            .unwrap();
+
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        open_theme_selector(&workspace, cx);
+        cx.update(|cx| cx.refresh());
+        let first_row = cx.debug_bounds("THEME_ITEM-Test Theme A").unwrap();
+        let second_row = cx.debug_bounds("THEME_ITEM-Test Theme B").unwrap();
+        assert!(first_row.center().y < second_row.center().y);
+
+        cx.simulate_mouse_move(first_row.center(), None, Modifiers::none());
+        cx.run_until_parked();
+        cx.update(|cx| assert_eq!(cx.theme().name, "Test Theme A"));
+
+        // Moving down to the next row keeps previewing the row under the mouse, even though
+        // the previous row's hover ends after the next one's starts.
+        cx.simulate_mouse_move(second_row.center(), None, Modifiers::none());
+        cx.run_until_parked();
+        cx.update(|cx| assert_eq!(cx.theme().name, "Test Theme B"));
+
+        cx.simulate_mouse_move(first_row.center(), None, Modifiers::none());
+        cx.run_until_parked();
+        cx.update(|cx| assert_eq!(cx.theme().name, "Test Theme A"));
+
+        cx.simulate_mouse_move(point(px(0.), px(0.)), None, Modifiers::none());
+        cx.run_until_parked();
+        cx.update(|cx| assert_eq!(cx.theme().name, "One Dark"));
+    }
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            super::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            state
+        })
+    }
+
+    #[track_caller]
+    fn open_theme_selector(
+        workspace: &View<Workspace>,
+        cx: &mut VisualTestContext,
+    ) -> View<Picker<ThemeSelectorDelegate>> {
+        cx.dispatch_action(Toggle::default());
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<ThemeSelector>(cx)
+                .expect("theme selector is not open")
+                .read(cx)
+                .picker
+                .clone()
+        })
+    }
+}
@@ -12,6 +12,7 @@ use smallvec::SmallVec;
 pub struct Facepile {
     base: Div,
     faces: SmallVec<[AnyElement; 2]>,
+    max_faces: Option<usize>,
 }
 
 impl Facepile {
@@ -20,7 +21,33 @@ impl Facepile {
     }
 
     pub fn new(faces: SmallVec<[AnyElement; 2]>) -> Self {
-        Self { base: div(), faces }
+        Self {
+            base: div(),
+            faces,
+            max_faces: None,
+        }
+    }
+
+    /// Shows at most `max_faces` faces, followed by a "+N" label counting the faces left out.
+    pub fn max_faces(mut self, max_faces: usize) -> Self {
+        self.max_faces = Some(max_faces);
+        self
+    }
+
+    fn visible_faces(&mut self) -> SmallVec<[AnyElement; 2]> {
+        let mut faces = std::mem::take(&mut self.faces);
+        if let Some(max_faces) = self.max_faces {
+            let hidden_count = faces.len().saturating_sub(max_faces);
+            if hidden_count > 0 {
+                faces.truncate(max_faces);
+                faces.push(
+                    Label::new(format!("+{hidden_count}"))
+                        .ml_2()
+                        .into_any_element(),
+                );
+            }
+        }
+        faces
     }
 }
 
@@ -42,7 +69,9 @@ impl Facepile {
 }
 
 impl RenderOnce for Facepile {
-    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+    fn render(mut self, _cx: &mut WindowContext) -> impl IntoElement {
+        let faces = self.visible_faces();
+
         // Lay the faces out in reverse so they overlap in the desired order (left to right, front to back)
         self.base
             .flex()
@@ -50,7 +79,7 @@ impl RenderOnce for Facepile {
             .items_center()
             .justify_start()
             .children(
-                self.faces
+                faces
                     .into_iter()
                     .enumerate()
                     .rev()
@@ -58,3 +87,27 @@ impl RenderOnce for Facepile {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn faces(count: usize) -> SmallVec<[AnyElement; 2]> {
+        (0..count).map(|_| div().into_any_element()).collect()
+    }
+
+    #[test]
+    fn test_max_faces() {
+        assert_eq!(Facepile::new(faces(8)).visible_faces().len(), 8);
+        assert_eq!(
+            Facepile::new(faces(3)).max_faces(3).visible_faces().len(),
+            3
+        );
+
+        // Three faces, plus a label for the remaining five.
+        assert_eq!(
+            Facepile::new(faces(8)).max_faces(3).visible_faces().len(),
+            4
+        );
+    }
+}
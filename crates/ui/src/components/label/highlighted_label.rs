@@ -65,6 +65,10 @@ impl LabelCommon for HighlightedLabel {
         self.base = self.base.underline(underline);
         self
     }
+    fn truncate(mut self) -> Self {
+        self.base = self.base.truncate();
+        self
+    }
 }
 
 pub fn highlight_ranges(
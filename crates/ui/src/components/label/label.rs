@@ -177,6 +177,20 @@ impl LabelCommon for Label {
         self.base = self.base.underline(underline);
         self
     }
+
+    /// Truncates overflowing text with an ellipsis (`…`) if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ui::prelude::*;
+    ///
+    /// let my_label = Label::new("Hello, World!").truncate();
+    /// ```
+    fn truncate(mut self) -> Self {
+        self.base = self.base.truncate();
+        self
+    }
 }
 
 impl RenderOnce for Label {
@@ -189,3 +203,60 @@ impl RenderOnce for Label {
         self.base.child(target_label)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::TestAppContext;
+    use settings::SettingsStore;
+
+    use crate::prelude::*;
+
+    struct LabelInRow {
+        truncate: bool,
+    }
+
+    impl Render for LabelInRow {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let label = Label::new("a_very_long_github_login");
+            h_flex()
+                .w(px(80.))
+                .font_family("Zed Plex Mono")
+                .child(if self.truncate {
+                    label.truncate()
+                } else {
+                    label
+                })
+                .child(div().debug_selector(|| "AFTER-LABEL".into()).size(px(1.)))
+        }
+    }
+
+    #[gpui::test]
+    fn test_truncate(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            theme::init(theme::LoadThemes::JustBase, cx);
+        });
+        cx.text_system()
+            .add_fonts(vec![std::fs::read(
+                "../../assets/fonts/plex-mono/ZedPlexMono-Regular.ttf",
+            )
+            .unwrap()
+            .into()])
+            .unwrap();
+
+        let (_, cx) = cx.add_window_view(|_| LabelInRow { truncate: false });
+        let after_label = cx.debug_bounds("AFTER-LABEL").unwrap();
+        assert!(
+            after_label.origin.x > px(80.),
+            "an untruncated label should overflow its row"
+        );
+
+        let (_, cx) = cx.add_window_view(|_| LabelInRow { truncate: true });
+        let after_label = cx.debug_bounds("AFTER-LABEL").unwrap();
+        assert!(
+            after_label.origin.x < px(80.),
+            "a truncated label should shrink to fit its row"
+        );
+    }
+}
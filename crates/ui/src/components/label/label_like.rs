@@ -49,6 +49,9 @@ pub trait LabelCommon {
 
     /// Sets the alpha property of the label, overwriting the alpha value of the color.
     fn alpha(self, alpha: f32) -> Self;
+
+    /// Truncates overflowing text with an ellipsis (`…`) if needed.
+    fn truncate(self) -> Self;
 }
 
 #[derive(IntoElement)]
@@ -63,6 +66,7 @@ pub struct LabelLike {
     children: SmallVec<[AnyElement; 2]>,
     alpha: Option<f32>,
     underline: bool,
+    truncate: bool,
 }
 
 impl Default for LabelLike {
@@ -84,6 +88,7 @@ impl LabelLike {
             children: SmallVec::new(),
             alpha: None,
             underline: false,
+            truncate: false,
         }
     }
 }
@@ -139,6 +144,11 @@ impl LabelCommon for LabelLike {
         self.alpha = Some(alpha);
         self
     }
+
+    fn truncate(mut self) -> Self {
+        self.truncate = true;
+        self
+    }
 }
 
 impl ParentElement for LabelLike {
@@ -177,6 +187,9 @@ impl RenderOnce for LabelLike {
                 this.line_height(relative(1.))
             })
             .when(self.italic, |this| this.italic())
+            .when(self.truncate, |this| {
+                this.overflow_x_hidden().text_ellipsis()
+            })
             .when(self.underline, |mut this| {
                 this.text_style()
                     .get_or_insert_with(Default::default)
@@ -188,6 +188,8 @@ pub trait Item: FocusableView + EventEmitter<Self::Event> {
     fn to_item_events(_event: &Self::Event, _f: impl FnMut(ItemEvent)) {}
 
     fn deactivated(&mut self, _: &mut ViewContext<Self>) {}
+    /// Called after the item has been closed and removed from its pane.
+    fn on_removed(&mut self, _: &mut ViewContext<Self>) {}
     fn discarded(&self, _project: Model<Project>, _cx: &mut ViewContext<Self>) {}
     fn workspace_deactivated(&mut self, _: &mut ViewContext<Self>) {}
     fn navigate(&mut self, _: Box<dyn Any>, _: &mut ViewContext<Self>) -> bool {
@@ -399,6 +401,7 @@ pub trait ItemHandle: 'static + Send {
         cx: &mut ViewContext<Workspace>,
     );
     fn deactivated(&self, cx: &mut WindowContext);
+    fn on_removed(&self, cx: &mut WindowContext);
     fn discarded(&self, project: Model<Project>, cx: &mut WindowContext);
     fn workspace_deactivated(&self, cx: &mut WindowContext);
     fn navigate(&self, data: Box<dyn Any>, cx: &mut WindowContext) -> bool;
@@ -748,6 +751,10 @@ impl<T: Item> ItemHandle for View<T> {
         self.update(cx, |this, cx| this.deactivated(cx));
     }
 
+    fn on_removed(&self, cx: &mut WindowContext) {
+        self.update(cx, |this, cx| this.on_removed(cx));
+    }
+
     fn workspace_deactivated(&self, cx: &mut WindowContext) {
         self.update(cx, |this, cx| this.workspace_deactivated(cx));
     }
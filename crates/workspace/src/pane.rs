@@ -1463,6 +1463,7 @@ impl Pane {
         self.nav_history.set_mode(NavigationMode::ClosingItem);
         item.deactivated(cx);
         self.nav_history.set_mode(mode);
+        item.on_removed(cx);
 
         if self.is_active_preview_item(item.item_id()) {
             self.set_preview_item_id(None, cx);
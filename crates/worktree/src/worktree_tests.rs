@@ -153,6 +153,62 @@ async fn test_circular_symlinks(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_self_referential_symlinks(cx: &mut TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        "/root",
+        json!({
+            "dir": {
+                "file.txt": ""
+            }
+        }),
+    )
+    .await;
+    fs.insert_symlink("/root/link-to-dir", "dir".into()).await;
+    fs.insert_symlink("/root/self", "self".into()).await;
+    fs.insert_symlink("/root/loop-a", "loop-b".into()).await;
+    fs.insert_symlink("/root/loop-b", "loop-a".into()).await;
+
+    let tree = Worktree::local(
+        Path::new("/root"),
+        true,
+        fs.clone(),
+        Default::default(),
+        &mut cx.to_async(),
+    )
+    .await
+    .unwrap();
+
+    cx.read(|cx| tree.read(cx).as_local().unwrap().scan_complete())
+        .await;
+
+    // Symlinks to directories are followed, while symlinks that never resolve
+    // are skipped rather than scanned forever.
+    tree.read_with(cx, |tree, _| {
+        assert_eq!(
+            tree.entries(false, 0)
+                .map(|entry| entry.path.as_ref())
+                .collect::<Vec<_>>(),
+            vec![
+                Path::new(""),
+                Path::new("dir"),
+                Path::new("dir/file.txt"),
+                Path::new("link-to-dir"),
+                Path::new("link-to-dir/file.txt"),
+            ]
+        );
+        assert_eq!(
+            tree.entry_for_path("link-to-dir")
+                .unwrap()
+                .canonical_path
+                .as_deref(),
+            Some(Path::new("/root/dir"))
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_symlinks_pointing_outside(cx: &mut TestAppContext) {
     init_test(cx);
@@ -412,7 +412,7 @@ fn main() {
         );
         recent_projects::init(cx);
 
-        load_embedded_fonts(cx);
+        load_embedded_fonts(cx).log_err();
 
         #[cfg(target_os = "linux")]
         crate::zed::linux_prompts::init(cx);
@@ -1131,28 +1131,29 @@ fn parse_url_arg(arg: &str, cx: &AppContext) -> Result<String> {
     }
 }
 
-fn load_embedded_fonts(cx: &AppContext) {
+fn load_embedded_fonts(cx: &AppContext) -> Result<()> {
     let asset_source = cx.asset_source();
-    let font_paths = asset_source.list("fonts").unwrap();
+    let font_paths = asset_source.list("fonts")?;
     let embedded_fonts = Mutex::new(Vec::new());
     let executor = cx.background_executor();
 
-    executor.block(executor.scoped(|scope| {
+    executor.block(executor.try_scoped(|scope| {
         for font_path in &font_paths {
             if !font_path.ends_with(".ttf") {
                 continue;
             }
 
-            scope.spawn(async {
-                let font_bytes = asset_source.load(font_path).unwrap().unwrap();
+            scope.try_spawn(async {
+                let font_bytes = asset_source
+                    .load(font_path)?
+                    .with_context(|| format!("missing embedded font {font_path:?}"))?;
                 embedded_fonts.lock().push(font_bytes);
+                Ok(())
             });
         }
-    }));
+    }))?;
 
-    cx.text_system()
-        .add_fonts(embedded_fonts.into_inner())
-        .unwrap();
+    cx.text_system().add_fonts(embedded_fonts.into_inner())
 }
 
 /// Spawns a background task to load the user themes from the themes directory.
@@ -1191,31 +1192,7 @@ fn load_user_themes_in_background(fs: Arc<dyn fs::Fs>, cx: &mut AppContext) {
 
 /// Spawns a background task to watch the themes directory for changes.
 fn watch_themes(fs: Arc<dyn fs::Fs>, cx: &mut AppContext) {
-    use std::time::Duration;
-    cx.spawn(|cx| async move {
-        let (mut events, _) = fs
-            .watch(paths::themes_dir(), Duration::from_millis(100))
-            .await;
-
-        while let Some(paths) = events.next().await {
-            for event in paths {
-                if fs.metadata(&event.path).await.ok().flatten().is_some() {
-                    if let Some(theme_registry) =
-                        cx.update(|cx| ThemeRegistry::global(cx).clone()).log_err()
-                    {
-                        if let Some(()) = theme_registry
-                            .load_user_theme(&event.path, fs.clone())
-                            .await
-                            .log_err()
-                        {
-                            cx.update(ThemeSettings::reload_current_theme).log_err();
-                        }
-                    }
-                }
-            }
-        }
-    })
-    .detach()
+    ThemeRegistry::watch_user_themes(paths::themes_dir().clone(), fs, cx).detach()
 }
 
 #[cfg(debug_assertions)]
@@ -1126,12 +1126,14 @@ mod tests {
     use super::*;
     use assets::Assets;
     use collections::HashSet;
-    use editor::{display_map::DisplayRow, scroll::Autoscroll, DisplayPoint, Editor};
+    use editor::{
+        display_map::DisplayRow, scroll::Autoscroll, DisplayPoint, Editor, EditorSettings,
+    };
     use gpui::{
         actions, Action, AnyWindowHandle, AppContext, AssetSource, BorrowAppContext, Entity,
         SemanticVersion, TestAppContext, UpdateGlobal, VisualTestContext, WindowHandle,
     };
-    use language::{LanguageMatcher, LanguageRegistry};
+    use language::{LanguageMatcher, LanguageRegistry, Point};
     use project::{project_settings::ProjectSettings, Project, ProjectPath, WorktreeSettings};
     use serde_json::json;
     use settings::{handle_settings_file_changes, watch_config_file, SettingsStore};
@@ -1143,7 +1145,7 @@ mod tests {
     use workspace::{
         item::{Item, ItemHandle},
         open_new, open_paths, pane, NewFile, OpenVisible, SaveIntent, SplitDirection,
-        WorkspaceHandle,
+        WorkspaceHandle, WorkspaceId,
     };
 
     #[gpui::test]
@@ -1723,6 +1725,24 @@ mod tests {
             .unwrap();
     }
 
+    #[gpui::test]
+    async fn test_open_browser(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        cx.update(|cx| open_new(Default::default(), app_state.clone(), cx, |_, _| {}))
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        let window = cx.update(|cx| cx.windows()[0]);
+        cx.dispatch_action(
+            window,
+            OpenBrowser {
+                url: "https://zed.dev/".into(),
+            },
+        );
+        assert_eq!(cx.opened_url(), Some("https://zed.dev/".to_string()));
+    }
+
     #[gpui::test]
     async fn test_open_entry(cx: &mut TestAppContext) {
         let app_state = init_test(cx);
@@ -3123,6 +3143,302 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_reopening_closed_items_restores_cursor(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(
+                "/root",
+                json!({
+                    "a": {
+                        "file1": "contents 1\n".repeat(20),
+                        "file2": "contents 2\n".repeat(20),
+                    },
+                }),
+            )
+            .await;
+
+        let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+        let workspace = cx.add_window(|cx| Workspace::test_new(project, cx));
+        let pane = workspace
+            .read_with(cx, |workspace, _| workspace.active_pane().clone())
+            .unwrap();
+
+        let entries = cx.update(|cx| workspace.root(cx).unwrap().file_project_paths(cx));
+        let file1 = entries[0].clone();
+        let file2 = entries[1].clone();
+
+        let mut item_ids = Vec::new();
+        for (file, row) in [(file1.clone(), 5), (file2.clone(), 12)] {
+            let editor = workspace
+                .update(cx, |w, cx| w.open_path(file, None, true, cx))
+                .unwrap()
+                .await
+                .unwrap()
+                .downcast::<Editor>()
+                .unwrap();
+            workspace
+                .update(cx, |_, cx| {
+                    editor.update(cx, |editor, cx| {
+                        editor.change_selections(Some(Autoscroll::fit()), cx, |s| {
+                            s.select_display_ranges([DisplayPoint::new(DisplayRow(row), 3)
+                                ..DisplayPoint::new(DisplayRow(row), 3)])
+                        });
+                    });
+                })
+                .unwrap();
+            item_ids.push(editor.item_id());
+        }
+
+        for item_id in item_ids {
+            workspace
+                .update(cx, |_, cx| {
+                    pane.update(cx, |pane, cx| {
+                        pane.close_item_by_id(item_id, SaveIntent::Close, cx)
+                    })
+                })
+                .unwrap()
+                .await
+                .unwrap();
+        }
+        assert!(workspace
+            .read_with(cx, |workspace, cx| workspace.active_item(cx).is_none())
+            .unwrap());
+
+        // Items are reopened in the reverse order in which they were closed,
+        // with the cursor where it was left.
+        workspace
+            .update(cx, Workspace::reopen_closed_item)
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(
+            active_cursor(&workspace, cx),
+            (file2, DisplayPoint::new(DisplayRow(12), 3))
+        );
+
+        workspace
+            .update(cx, Workspace::reopen_closed_item)
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(
+            active_cursor(&workspace, cx),
+            (file1, DisplayPoint::new(DisplayRow(5), 3))
+        );
+
+        fn active_cursor(
+            workspace: &WindowHandle<Workspace>,
+            cx: &mut TestAppContext,
+        ) -> (ProjectPath, DisplayPoint) {
+            workspace
+                .update(cx, |workspace, cx| {
+                    let item = workspace.active_item(cx).unwrap();
+                    let editor = item.downcast::<Editor>().unwrap();
+                    let selections =
+                        editor.update(cx, |editor, cx| editor.selections.display_ranges(cx));
+                    (item.project_path(cx).unwrap(), selections[0].head())
+                })
+                .unwrap()
+        }
+    }
+
+    #[gpui::test]
+    async fn test_restoring_cursor_position_when_reopening_file(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(
+                "/root",
+                json!({ "a.txt": "contents\n".repeat(50), "b.txt": "" }),
+            )
+            .await;
+
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        let workspace = open_persisted_workspace(workspace_id, &app_state, &["/root"], cx).await;
+        let files = cx.update(|cx| workspace.root(cx).unwrap().file_project_paths(cx));
+        let (file, other_file) = (files[0].clone(), files[1].clone());
+
+        let editor = open_editor(&workspace, file.clone(), cx).await;
+        set_cursor(&workspace, &editor, Point::new(30, 4), cx);
+        close_editor(&workspace, &editor, cx).await;
+
+        // The position is read back from the database, so it survives a restart.
+        let workspace = open_persisted_workspace(workspace_id, &app_state, &["/root"], cx).await;
+        let editor = open_editor(&workspace, file.clone(), cx).await;
+        assert_eq!(cursor(&workspace, &editor, cx), Point::new(30, 4));
+        let scroll_position = workspace
+            .update(cx, |_, cx| {
+                editor.update(cx, |editor, cx| editor.scroll_position(cx))
+            })
+            .unwrap();
+        assert_eq!(scroll_position, point(0., 25.));
+
+        // Switching to another tab doesn't record the position, only closing does.
+        set_cursor(&workspace, &editor, Point::new(10, 2), cx);
+        open_editor(&workspace, other_file.clone(), cx).await;
+        let other_workspace =
+            open_persisted_workspace(workspace_id, &app_state, &["/root"], cx).await;
+        let other_editor = open_editor(&other_workspace, file.clone(), cx).await;
+        assert_eq!(
+            cursor(&other_workspace, &other_editor, cx),
+            Point::new(30, 4)
+        );
+        close_editor(&workspace, &editor, cx).await;
+
+        // Nothing is restored when the setting is disabled.
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings::<EditorSettings>(cx, |settings| {
+                    settings.restore_cursor_on_reopen = Some(false);
+                });
+            });
+        });
+        let editor = open_editor(&workspace, file, cx).await;
+        assert_eq!(cursor(&workspace, &editor, cx), Point::new(0, 0));
+    }
+
+    #[gpui::test]
+    async fn test_restored_cursor_positions_are_kept_per_worktree(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let contents = "contents\n".repeat(50);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(
+                "/",
+                json!({
+                    "one": { "root": { "a.txt": contents.clone() } },
+                    "two": { "root": { "a.txt": contents } },
+                }),
+            )
+            .await;
+
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        let workspace =
+            open_persisted_workspace(workspace_id, &app_state, &["/one/root", "/two/root"], cx)
+                .await;
+        let files = cx.update(|cx| workspace.root(cx).unwrap().file_project_paths(cx));
+        assert_eq!(files.len(), 2);
+
+        // Both worktrees are named "root", so their files share the same full path.
+        for (file, row) in files.iter().zip([10, 20]) {
+            let editor = open_editor(&workspace, file.clone(), cx).await;
+            set_cursor(&workspace, &editor, Point::new(row, 1), cx);
+            close_editor(&workspace, &editor, cx).await;
+        }
+
+        for (file, row) in files.iter().zip([10, 20]) {
+            let editor = open_editor(&workspace, file.clone(), cx).await;
+            assert_eq!(cursor(&workspace, &editor, cx), Point::new(row, 1));
+        }
+    }
+
+    #[gpui::test]
+    async fn test_restored_cursor_position_is_clamped(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree("/root", json!({ "a.txt": "contents\n".repeat(50) }))
+            .await;
+
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        let workspace = open_persisted_workspace(workspace_id, &app_state, &["/root"], cx).await;
+        let file = cx.update(|cx| workspace.root(cx).unwrap().file_project_paths(cx))[0].clone();
+
+        let editor = open_editor(&workspace, file.clone(), cx).await;
+        set_cursor(&workspace, &editor, Point::new(40, 6), cx);
+        close_editor(&workspace, &editor, cx).await;
+
+        // Shrink the file while it is closed.
+        app_state
+            .fs
+            .as_fake()
+            .insert_file("/root/a.txt", "one\ntwo\nthree".into())
+            .await;
+        cx.run_until_parked();
+
+        let editor = open_editor(&workspace, file, cx).await;
+        assert_eq!(cursor(&workspace, &editor, cx), Point::new(2, 5));
+    }
+
+    async fn open_persisted_workspace(
+        workspace_id: WorkspaceId,
+        app_state: &Arc<AppState>,
+        roots: &[&str],
+        cx: &mut TestAppContext,
+    ) -> WindowHandle<Workspace> {
+        let project = Project::test(app_state.fs.clone(), roots.iter().map(Path::new), cx).await;
+        cx.add_window(|cx| Workspace::new(Some(workspace_id), project, app_state.clone(), cx))
+    }
+
+    async fn open_editor(
+        workspace: &WindowHandle<Workspace>,
+        path: ProjectPath,
+        cx: &mut TestAppContext,
+    ) -> View<Editor> {
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_path(path, None, true, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap()
+    }
+
+    async fn close_editor(
+        workspace: &WindowHandle<Workspace>,
+        editor: &View<Editor>,
+        cx: &mut TestAppContext,
+    ) {
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.close_item_by_id(editor.item_id(), SaveIntent::Close, cx)
+                })
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        cx.run_until_parked();
+    }
+
+    fn set_cursor(
+        workspace: &WindowHandle<Workspace>,
+        editor: &View<Editor>,
+        position: Point,
+        cx: &mut TestAppContext,
+    ) {
+        workspace
+            .update(cx, |_, cx| {
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(None, cx, |s| s.select_ranges([position..position]));
+                    editor.set_scroll_position(point(0., position.row as f32 - 5.), cx);
+                })
+            })
+            .unwrap();
+    }
+
+    fn cursor(
+        workspace: &WindowHandle<Workspace>,
+        editor: &View<Editor>,
+        cx: &mut TestAppContext,
+    ) -> Point {
+        workspace
+            .update(cx, |_, cx| {
+                editor.update(cx, |editor, cx| {
+                    editor.selections.newest::<Point>(cx).head()
+                })
+            })
+            .unwrap()
+    }
+
     fn init_keymap_test(cx: &mut TestAppContext) -> Arc<AppState> {
         cx.update(|cx| {
             let app_state = AppState::test(cx);